@@ -1,20 +1,64 @@
+use inflector::cases::camelcase::to_camel_case;
+use inflector::cases::kebabcase::to_kebab_case;
+use inflector::cases::pascalcase::to_pascal_case;
+use inflector::cases::screamingsnakecase::to_screaming_snake_case;
+use inflector::cases::snakecase::to_snake_case;
 use quote::ToTokens;
 use syn::punctuated::Punctuated;
 
 pub const BSON: &str = "bson";
+pub const CAPPED: &str = "capped";
+pub const COLLATION: &str = "collation";
 pub const COLLECTION: &str = "collection";
+pub const COLLECTION_FN: &str = "collection_fn";
+pub const CONTENT: &str = "content";
+pub const DEFAULT: &str = "default";
 pub const FIELD: &str = "field";
+pub const FIELDS: &str = "fields";
+pub const FLUENT: &str = "fluent";
 pub const FILTER: &str = "filter";
 pub const FROM: &str = "from";
+pub const ID: &str = "id";
+pub const INDEX: &str = "index";
 pub const INTO: &str = "into";
+pub const JSON_SCHEMA: &str = "json_schema";
+pub const LENGTH: &str = "length";
+pub const MAX: &str = "max";
+pub const MIN: &str = "min";
+pub const MODULE: &str = "module";
 pub const MONGO: &str = "mongo";
+pub const NAME: &str = "name";
+pub const NESTED: &str = "nested";
+pub const OPTIONS: &str = "options";
 pub const OID: &str = "oid";
+pub const QUERY_STRING: &str = "query_string";
+pub const REFERENCES: &str = "references";
+pub const REGEX: &str = "regex";
+pub const RENAME: &str = "rename";
+pub const REPR: &str = "repr";
+pub const SCALAR: &str = "scalar";
 pub const SERDE: &str = "serde";
+pub const SIZE: &str = "size";
 pub const SKIP: &str = "skip";
+pub const SKIP_FIELD: &str = "skip_field";
+pub const SKIP_FILTER: &str = "skip_filter";
+pub const SKIP_NONE: &str = "skip_none";
+pub const SKIP_UPDATE: &str = "skip_update";
+pub const SPARSE: &str = "sparse";
+pub const TAG: &str = "tag";
+pub const TYPES: &str = "types";
+pub const UNIQUE: &str = "unique";
+pub const UNTAGGED: &str = "untagged";
 pub const UPDATE: &str = "update";
+pub const UPDATER: &str = "updater";
+pub const VARIANT_CASE: &str = "variant_case";
+pub const VALIDATE: &str = "validate";
+pub const VIEW: &str = "view";
+pub const WITH: &str = "with";
 
 pub struct Container<'a> {
     pub ident: syn::Ident,
+    pub generics: &'a syn::Generics,
 
     pub attrs: attr::Container,
     pub data: Data<'a>,
@@ -28,6 +72,7 @@ pub enum Data<'a> {
 pub struct Variant<'a> {
     pub ident: syn::Ident,
 
+    pub attrs: attr::Variant,
     pub fields: Vec<Field<'a>>,
     pub style: Style,
 }
@@ -85,10 +130,82 @@ impl<'a> Container<'a> {
         if !errors.is_empty() {
             return Err(errors);
         }
+        let attrs = attrs.expect("could not get attributes");
+        let mut data = data.expect("could not get data");
+        if let (Some(_), Data::Enum(variants)) = (&attrs.repr, &data) {
+            for variant in variants {
+                if variant.style != Style::Unit {
+                    errors.push(syn::Error::new_spanned(
+                        &variant.ident,
+                        "bson container attribute `repr` can only be used on enums whose variants are all unit variants",
+                    ));
+                } else if variant.attrs.rename.is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        &variant.ident,
+                        "bson variant attribute `rename` cannot be used with `repr`",
+                    ));
+                }
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+        // Variants without their own `rename` are renamed to the container's `variant_case`
+        if let (Some(case), Data::Enum(variants)) = (attrs.variant_case, &mut data) {
+            for variant in variants {
+                let ident = variant.ident.to_string();
+                variant
+                    .attrs
+                    .rename
+                    .get_or_insert_with(|| case.apply(&ident));
+            }
+        }
+        if attrs.skip_none {
+            let fields = match &mut data {
+                Data::Enum(variants) => variants
+                    .iter_mut()
+                    .flat_map(|v| v.fields.iter_mut())
+                    .collect::<Vec<_>>(),
+                Data::Struct(_, fields) => fields.iter_mut().collect(),
+            };
+            for field in fields {
+                field.attrs.skip_none = true;
+            }
+        }
+        if let Data::Struct(_, fields) = &mut data {
+            let ids = fields.iter().filter(|f| f.attrs.id).collect::<Vec<_>>();
+            if ids.len() > 1 {
+                return Err(ids
+                    .iter()
+                    .map(|f| {
+                        syn::Error::new_spanned(
+                            f.raw.into_token_stream(),
+                            "mongo field attribute `id` can only be used once",
+                        )
+                    })
+                    .collect());
+            }
+            // With `oid` the `id` field holds the document's `_id`, which is already filtered by
+            // the derived `_id` and cannot be updated, unless another field is the `_id`
+            if attrs.oid && ids.is_empty() {
+                for field in fields.iter_mut() {
+                    if matches!(&field.member, syn::Member::Named(name) if name == "id" || name == "_id")
+                    {
+                        field.attrs.rename.get_or_insert_with(|| "_id".to_owned());
+                    }
+                    if field.attrs.rename.as_deref() == Some("_id") {
+                        field.attrs.id = true;
+                        field.attrs.skip_filter = true;
+                        field.attrs.skip_update = true;
+                    }
+                }
+            }
+        }
         Ok(Container {
             ident: input.ident.clone(),
-            attrs: attrs.expect("could not get attributes"),
-            data: data.expect("could not get data"),
+            generics: &input.generics,
+            attrs,
+            data,
         })
     }
 }
@@ -120,6 +237,7 @@ fn enum_from(
             let (style, fields) = data.expect("could not get data");
             Some(Variant {
                 ident: v.ident.clone(),
+                attrs: attrs.expect("could not get attributes"),
                 fields,
                 style,
             })
@@ -165,6 +283,20 @@ fn fields_from(
     Ok(fields)
 }
 
+// Whether any of the fields hold a reference, i.e. `&'a str`
+pub fn has_borrowed_fields(data: &syn::Data) -> bool {
+    let borrowed = |fields: &syn::Fields| {
+        fields
+            .iter()
+            .any(|f| matches!(f.ty, syn::Type::Reference(_)))
+    };
+    match data {
+        syn::Data::Enum(data) => data.variants.iter().any(|v| borrowed(&v.fields)),
+        syn::Data::Struct(data) => borrowed(&data.fields),
+        syn::Data::Union(_) => false,
+    }
+}
+
 fn struct_from(fields: &syn::Fields) -> Result<(Style, Vec<Field<'_>>), Vec<syn::Error>> {
     Ok(match fields {
         syn::Fields::Named(fields) => {
@@ -202,21 +334,148 @@ pub mod attr {
         }
     }
 
+    // The case that variant names are converted to, using the same names as serde's `rename_all`
+    #[derive(Clone, Copy)]
+    pub enum VariantCase {
+        Camel,
+        Kebab,
+        Lower,
+        Pascal,
+        ScreamingKebab,
+        ScreamingSnake,
+        Snake,
+        Upper,
+    }
+
+    impl std::str::FromStr for VariantCase {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "camelCase" => Ok(Self::Camel),
+                "kebab-case" => Ok(Self::Kebab),
+                "lowercase" => Ok(Self::Lower),
+                "PascalCase" => Ok(Self::Pascal),
+                "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+                "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+                "snake_case" => Ok(Self::Snake),
+                "UPPERCASE" => Ok(Self::Upper),
+                _ => Err(format!("unknown `VariantCase` variant {}", s)),
+            }
+        }
+    }
+
+    impl VariantCase {
+        pub fn apply(self, name: &str) -> String {
+            match self {
+                Self::Camel => to_camel_case(name),
+                Self::Kebab => to_kebab_case(name),
+                Self::Lower => name.to_lowercase(),
+                Self::Pascal => to_pascal_case(name),
+                Self::ScreamingKebab => to_kebab_case(name).to_uppercase(),
+                Self::ScreamingSnake => to_screaming_snake_case(name),
+                Self::Snake => to_snake_case(name),
+                Self::Upper => name.to_uppercase(),
+            }
+        }
+    }
+
+    // How the variants of data-carrying enums are represented in BSON
+    pub enum Tagging {
+        Adjacent { tag: String, content: String },
+        Internal(String),
+        Untagged,
+    }
+
+    // An index declared using `#[mongo(index(...))]`, where the fields are `(name, descending)`
+    pub struct Index {
+        pub fields: Vec<(syn::Ident, bool)>,
+        pub name: Option<String>,
+        pub sparse: bool,
+        pub unique: bool,
+    }
+
+    // The collection's creation options declared using `#[mongo(options(...))]`
+    #[derive(Default)]
+    pub struct CreateOptions {
+        pub capped: Option<syn::LitBool>,
+        pub collation: Option<syn::LitStr>,
+        pub max: Option<syn::Expr>,
+        pub size: Option<syn::Expr>,
+    }
+
+    // The names of the companion types declared using `#[mongo(types(...))]`
+    #[derive(Default)]
+    pub struct Types {
+        pub field: Option<syn::Ident>,
+        pub fields: Option<syn::Ident>,
+        pub filter: Option<syn::Ident>,
+        pub update: Option<syn::Ident>,
+        pub updater: Option<syn::Ident>,
+    }
+
+    // The checks declared using `#[mongo(validate(...))]`
+    #[derive(Default)]
+    pub struct Validate {
+        pub min: Option<syn::Expr>,
+        pub max: Option<syn::Expr>,
+        pub length: Option<(Option<syn::Expr>, Option<syn::Expr>)>,
+        pub regex: Option<syn::LitStr>,
+    }
+
+    // A view declared using `#[mongo(view(Summary = [foo, bar]))]`
+    pub struct View {
+        pub name: syn::Ident,
+        pub fields: Vec<syn::Ident>,
+    }
+
     pub struct Container {
         pub bson: BsonMode,
         pub collection: Option<String>,
+        pub collection_fn: Option<syn::ExprPath>,
         pub field: bool,
         pub filter: bool,
+        pub fluent: bool,
         pub from: bool,
+        pub indexes: Vec<Index>,
         pub into: bool,
+        pub json_schema: bool,
+        pub module: Option<syn::Ident>,
         pub oid: bool,
+        pub options: Option<CreateOptions>,
+        pub query_string: bool,
+        pub repr: Option<syn::Ident>,
+        pub scalar: bool,
+        pub skip_none: bool,
+        pub tagging: Option<Tagging>,
+        pub types: Option<Types>,
         pub update: bool,
+        pub variant_case: Option<VariantCase>,
+        pub views: Vec<View>,
     }
+    pub enum DefaultValue {
+        Path(syn::ExprPath),
+        Trait,
+    }
+
     pub struct Field {
+        pub default: Option<DefaultValue>,
+        pub filter: bool,
+        pub id: bool,
+        pub nested: bool,
+        pub references: Option<syn::Type>,
+        pub rename: Option<String>,
         pub serde: bool,
-        pub skip: bool,
+        pub skip_field: bool,
+        pub skip_filter: bool,
+        pub skip_none: bool,
+        pub skip_update: bool,
+        pub validate: Option<Validate>,
+        pub with: Option<syn::ExprPath>,
+    }
+    pub struct Variant {
+        pub rename: Option<String>,
     }
-    pub struct Variant {}
 
     impl Container {
         pub fn from(item: &syn::DeriveInput) -> Result<Self, Vec<syn::Error>> {
@@ -224,12 +483,29 @@ pub mod attr {
 
             let mut bson = BsonMode::Convert;
             let mut collection = None;
+            let mut collection_fn = None;
             let mut field = false;
             let mut filter = false;
+            let mut fluent = false;
             let mut from = false;
+            let mut indexes = vec![];
             let mut into = false;
+            let mut json_schema = false;
+            let mut module = None;
             let mut oid = false;
+            let mut options = None;
+            let mut query_string = false;
+            let mut repr = None;
+            let mut scalar = false;
+            let mut skip_none = false;
+            let mut types = None;
             let mut update = false;
+            let mut variant_case = None;
+            let mut views = vec![];
+
+            let mut content = None;
+            let mut tag = None;
+            let mut untagged = false;
 
             for attr in &item.attrs {
                 if !attr.path().is_ident(BSON) {
@@ -243,12 +519,60 @@ pub mod attr {
                 }
 
                 if let Err(err) = attr.parse_nested_meta(|meta| {
+                    // Parse `#[bson(content = "foo")]`
+                    if meta.path.is_ident(CONTENT) {
+                        match get_lit_str(CONTENT, &meta) {
+                            Ok(s) => content = Some(s.value()),
+                            Err(e) => errors.push(e),
+                        }
                     // Parse `#[bson(from)]`
-                    if meta.path.is_ident(FROM) {
+                    } else if meta.path.is_ident(FROM) {
                         from = true;
                     // Parse `#[bson(from)]`
                     } else if meta.path.is_ident(INTO) {
                         into = true;
+                    // Parse `#[bson(repr = "i32")]`
+                    } else if meta.path.is_ident(REPR) {
+                        match get_lit_str(REPR, &meta) {
+                            Ok(s) if s.value() == "i32" || s.value() == "i64" => {
+                                repr = Some(syn::Ident::new(&s.value(), s.span()))
+                            }
+                            Ok(s) => errors.push(syn::Error::new_spanned(
+                                meta.path.clone(),
+                                format!("unknown bson container attribute value `{}`", s.value()),
+                            )),
+                            Err(e) => errors.push(e),
+                        }
+                    // Parse `#[bson(scalar)]`
+                    } else if meta.path.is_ident(SCALAR) {
+                        scalar = true;
+                    // Parse `#[bson(skip_none)]`
+                    } else if meta.path.is_ident(SKIP_NONE) {
+                        skip_none = true;
+                    // Parse `#[bson(tag = "foo")]`
+                    } else if meta.path.is_ident(TAG) {
+                        match get_lit_str(TAG, &meta) {
+                            Ok(s) => tag = Some(s.value()),
+                            Err(e) => errors.push(e),
+                        }
+                    // Parse `#[bson(untagged)]`
+                    } else if meta.path.is_ident(UNTAGGED) {
+                        untagged = true;
+                    // Parse `#[bson(variant_case = "SCREAMING_SNAKE_CASE")]`
+                    } else if meta.path.is_ident(VARIANT_CASE) {
+                        match get_lit_str(VARIANT_CASE, &meta) {
+                            Ok(s) => match str::parse::<VariantCase>(&s.value()) {
+                                Ok(x) => variant_case = Some(x),
+                                Err(_) => errors.push(syn::Error::new_spanned(
+                                    meta.path.clone(),
+                                    format!(
+                                        "unknown bson container attribute value `{}`",
+                                        s.value()
+                                    ),
+                                )),
+                            },
+                            Err(e) => errors.push(e),
+                        }
                     } else {
                         let path = meta.path.to_token_stream().to_string().replace(' ', "");
                         return Err(syn::Error::new_spanned(
@@ -295,18 +619,59 @@ pub mod attr {
                             Ok(s) => collection = Some(s.value()),
                             Err(e) => errors.push(e),
                         }
+                    // Parse `#[mongo(collection_fn = "path")]`
+                    } else if meta.path.is_ident(COLLECTION_FN) {
+                        match get_lit_str(COLLECTION_FN, &meta).and_then(|s| s.parse()) {
+                            Ok(path) => collection_fn = Some(path),
+                            Err(e) => errors.push(e),
+                        }
                     // Parse `#[mongo(field)]`
                     } else if meta.path.is_ident(FIELD) {
                         field = true;
                     // Parse `#[mongo(filter)]`
                     } else if meta.path.is_ident(FILTER) {
                         filter = true;
-                        // Parse `#[mongo(oid)]`
+                    // Parse `#[mongo(fluent)]`
+                    } else if meta.path.is_ident(FLUENT) {
+                        fluent = true;
+                    // Parse `#[mongo(index(fields(foo, -bar), name = "foo", sparse, unique))]`
+                    } else if meta.path.is_ident(INDEX) {
+                        let span = meta.path.clone();
+                        let index = Index::parse(&meta)?;
+                        if index.fields.is_empty() {
+                            errors.push(syn::Error::new_spanned(
+                                span,
+                                "mongo container attribute `index` requires `fields`",
+                            ));
+                        }
+                        indexes.push(index);
+                    // Parse `#[mongo(json_schema)]`
+                    } else if meta.path.is_ident(JSON_SCHEMA) {
+                        json_schema = true;
+                    // Parse `#[mongo(module = "foo")]`
+                    } else if meta.path.is_ident(MODULE) {
+                        match get_lit_str(MODULE, &meta).and_then(|s| s.parse()) {
+                            Ok(ident) => module = Some(ident),
+                            Err(e) => errors.push(e),
+                        }
+                    // Parse `#[mongo(oid)]`
                     } else if meta.path.is_ident(OID) {
                         oid = true;
+                    // Parse `#[mongo(options(capped, size = 1024, collation = "en_ci"))]`
+                    } else if meta.path.is_ident(OPTIONS) {
+                        options = Some(CreateOptions::parse(&meta)?);
+                    // Parse `#[mongo(query_string)]`
+                    } else if meta.path.is_ident(QUERY_STRING) {
+                        query_string = true;
+                    // Parse `#[mongo(types)]` & `#[mongo(types(filter = "FooFilter"))]`
+                    } else if meta.path.is_ident(TYPES) {
+                        types = Some(Types::parse(&meta)?);
                     // Parse `#[mongo(update)]`
                     } else if meta.path.is_ident(UPDATE) {
                         update = true;
+                    // Parse `#[mongo(view(Summary = [foo, bar]))]`
+                    } else if meta.path.is_ident(VIEW) {
+                        views.extend(View::parse(&meta)?);
                     } else {
                         let path = meta.path.to_token_stream().to_string().replace(' ', "");
                         return Err(syn::Error::new_spanned(
@@ -320,8 +685,105 @@ pub mod attr {
                 }
             }
 
+            // The collection's name is only resolved at runtime, so the constant falls back to the
+            // type's name in snake_case
+            if collection_fn.is_some() && collection.is_none() {
+                collection = Some(to_snake_case(&item.ident.to_string()));
+            }
+            if options.is_some() && collection.is_none() {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "mongo container attribute `options` requires `collection`",
+                ));
+            }
+            if !indexes.is_empty() && collection.is_none() {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "mongo container attribute `index` requires `collection`",
+                ));
+            }
+            if module.is_some() && types.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "mongo container attributes `module` & `types` cannot be used together",
+                ));
+            }
+            for (i, view) in views.iter().enumerate() {
+                if views[..i].iter().any(|v| v.name == view.name) {
+                    errors.push(syn::Error::new_spanned(
+                        view.name.clone(),
+                        format!("mongo view `{}` is declared more than once", view.name),
+                    ));
+                }
+            }
+            if query_string && !(field && filter) {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "mongo container attribute `query_string` requires `field` & `filter`",
+                ));
+            }
+            let tagging = match (tag, content, untagged) {
+                (None, None, false) => None,
+                (Some(tag), None, false) => Some(Tagging::Internal(tag)),
+                (Some(tag), Some(content), false) => Some(Tagging::Adjacent { tag, content }),
+                (None, Some(_), false) => {
+                    errors.push(syn::Error::new_spanned(
+                        item.ident.clone(),
+                        "bson container attribute `content` requires `tag`",
+                    ));
+                    None
+                }
+                (None, None, true) => Some(Tagging::Untagged),
+                (_, _, true) => {
+                    errors.push(syn::Error::new_spanned(
+                        item.ident.clone(),
+                        "bson container attribute `untagged` cannot be used with `tag` or `content`",
+                    ));
+                    None
+                }
+            };
+            if tagging.is_some() && !matches!(item.data, syn::Data::Enum(_)) {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attributes `tag`, `content` & `untagged` can only be used on enums",
+                ));
+            }
+            if variant_case.is_some() && !matches!(item.data, syn::Data::Enum(_)) {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attribute `variant_case` can only be used on enums",
+                ));
+            }
+            if repr.is_some() && !matches!(item.data, syn::Data::Enum(_)) {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attribute `repr` can only be used on enums",
+                ));
+            }
+            if repr.is_some() && (tagging.is_some() || variant_case.is_some()) {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attribute `repr` cannot be used with `tag`, `content`, `untagged` or `variant_case`",
+                ));
+            }
+            if scalar
+                && (tagging.is_some() || variant_case.is_some() || repr.is_some() || skip_none)
+            {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attribute `scalar` cannot be used with `tag`, `content`, `untagged`, `variant_case`, `repr` or `skip_none`",
+                ));
+            }
+            // Borrowed fields cannot be converted from BSON, as there is nothing to borrow from
+            let borrowed = has_borrowed_fields(&item.data);
+            if from && borrowed {
+                errors.push(syn::Error::new_spanned(
+                    item.ident.clone(),
+                    "bson container attribute `from` cannot be used with borrowed fields, use `Cow` instead",
+                ));
+            }
             if !from && !into {
-                from = true;
+                from = !borrowed;
                 into = true;
             }
             if !errors.is_empty() {
@@ -330,22 +792,227 @@ pub mod attr {
             Ok(Container {
                 bson,
                 collection,
+                collection_fn,
                 field,
                 filter,
+                fluent,
                 from,
+                indexes,
                 into,
+                json_schema,
+                module,
                 oid,
+                options,
+                query_string,
+                repr,
+                scalar,
+                skip_none,
+                tagging,
+                types,
                 update,
+                variant_case,
+                views,
             })
         }
     }
 
+    impl Index {
+        fn parse(meta: &ParseNestedMeta) -> Result<Self, syn::Error> {
+            let mut fields = vec![];
+            let mut name = None;
+            let mut sparse = false;
+            let mut unique = false;
+            meta.parse_nested_meta(|meta| {
+                // Parse `fields(foo, -bar)`, where `-` sorts the field in descending order
+                if meta.path.is_ident(FIELDS) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    while !content.is_empty() {
+                        let descending = content.parse::<Option<Token![-]>>()?.is_some();
+                        fields.push((content.parse::<syn::Ident>()?, descending));
+                        if content.is_empty() {
+                            break;
+                        }
+                        content.parse::<Token![,]>()?;
+                    }
+                // Parse `name = "foo"`
+                } else if meta.path.is_ident(NAME) {
+                    name = Some(get_lit_str(NAME, &meta)?.value());
+                // Parse `sparse`
+                } else if meta.path.is_ident(SPARSE) {
+                    sparse = true;
+                // Parse `unique`
+                } else if meta.path.is_ident(UNIQUE) {
+                    unique = true;
+                } else {
+                    let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                    return Err(syn::Error::new_spanned(
+                        meta.path,
+                        format!("unknown mongo index attribute `{}`", path),
+                    ));
+                }
+                Ok(())
+            })?;
+            Ok(Index {
+                fields,
+                name,
+                sparse,
+                unique,
+            })
+        }
+    }
+
+    impl CreateOptions {
+        fn parse(meta: &ParseNestedMeta) -> Result<Self, syn::Error> {
+            let mut options = CreateOptions::default();
+            meta.parse_nested_meta(|meta| {
+                // Parse `capped` & `capped = true`
+                if meta.path.is_ident(CAPPED) {
+                    options.capped = Some(if meta.input.peek(Token![=]) {
+                        meta.value()?.parse()?
+                    } else {
+                        syn::LitBool::new(true, syn::spanned::Spanned::span(&meta.path))
+                    });
+                // Parse `collation = "en"`, where a `_ci` suffix makes it case-insensitive
+                } else if meta.path.is_ident(COLLATION) {
+                    options.collation = Some(get_lit_str(COLLATION, &meta)?);
+                // Parse `max = 1000`
+                } else if meta.path.is_ident(MAX) {
+                    options.max = Some(meta.value()?.parse()?);
+                // Parse `size = 1048576`
+                } else if meta.path.is_ident(SIZE) {
+                    options.size = Some(meta.value()?.parse()?);
+                } else {
+                    let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                    return Err(syn::Error::new_spanned(
+                        meta.path,
+                        format!("unknown mongo options attribute `{}`", path),
+                    ));
+                }
+                Ok(())
+            })?;
+            Ok(options)
+        }
+    }
+
+    impl Types {
+        fn parse(meta: &ParseNestedMeta) -> Result<Self, syn::Error> {
+            let mut types = Types::default();
+            // The names are optional, defaulting to the type's name suffixed with the companion's
+            if !meta.input.peek(syn::token::Paren) {
+                return Ok(types);
+            }
+            meta.parse_nested_meta(|meta| {
+                let (name, ident) = if meta.path.is_ident(FIELD) {
+                    (FIELD, &mut types.field)
+                } else if meta.path.is_ident(FIELDS) {
+                    (FIELDS, &mut types.fields)
+                } else if meta.path.is_ident(FILTER) {
+                    (FILTER, &mut types.filter)
+                } else if meta.path.is_ident(UPDATE) {
+                    (UPDATE, &mut types.update)
+                } else if meta.path.is_ident(UPDATER) {
+                    (UPDATER, &mut types.updater)
+                } else {
+                    let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                    return Err(syn::Error::new_spanned(
+                        meta.path,
+                        format!("unknown mongo types attribute `{}`", path),
+                    ));
+                };
+                *ident = Some(get_lit_str(name, &meta)?.parse()?);
+                Ok(())
+            })?;
+            Ok(types)
+        }
+    }
+
+    impl View {
+        fn parse(meta: &ParseNestedMeta) -> Result<Vec<Self>, syn::Error> {
+            let mut views = vec![];
+            meta.parse_nested_meta(|meta| {
+                let name = meta.path.require_ident()?.clone();
+                let input = meta.value()?;
+                let content;
+                syn::bracketed!(content in input);
+                let fields = Punctuated::<syn::Ident, Token![,]>::parse_terminated(&content)?;
+                if fields.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "mongo view requires at least one field",
+                    ));
+                }
+                views.push(View {
+                    name,
+                    fields: fields.into_iter().collect(),
+                });
+                Ok(())
+            })?;
+            Ok(views)
+        }
+    }
+
+    impl Validate {
+        fn parse(meta: &ParseNestedMeta) -> Result<Self, syn::Error> {
+            let mut validate = Validate::default();
+            meta.parse_nested_meta(|meta| {
+                // Parse `min = 0`
+                if meta.path.is_ident(MIN) {
+                    validate.min = Some(meta.value()?.parse()?);
+                // Parse `max = 150`
+                } else if meta.path.is_ident(MAX) {
+                    validate.max = Some(meta.value()?.parse()?);
+                // Parse `length(min = 1, max = 32)`
+                } else if meta.path.is_ident(LENGTH) {
+                    let (mut min, mut max) = (None, None);
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident(MIN) {
+                            min = Some(meta.value()?.parse()?);
+                        } else if meta.path.is_ident(MAX) {
+                            max = Some(meta.value()?.parse()?);
+                        } else {
+                            let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                            return Err(syn::Error::new_spanned(
+                                meta.path,
+                                format!("unknown mongo length attribute `{}`", path),
+                            ));
+                        }
+                        Ok(())
+                    })?;
+                    validate.length = Some((min, max));
+                // Parse `regex = "^[a-z]+$"`
+                } else if meta.path.is_ident(REGEX) {
+                    validate.regex = Some(get_lit_str(REGEX, &meta)?);
+                } else {
+                    let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                    return Err(syn::Error::new_spanned(
+                        meta.path,
+                        format!("unknown mongo validate attribute `{}`", path),
+                    ));
+                }
+                Ok(())
+            })?;
+            Ok(validate)
+        }
+    }
+
     impl Field {
         pub fn from(_index: usize, field: &syn::Field) -> Result<Self, Vec<syn::Error>> {
             let mut errors: Vec<syn::Error> = Vec::new();
 
+            let mut default = None;
+            let mut filter = false;
+            let mut id = false;
+            let mut nested = false;
+            let mut references = None;
+            let mut rename = None;
             let mut serde = false;
-            let mut skip = false;
+            let mut skip_field = false;
+            let mut skip_filter = false;
+            let mut skip_none = false;
+            let mut skip_update = false;
+            let mut validate = None;
+            let mut with = None;
 
             for attr in &field.attrs {
                 if !attr.path().is_ident(BSON) {
@@ -359,9 +1026,37 @@ pub mod attr {
                 }
 
                 if let Err(err) = attr.parse_nested_meta(|meta| {
+                    // Parse `#[bson(default)]` & `#[bson(default = "path")]`
+                    if meta.path.is_ident(DEFAULT) {
+                        if meta.input.peek(Token![=]) {
+                            match get_lit_str(DEFAULT, &meta).and_then(|s| s.parse()) {
+                                Ok(path) => default = Some(DefaultValue::Path(path)),
+                                Err(e) => errors.push(e),
+                            }
+                        } else {
+                            default = Some(DefaultValue::Trait);
+                        }
+                    // Parse `#[bson(nested)]`
+                    } else if meta.path.is_ident(NESTED) {
+                        nested = true;
+                    // Parse `#[bson(rename = "foo")]`
+                    } else if meta.path.is_ident(RENAME) {
+                        match get_lit_str(RENAME, &meta) {
+                            Ok(s) => rename = Some(s.value()),
+                            Err(e) => errors.push(e),
+                        }
                     // Parse `#[bson(serde)]`
-                    if meta.path.is_ident(SERDE) {
+                    } else if meta.path.is_ident(SERDE) {
                         serde = true;
+                    // Parse `#[bson(skip_none)]`
+                    } else if meta.path.is_ident(SKIP_NONE) {
+                        skip_none = true;
+                    // Parse `#[bson(with = "module")]`
+                    } else if meta.path.is_ident(WITH) {
+                        match get_lit_str(WITH, &meta).and_then(|s| s.parse()) {
+                            Ok(path) => with = Some(path),
+                            Err(e) => errors.push(e),
+                        }
                     } else {
                         let path = meta.path.to_token_stream().to_string().replace(' ', "");
                         return Err(syn::Error::new_spanned(
@@ -388,12 +1083,47 @@ pub mod attr {
                 }
 
                 if let Err(err) = attr.parse_nested_meta(|meta| {
+                    // Parse `#[mongo(filter)]`
+                    if meta.path.is_ident(FILTER) {
+                        filter = true;
+                    // Parse `#[mongo(id)]`
+                    } else if meta.path.is_ident(ID) {
+                        id = true;
+                    // Parse `#[mongo(references = "Type")]`
+                    } else if meta.path.is_ident(REFERENCES) {
+                        match get_lit_str(REFERENCES, &meta).and_then(|s| s.parse()) {
+                            Ok(ty) => references = Some(ty),
+                            Err(e) => errors.push(e),
+                        }
+                    // Parse `#[mongo(rename = "foo")]`
+                    } else if meta.path.is_ident(RENAME) {
+                        match get_lit_str(RENAME, &meta) {
+                            Ok(s) => rename = Some(s.value()),
+                            Err(e) => errors.push(e),
+                        }
                     // Parse `#[mongo(serde)]`
-                    if meta.path.is_ident(SERDE) {
+                    } else if meta.path.is_ident(SERDE) {
                         serde = true;
                     // Parse `#[mongo(skip)]`
                     } else if meta.path.is_ident(SKIP) {
-                        skip = true;
+                        skip_field = true;
+                        skip_filter = true;
+                        skip_update = true;
+                    // Parse `#[mongo(skip_field)]`
+                    } else if meta.path.is_ident(SKIP_FIELD) {
+                        skip_field = true;
+                    // Parse `#[mongo(skip_filter)]`
+                    } else if meta.path.is_ident(SKIP_FILTER) {
+                        skip_filter = true;
+                    // Parse `#[mongo(skip_update)]`
+                    } else if meta.path.is_ident(SKIP_UPDATE) {
+                        skip_update = true;
+                    // Parse `#[mongo(validate(...))]`
+                    } else if meta.path.is_ident(VALIDATE) {
+                        match Validate::parse(&meta) {
+                            Ok(v) => validate = Some(v),
+                            Err(e) => errors.push(e),
+                        }
                     } else {
                         let path = meta.path.to_token_stream().to_string().replace(' ', "");
                         return Err(syn::Error::new_spanned(
@@ -408,16 +1138,102 @@ pub mod attr {
                 }
             }
 
+            if filter && serde {
+                errors.push(syn::Error::new_spanned(
+                    field.into_token_stream(),
+                    "mongo field attributes `filter` & `serde` cannot be used together",
+                ));
+            }
+
+            if serde && with.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    field.into_token_stream(),
+                    "bson field attributes `serde` & `with` cannot be used together",
+                ));
+            }
+
+            // The `id` field is always stored as `_id`, and the `_id` of a document is immutable
+            if id {
+                match rename.as_deref() {
+                    None | Some("_id") => rename = Some("_id".to_owned()),
+                    Some(_) => errors.push(syn::Error::new_spanned(
+                        field.into_token_stream(),
+                        "mongo field attributes `id` & `rename` cannot be used together",
+                    )),
+                }
+                skip_update = true;
+            }
+
+            if nested && (serde || with.is_some()) {
+                errors.push(syn::Error::new_spanned(
+                    field.into_token_stream(),
+                    "bson field attribute `nested` cannot be used with `serde` or `with`",
+                ));
+            }
+
             if !errors.is_empty() {
                 return Err(errors);
             }
-            Ok(Field { serde, skip })
+            Ok(Field {
+                default,
+                filter,
+                id,
+                nested,
+                references,
+                rename,
+                serde,
+                skip_field,
+                skip_filter,
+                skip_none,
+                skip_update,
+                validate,
+                with,
+            })
         }
     }
 
     impl Variant {
-        pub fn from(_variant: &syn::Variant) -> Result<Self, Vec<syn::Error>> {
-            Ok(Variant {})
+        pub fn from(variant: &syn::Variant) -> Result<Self, Vec<syn::Error>> {
+            let mut errors: Vec<syn::Error> = Vec::new();
+
+            let mut rename = None;
+
+            for attr in &variant.attrs {
+                if !attr.path().is_ident(BSON) {
+                    continue;
+                }
+
+                if let syn::Meta::List(meta) = &attr.meta {
+                    if meta.tokens.is_empty() {
+                        continue;
+                    }
+                }
+
+                if let Err(err) = attr.parse_nested_meta(|meta| {
+                    // Parse `#[bson(rename = "foo")]`
+                    if meta.path.is_ident(RENAME) {
+                        match get_lit_str(RENAME, &meta) {
+                            Ok(s) => rename = Some(s.value()),
+                            Err(e) => errors.push(e),
+                        }
+                    } else {
+                        let path = meta.path.to_token_stream().to_string().replace(' ', "");
+                        return Err(syn::Error::new_spanned(
+                            meta.path,
+                            format!("unknown bson variant attribute `{}`", path),
+                        ));
+                    }
+
+                    Ok(())
+                }) {
+                    errors.push(err);
+                }
+            }
+
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+            Ok(Variant { rename })
         }
     }
 
@@ -1,6 +1,7 @@
 use inflector::cases::snakecase::to_snake_case;
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::{Ident, Member, Type};
 
 use crate::ast::{attr, Container, Data, Field, Style, Variant};
@@ -8,19 +9,48 @@ use crate::ast::{attr, Container, Data, Field, Style, Variant};
 pub fn expand_derive_bson(input: &syn::DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
     let container = Container::from(input)?;
 
+    // Scalars are converted by their own `ToBson` & `FromBson`, so their fields are left alone
+    let errors = match &container.data {
+        _ if container.attrs.scalar => vec![],
+        Data::Struct(_, fields) => unsupported_fields(fields.iter(), &container.attrs),
+        Data::Enum(variants) => unsupported_fields(
+            variants.iter().flat_map(|v| v.fields.iter()),
+            &container.attrs,
+        ),
+    };
+    // The conversions are still derived so that the errors do not cascade onto uses of the type
+    let errors = errors.iter().map(syn::Error::to_compile_error);
+
     let body = match &container.data {
+        _ if container.attrs.scalar => {
+            let bounds = Bounds::from(container.generics, std::iter::empty());
+            impl_scalar(&container.ident, &container.attrs, &bounds)
+        }
         Data::Struct(style, fields) => {
-            match style {
-                Style::Struct => {}
-                _ => panic!("#[derive(Bson)] can only be derived on named structs"),
+            let bounds = Bounds::from(container.generics, fields.iter());
+            match (style, fields.as_slice()) {
+                (Style::Tuple, [field]) => {
+                    impl_newtype(&container.ident, field, &container.attrs, &bounds)
+                }
+                _ => impl_struct(&container.ident, style, fields, &container.attrs, &bounds),
             }
-            impl_struct(&container.ident, style, fields, &container.attrs)
         }
-        Data::Enum(variants) => impl_enum(&container.ident, variants, &container.attrs),
+        Data::Enum(variants) => {
+            let bounds = Bounds::from(
+                container.generics,
+                variants.iter().flat_map(|v| v.fields.iter()),
+            );
+            impl_enum(&container.ident, variants, &container.attrs, &bounds)
+        }
     };
 
     Ok(quote! {
-        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        #[allow(
+            non_upper_case_globals,
+            unused_attributes,
+            unused_qualifications,
+            clippy::unnecessary_fallible_conversions
+        )]
         const _: () = {
             extern crate mongod as _mongo;
 
@@ -29,13 +59,60 @@ pub fn expand_derive_bson(input: &syn::DeriveInput) -> Result<TokenStream, Vec<s
 
             #body
         };
+        #(#errors)*
     })
 }
 
+// Reports the fields using primitives that have no BSON equivalent, as otherwise the compiler only
+// reports the missing conversion
+fn unsupported_fields<'a>(
+    fields: impl Iterator<Item = &'a Field<'a>>,
+    attrs: &attr::Container,
+) -> Vec<syn::Error> {
+    fn find(ty: &Type, attrs: &attr::Container) -> Option<(&'static str, &'static str)> {
+        let segment = match ty {
+            Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+            _ => return None,
+        };
+        match segment.ident.to_string().as_str() {
+            // These can still be converted from BSON
+            "isize" if attrs.into => return Some(("isize", "i64")),
+            "usize" if attrs.into => return Some(("usize", "u64")),
+            "i128" => return Some(("i128", "i64")),
+            "u128" => return Some(("u128", "u64")),
+            _ => {}
+        }
+        match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => {
+                args.args.iter().find_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => find(ty, attrs),
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    }
+    fields
+        .filter(|f| !f.attrs.serde && f.attrs.with.is_none())
+        .filter_map(|f| {
+            let (ty, suggestion) = find(f.ty, attrs)?;
+            Some(syn::Error::new_spanned(
+                f.ty,
+                format!(
+                    "`{}` has no BSON equivalent, use `{}` instead or convert the field using \
+                     `#[bson(serde)]` or `#[bson(with = \"...\")]`",
+                    ty, suggestion
+                ),
+            ))
+        })
+        .collect()
+}
+
 fn impl_enum(
     name: &Ident,
     variants: &[Variant],
     attrs: &attr::Container,
+    bounds: &Bounds,
 ) -> proc_macro2::TokenStream {
     // This is how we handle conversion into bson, if people want more options then they will have
     // to use serde for now.
@@ -43,15 +120,22 @@ fn impl_enum(
     //      {
     //          "NAME": "VALUE",
     //      }
-    // 2. Otherwise handle as a structure, where _type is injected into the struct.
+    // 2. Otherwise handle as a structure, where by default _type is injected into the struct.
     //      {
     //          "_type": "NAME",
     //          "0": "VALUE",
     //      }
-    if variants.len() == variants.iter().filter(|v| v.style == Style::Unit).count() {
-        impl_enum_unit(name, variants, attrs)
+    //    Or using the tagging set by `#[bson(tag = "...")]`, `#[bson(content = "...")]` or
+    //    `#[bson(untagged)]`.
+    // 3. If `#[bson(repr = "...")]` is set then the variants are stored as their discriminants.
+    if let Some(repr) = &attrs.repr {
+        impl_enum_repr(name, variants, repr, attrs, bounds)
+    } else if attrs.tagging.is_none()
+        && variants.len() == variants.iter().filter(|v| v.style == Style::Unit).count()
+    {
+        impl_enum_unit(name, variants, attrs, bounds)
     } else {
-        impl_enum_struct(name, variants, attrs)
+        impl_enum_struct(name, variants, attrs, bounds)
     }
 }
 
@@ -59,51 +143,68 @@ fn impl_enum_struct(
     name: &Ident,
     variants: &[Variant],
     attrs: &attr::Container,
+    bounds: &Bounds,
 ) -> proc_macro2::TokenStream {
+    let default = attr::Tagging::Internal("_type".to_owned());
+    let tagging = attrs.tagging.as_ref().unwrap_or(&default);
     let try_from_collection_fields = variants.iter().map(|v| {
         let id = &v.ident;
-        let value = to_snake_case(&v.ident.to_string());
+        let value = variant_to_id(v);
         let fields = v.fields.iter().map(|f| member_to_ident(&f.member));
         let values = v.fields.iter().map(|f| {
-            let id = member_to_id(&f.member);
             let member = member_to_ident(&f.member);
-            if f.attrs.serde {
-                quote! {
-                    doc.insert(#id, _mongo::bson::to_bson(&#member)?);
-                }
-            } else {
-                quote! {
-                    doc.insert(#id, _mongo::ext::bson::Bson::try_from(#member)?.0);
-                }
-            }
+            impl_struct_insert_field(f, field_into_bson(f, quote! { #member }))
         });
+        let body = match (tagging, v.style) {
+            (attr::Tagging::Internal(tag), _) => quote! {
+                let mut doc = _mongo::bson::Document::new();
+                doc.insert(#tag, #value.to_owned());
+                #(#values)*
+                _mongo::bson::Bson::Document(doc)
+            },
+            (attr::Tagging::Adjacent { tag, .. }, Style::Unit) => quote! {
+                let mut outer = _mongo::bson::Document::new();
+                outer.insert(#tag, #value.to_owned());
+                _mongo::bson::Bson::Document(outer)
+            },
+            (attr::Tagging::Adjacent { tag, content }, _) => quote! {
+                let mut doc = _mongo::bson::Document::new();
+                #(#values)*
+                let mut outer = _mongo::bson::Document::new();
+                outer.insert(#tag, #value.to_owned());
+                outer.insert(#content, doc);
+                _mongo::bson::Bson::Document(outer)
+            },
+            (attr::Tagging::Untagged, Style::Unit) => quote! {
+                _mongo::bson::Bson::Null
+            },
+            (attr::Tagging::Untagged, _) => quote! {
+                let mut doc = _mongo::bson::Document::new();
+                #(#values)*
+                _mongo::bson::Bson::Document(doc)
+            },
+        };
         match v.style {
-            Style::Struct => {
-                quote! {
-                    #name::#id { #(#fields),* } => {
-                        doc.insert("_type", #value.to_owned());
-                        #(#values)*
-                    }
+            Style::Struct => quote! {
+                #name::#id { #(#fields),* } => {
+                    #body
                 }
-            }
-            Style::Tuple => {
-                quote! {
-                    #name::#id(#(#fields),*) => {
-                        doc.insert("_type", #value.to_owned());
-                        #(#values)*
-                    }
+            },
+            Style::Tuple => quote! {
+                #name::#id(#(#fields),*) => {
+                    #body
                 }
-            }
+            },
             Style::Unit => quote! {
                 #name::#id => {
-                    doc.insert("_type", #value.to_owned());
+                    #body
                 }
             },
         }
     });
-    let try_from_bson_fields = variants.iter().map(|v| {
+    // Builds each variant from the fields in `doc`
+    let try_from_bson_variants = variants.iter().map(|v| {
         let id = &v.ident;
-        let value = to_snake_case(&v.ident.to_string());
         let options = v.fields.iter().map(|f| {
             let member = member_to_ident(&f.member);
             let ty = &f.ty;
@@ -112,76 +213,140 @@ fn impl_enum_struct(
             }
         });
         let values = v.fields.iter().map(|f| impl_struct_try_from_bson_field(f));
-        let missing = v.fields.iter().map(|f| {
-            let id = member_to_id(&f.member);
-            let member = member_to_ident(&f.member);
-            let msg = format!("'{}' is missing", id);
-            quote! {
-                if #member.is_none() {
-                    return Err(_mongo::bson::de::Error::custom(
-                        #msg.to_owned(),
-                    ).into());
-                }
-            }
-        });
+        let missing = v.fields.iter().map(impl_struct_missing_field);
         match v.style {
             Style::Struct => {
                 let expects = v.fields.iter().map(|f| {
-                    let id = member_to_id(&f.member);
                     let member = member_to_ident(&f.member);
-                    let msg = format!("'{}' is missing", id);
+                    let value = impl_struct_field_value(f);
                     quote! {
-                        #member: #member.expect(#msg)
+                        #member: #value
                     }
                 });
                 quote! {
-                    Some(#value) => {
-                        #(#options)*
-                        #(#values)*
-                        #(#missing)*
-                        Ok(#name::#id {
-                            #(#expects),*
-                        })
-                    }
+                    #(#options)*
+                    #(#values)*
+                    #(#missing)*
+                    Ok(#name::#id {
+                        #(#expects),*
+                    })
                 }
             }
             Style::Tuple => {
-                let expects = v.fields.iter().map(|f| {
-                    let id = member_to_id(&f.member);
-                    let member = member_to_ident(&f.member);
-                    let msg = format!("'{}' is missing", id);
-                    quote! {
-                            #member.expect(#msg)
-                    }
-                });
+                let expects = v.fields.iter().map(impl_struct_field_value);
                 quote! {
-                    Some(#value) => {
-                        #(#options)*
-                        #(#values)*
-                        #(#missing)*
-                        Ok(#name::#id(
-                            #(#expects),*
-                        ))
-                    }
+                    #(#options)*
+                    #(#values)*
+                    #(#missing)*
+                    Ok(#name::#id(
+                        #(#expects),*
+                    ))
                 }
             }
             Style::Unit => quote! {
-                Some(#value) => Ok(#name::#id),
+                Ok(#name::#id)
             },
         }
     });
+    let try_from_bson = match tagging {
+        attr::Tagging::Internal(_) | attr::Tagging::Adjacent { .. } => {
+            let arms = variants
+                .iter()
+                .zip(try_from_bson_variants)
+                .map(|(v, body)| {
+                    let value = variant_to_id(v);
+                    quote! {
+                        Some(#value) => {
+                            #body
+                        }
+                    }
+                });
+            let (tag, content) = match tagging {
+                attr::Tagging::Internal(tag) => (tag, quote! {}),
+                attr::Tagging::Adjacent { tag, content } => (
+                    tag,
+                    quote! {
+                        #[allow(unused_mut)]
+                        let mut doc = match doc.remove(#content) {
+                            Some(_mongo::bson::Bson::Document(doc)) => doc,
+                            Some(_) => return Err(_mongo::bson::de::Error::custom(
+                                "enum content not a BSON Document".to_owned()
+                            ).into()),
+                            None => _mongo::bson::Document::new(),
+                        };
+                    },
+                ),
+                attr::Tagging::Untagged => unreachable!(),
+            };
+            quote! {
+                let mut doc = match bson {
+                    _mongo::bson::Bson::Document(doc) => doc,
+                    _ => return Err(_mongo::bson::de::Error::custom(
+                        "not a BSON Document".to_owned()
+                    ).into()),
+                };
+                let value = match doc.remove(#tag) {
+                    Some(v) => v,
+                    None => return Err(_mongo::bson::de::Error::custom(
+                        "enum type not found".to_owned()
+                    ).into()),
+                };
+                #content
+                match value.as_str() {
+                    #(#arms)*
+                    _ => return Err(_mongo::bson::de::Error::custom(
+                        "invalid variant".to_owned()
+                    ).into()),
+                }
+            }
+        }
+        attr::Tagging::Untagged => {
+            // Untagged variants are tried in order, returning the first that matches
+            let attempts = variants
+                .iter()
+                .zip(try_from_bson_variants)
+                .map(|(v, body)| match v.style {
+                    Style::Unit => quote! {
+                        if let _mongo::bson::Bson::Null = &bson {
+                            return #body;
+                        }
+                    },
+                    _ => quote! {
+                        if let _mongo::bson::Bson::Document(doc) = &bson {
+                            let attempt = || -> core::result::Result<Self, Self::Error> {
+                                let mut doc = doc.clone();
+                                #body
+                            };
+                            if let Ok(value) = attempt() {
+                                return Ok(value);
+                            }
+                        }
+                    },
+                });
+            quote! {
+                #(#attempts)*
+                Err(_mongo::bson::de::Error::custom(
+                    "data did not match any variant".to_owned()
+                ).into())
+            }
+        }
+    };
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
     let into = if attrs.into {
-        let try_from_type = try_from_type_to_ext_bson(name);
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<#name> for _mongo::bson::Bson {
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
                 type Error = _mongo::ext::bson::ser::Error;
-                fn try_from(value: #name) -> core::result::Result<Self, Self::Error> {
-                    let mut doc = _mongo::bson::Document::new();
-                    match value {
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
+                    Ok(match value {
                         #(#try_from_collection_fields),*
-                    }
-                    Ok(_mongo::bson::Bson::Document(doc))
+                    })
                 }
             }
             #try_from_type
@@ -190,30 +355,13 @@ fn impl_enum_struct(
         quote! {}
     };
     let from = if attrs.from {
-        let try_from_ext = try_from_ext_bson_to_type(name);
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<_mongo::bson::Bson> for #name {
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
                 type Error = _mongo::ext::bson::de::Error;
                 fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
-                    let mut doc = match bson {
-                        _mongo::bson::Bson::Document(doc) => doc,
-                        _ => return Err(_mongo::bson::de::Error::custom(
-                            "not a BSON Document".to_owned()
-                        ).into()),
-                    };
-                    let value = match doc.remove("_type") {
-                        Some(v) => v,
-                        None => return Err(_mongo::bson::de::Error::custom(
-                            "enum type not found".to_owned()
-                        ).into()),
-                    };
-                    match value.as_str() {
-                        #(#try_from_bson_fields)*
-                        _ => return Err(_mongo::bson::de::Error::custom(
-                            "invalid variant".to_owned()
-                        ).into()),
-                    }
+                    #try_from_bson
                 }
             }
             #try_from_ext
@@ -232,10 +380,11 @@ fn impl_enum_unit(
     name: &Ident,
     variants: &[Variant],
     attrs: &attr::Container,
+    bounds: &Bounds,
 ) -> proc_macro2::TokenStream {
     let try_from_collection_fields = variants.iter().map(|v| {
         let id = &v.ident;
-        let value = to_snake_case(&v.ident.to_string());
+        let value = variant_to_id(v);
         quote! {
             #name::#id => #value.to_owned()
         }
@@ -243,19 +392,25 @@ fn impl_enum_unit(
 
     let try_from_bson_fields = variants.iter().map(|v| {
         let id = &v.ident;
-        let value = to_snake_case(&v.ident.to_string());
+        let value = variant_to_id(v);
         quote! {
             #value => Ok(#name::#id),
         }
     });
 
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
     let into = if attrs.into {
-        let try_from_type = try_from_type_to_ext_bson(name);
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<#name> for _mongo::bson::Bson {
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
                 type Error = _mongo::ext::bson::ser::Error;
-                fn try_from(value: #name) -> core::result::Result<Self, Self::Error> {
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
                     let v = match value {
                         #(#try_from_collection_fields),*
                     };
@@ -269,10 +424,10 @@ fn impl_enum_unit(
     };
 
     let from = if attrs.from {
-        let try_from_ext = try_from_ext_bson_to_type(name);
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<_mongo::bson::Bson> for #name {
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
                 type Error = _mongo::ext::bson::de::Error;
                 fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
                     let value = match bson {
@@ -295,6 +450,167 @@ fn impl_enum_unit(
         quote! {}
     };
 
+    // Unit enums are plain strings, so their schema can always be described
+    let values = variants.iter().map(variant_to_id);
+    let json_schema = quote! {
+        #[automatically_derived]
+        impl #impl_generics _mongo::JsonSchema for #name #ty_generics {
+            fn json_schema() -> _mongo::bson::Document {
+                _mongo::bson::doc! { "bsonType": "string", "enum": [#(#values),*] }
+            }
+        }
+    };
+
+    quote! {
+        #into
+        #from
+        #json_schema
+    }
+}
+
+fn impl_enum_repr(
+    name: &Ident,
+    variants: &[Variant],
+    repr: &Ident,
+    attrs: &attr::Container,
+    bounds: &Bounds,
+) -> proc_macro2::TokenStream {
+    let variant = if repr == "i32" {
+        quote! { Int32 }
+    } else {
+        quote! { Int64 }
+    };
+    let bson_type = if repr == "i32" { "int" } else { "long" };
+
+    let try_from_collection_fields = variants.iter().map(|v| {
+        let id = &v.ident;
+        quote! {
+            #name::#id => #name::#id as #repr
+        }
+    });
+
+    // Guards are used as the discriminants are not necessarily literals
+    let try_from_bson_fields = variants.iter().map(|v| {
+        let id = &v.ident;
+        quote! {
+            v if v == #name::#id as i64 => Ok(#name::#id),
+        }
+    });
+
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
+    let into = if attrs.into {
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
+                type Error = _mongo::ext::bson::ser::Error;
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
+                    let v = match value {
+                        #(#try_from_collection_fields),*
+                    };
+                    Ok(_mongo::bson::Bson::#variant(v))
+                }
+            }
+            #try_from_type
+        }
+    } else {
+        quote! {}
+    };
+
+    let from = if attrs.from {
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
+        let expected = format!(
+            "invalid value, expected a discriminant of `{}` but found `{{}}`",
+            name
+        );
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
+                type Error = _mongo::ext::bson::de::Error;
+                fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
+                    // Existing collections do not always agree on the width, so either is accepted,
+                    // while anything else goes through `ext` to get its numeric coercion
+                    let value = match bson {
+                        _mongo::bson::Bson::Int32(v) => i64::from(v),
+                        _mongo::bson::Bson::Int64(v) => v,
+                        _ => i64::try_from(_mongo::ext::bson::Bson(bson))?,
+                    };
+                    match value {
+                        #(#try_from_bson_fields)*
+                        v => Err(_mongo::bson::de::Error::custom(format!(#expected, v)).into()),
+                    }
+                }
+            }
+            #try_from_ext
+        }
+    } else {
+        quote! {}
+    };
+
+    let values = variants.iter().map(|v| {
+        let id = &v.ident;
+        quote! { #name::#id as #repr }
+    });
+    let json_schema = quote! {
+        #[automatically_derived]
+        impl #impl_generics _mongo::JsonSchema for #name #ty_generics {
+            fn json_schema() -> _mongo::bson::Document {
+                _mongo::bson::doc! { "bsonType": #bson_type, "enum": [#(#values),*] }
+            }
+        }
+    };
+
+    quote! {
+        #into
+        #from
+        #json_schema
+    }
+}
+
+fn impl_scalar(name: &Ident, attrs: &attr::Container, bounds: &Bounds) -> TokenStream {
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
+    let into = if attrs.into {
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
+                type Error = _mongo::ext::bson::ser::Error;
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
+                    _mongo::ext::bson::ToBson::to_bson(value)
+                }
+            }
+            #try_from_type
+        }
+    } else {
+        quote! {}
+    };
+
+    let from = if attrs.from {
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
+                type Error = _mongo::ext::bson::de::Error;
+                fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
+                    _mongo::ext::bson::FromBson::from_bson(bson)
+                }
+            }
+            #try_from_ext
+        }
+    } else {
+        quote! {}
+    };
+
     quote! {
         #into
         #from
@@ -306,30 +622,28 @@ fn impl_struct(
     _style: &Style,
     fields: &[Field],
     attrs: &attr::Container,
+    bounds: &Bounds,
 ) -> TokenStream {
     let try_from_collection_fields = fields
         .iter()
         .map(|f| {
             let member = &f.member;
-            let id = member_to_id(&f.member);
-            if f.attrs.serde {
-                quote! {
-                    doc.insert(#id, _mongo::bson::to_bson(&value.#member)?);
-                }
-            } else {
-                quote! {
-                    doc.insert(#id, _mongo::ext::bson::Bson::try_from(value.#member)?.0);
-                }
-            }
+            impl_struct_insert_field(f, field_into_bson(f, quote! { value.#member }))
         })
         .collect::<Vec<_>>();
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
     let into = if attrs.into {
-        let try_from_type = try_from_type_to_ext_bson(name);
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<#name> for _mongo::bson::Bson {
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
                 type Error = _mongo::ext::bson::ser::Error;
-                fn try_from(value: #name) -> core::result::Result<Self, Self::Error> {
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
                     let mut doc = _mongo::bson::Document::new();
                     #(#try_from_collection_fields)*
                     Ok(_mongo::bson::Bson::Document(doc))
@@ -342,39 +656,27 @@ fn impl_struct(
     };
 
     let options = fields.iter().map(|f| {
-        let member = &f.member;
+        let member = member_to_ident(&f.member);
         let ty = &f.ty;
         quote! {
             let mut #member: Option<#ty> = None;
         }
     });
     let values = fields.iter().map(|f| impl_struct_try_from_bson_field(f));
-    let missing = fields.iter().map(|f| {
-        let id = member_to_id(&f.member);
-        let member = &f.member;
-        let msg = format!("'{}' is missing", id);
-        quote! {
-            if #member.is_none() {
-                return Err(_mongo::bson::de::Error::custom(
-                    #msg.to_owned(),
-                ).into());
-            }
-        }
-    });
+    let missing = fields.iter().map(impl_struct_missing_field);
     let expects = fields.iter().map(|f| {
-        let id = member_to_id(&f.member);
         let member = &f.member;
-        let msg = format!("'{}' is missing", id);
+        let value = impl_struct_field_value(f);
         quote! {
-            #member: #member.expect(#msg)
+            #member: #value
         }
     });
 
     let from = if attrs.from {
-        let try_from_ext = try_from_ext_bson_to_type(name);
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
         quote! {
             #[automatically_derived]
-            impl TryFrom<_mongo::bson::Bson> for #name {
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
                 type Error = _mongo::ext::bson::de::Error;
                 fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
                     let mut doc = match bson {
@@ -403,35 +705,446 @@ fn impl_struct(
     }
 }
 
-fn impl_struct_try_from_bson_field(f: &Field) -> TokenStream {
+// Newtypes are converted transparently as their inner type, while other tuple structs are
+// converted into documents keyed by index
+fn impl_newtype(
+    name: &Ident,
+    field: &Field,
+    attrs: &attr::Container,
+    bounds: &Bounds,
+) -> TokenStream {
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        from_where,
+    } = bounds;
+    let into = if attrs.into {
+        let value = field_into_bson(field, quote! { value.0 });
+        let try_from_type = try_from_type_to_ext_bson(name, bounds);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<#name #ty_generics> for _mongo::bson::Bson #into_where {
+                type Error = _mongo::ext::bson::ser::Error;
+                fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
+                    Ok(#value)
+                }
+            }
+            #try_from_type
+        }
+    } else {
+        quote! {}
+    };
+    let from = if attrs.from {
+        let value = field_from_bson(field, quote! { bson });
+        let try_from_ext = try_from_ext_bson_to_type(name, bounds);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<_mongo::bson::Bson> for #name #ty_generics #from_where {
+                type Error = _mongo::ext::bson::de::Error;
+                fn try_from(bson: _mongo::bson::Bson) -> core::result::Result<Self, Self::Error> {
+                    Ok(Self(#value))
+                }
+            }
+            #try_from_ext
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #into
+        #from
+    }
+}
+
+pub fn impl_struct_try_from_bson_field(f: &Field) -> TokenStream {
     let member = member_to_ident(&f.member);
-    let id = member_to_id(&f.member);
-    let optional = is_option(f.ty);
-    let ty = &f.ty;
-    if f.attrs.serde {
+    let id = field_to_id(f);
+    let value = field_from_bson(f, quote! { __value });
+    // Fields that omit `null` treat a missing value as `null`
+    if f.attrs.skip_none {
+        return quote! {
+            let __value = doc.remove(#id).unwrap_or(_mongo::bson::Bson::Null);
+            #member = Some(#value);
+        };
+    }
+    quote! {
+        if let Some(__value) = doc.remove(#id) {
+            #member = Some(#value);
+        }
+    }
+}
+
+// Inserts the converted `value` of the field into `doc`, leaving it out when it is `null` and the
+// field uses `#[bson(skip_none)]`
+fn impl_struct_insert_field(f: &Field, value: TokenStream) -> TokenStream {
+    let id = field_to_id(f);
+    if f.attrs.skip_none {
         quote! {
-            if let Some(__value) = doc.remove(#id) {
-                #member = Some(_mongo::bson::from_bson(__value)?);
+            match #value {
+                _mongo::bson::Bson::Null => {}
+                __value => {
+                    doc.insert(#id, __value);
+                }
             }
         }
-    } else if optional {
+    } else {
         quote! {
-            if let Some(__value) = doc.remove(#id) {
-                let wrap = _mongo::ext::bson::Bson(__value);
-                let opt = match Option::<_mongo::bson::Bson>::from(wrap) {
-                    Some(v) => Some(_mongo::ext::bson::Bson(v).try_into()?),
-                    None => None,
-                };
-                #member = Some(opt);
+            doc.insert(#id, #value);
+        }
+    }
+}
+
+// Converts the field's `value` into a `bson::Bson`.
+//
+// The conversion is spanned to the field's type so that an unsupported type is reported on the
+// field, rather than on the derive.
+fn field_into_bson(f: &Field, value: TokenStream) -> TokenStream {
+    let ty = &f.ty;
+    if let Some(with) = &f.attrs.with {
+        quote_spanned! {ty.span()=> #with::to_bson(#value)? }
+    } else if f.attrs.nested {
+        nested_into_bson(ty, value)
+    } else if f.attrs.serde {
+        quote_spanned! {ty.span()=> _mongo::bson::to_bson(&#value)? }
+    } else if let Some(inner) = cow_inner(ty) {
+        quote_spanned! {ty.span()=>
+            <_mongo::ext::bson::Bson as TryFrom<&#inner>>::try_from(&*#value)?.0
+        }
+    } else if let Some(inner) = option_inner(ty).and_then(cow_inner) {
+        quote_spanned! {ty.span()=>
+            match #value {
+                Some(v) => <_mongo::ext::bson::Bson as TryFrom<&#inner>>::try_from(&*v)?.0,
+                None => _mongo::bson::Bson::Null,
             }
         }
+    } else if let Some(into) = pointer_into_bson(ty, value.clone()) {
+        into
+    } else {
+        quote_spanned! {ty.span()=>
+            <_mongo::ext::bson::Bson as TryFrom<#ty>>::try_from(#value)?.0
+        }
+    }
+}
+
+// Whether the type is a smart pointer, or an optional one, which is converted through its pointee.
+pub fn is_pointer(ty: &Type) -> bool {
+    Pointer::from(option_inner(ty).unwrap_or(ty)).is_some()
+}
+
+// Converts the `value` of a smart pointer, or an optional one, into a `bson::Bson` through its
+// pointee, returning `None` for any other type.
+pub fn pointer_into_bson(ty: &Type, value: TokenStream) -> Option<TokenStream> {
+    if let Some((pointer, inner)) = Pointer::from(ty) {
+        let value = pointer.unwrap(value);
+        Some(quote_spanned! {ty.span()=>
+            <_mongo::ext::bson::Bson as TryFrom<#inner>>::try_from(#value)?.0
+        })
+    } else if let Some((pointer, inner)) = option_inner(ty).and_then(Pointer::from) {
+        let v = pointer.unwrap(quote! { v });
+        Some(quote_spanned! {ty.span()=>
+            match #value {
+                Some(v) => <_mongo::ext::bson::Bson as TryFrom<#inner>>::try_from(#v)?.0,
+                None => _mongo::bson::Bson::Null,
+            }
+        })
     } else {
+        None
+    }
+}
+
+// Converts the `bson::Bson` `value` into the field's type, see `field_into_bson`.
+fn field_from_bson(f: &Field, value: TokenStream) -> TokenStream {
+    let ty = &f.ty;
+    if let Some(with) = &f.attrs.with {
+        quote_spanned! {ty.span()=> #with::from_bson(#value)? }
+    } else if f.attrs.nested {
+        nested_from_bson(ty, value)
+    } else if f.attrs.serde {
+        quote_spanned! {ty.span()=> _mongo::bson::from_bson(#value)? }
+    } else if let Some(inner) = cow_inner(ty) {
+        quote_spanned! {ty.span()=>
+            std::borrow::Cow::Owned(
+                <<#inner as std::borrow::ToOwned>::Owned as TryFrom<_mongo::ext::bson::Bson>>::try_from(
+                    _mongo::ext::bson::Bson(#value),
+                )?,
+            )
+        }
+    } else if let Some(inner) = option_inner(ty).and_then(cow_inner) {
+        quote_spanned! {ty.span()=>
+            match Option::<_mongo::bson::Bson>::from(_mongo::ext::bson::Bson(#value)) {
+                Some(v) => Some(std::borrow::Cow::Owned(
+                    <<#inner as std::borrow::ToOwned>::Owned as TryFrom<_mongo::ext::bson::Bson>>::try_from(
+                        _mongo::ext::bson::Bson(v),
+                    )?,
+                )),
+                None => None,
+            }
+        }
+    } else if let Some((pointer, inner)) = Pointer::from(ty) {
+        pointer.wrap(quote_spanned! {ty.span()=>
+            <#inner as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(#value))?
+        })
+    } else if let Some((pointer, inner)) = option_inner(ty).and_then(Pointer::from) {
+        let v = pointer.wrap(quote_spanned! {ty.span()=>
+            <#inner as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(v))?
+        });
         quote! {
-            if let Some(__value) = doc.remove(#id) {
-                let wrap = _mongo::ext::bson::Bson(__value);
-                #member = Some(<#ty>::try_from(wrap)?);
+            match Option::<_mongo::bson::Bson>::from(_mongo::ext::bson::Bson(#value)) {
+                Some(v) => Some(#v),
+                None => None,
             }
         }
+    } else if let Some(inner) = option_inner(ty) {
+        quote_spanned! {ty.span()=>
+            match Option::<_mongo::bson::Bson>::from(_mongo::ext::bson::Bson(#value)) {
+                Some(v) => Some(<#inner as TryFrom<_mongo::ext::bson::Bson>>::try_from(
+                    _mongo::ext::bson::Bson(v),
+                )?),
+                None => None,
+            }
+        }
+    } else {
+        quote_spanned! {ty.span()=>
+            <#ty as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(#value))?
+        }
+    }
+}
+
+// The containers that `#[bson(nested)]` converts element by element, going by the last segment of
+// their path, so that the elements can use their own fallible conversions.
+enum Nested<'a> {
+    Pointer(Pointer, &'a Type),
+    Map(&'a Type, &'a Type),
+    Option(&'a Type),
+    Seq(&'a Type),
+    Leaf,
+}
+
+impl<'a> Nested<'a> {
+    fn from(ty: &'a Type) -> Self {
+        let segment = match ty {
+            Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+                Some(segment) => segment,
+                None => return Nested::Leaf,
+            },
+            _ => return Nested::Leaf,
+        };
+        let args = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ => return Nested::Leaf,
+        };
+        match (segment.ident.to_string().as_str(), args.as_slice()) {
+            ("Arc", [inner]) => Nested::Pointer(Pointer::Arc, inner),
+            ("Box", [inner]) => Nested::Pointer(Pointer::Box, inner),
+            ("Rc", [inner]) => Nested::Pointer(Pointer::Rc, inner),
+            ("BTreeMap", [key, value]) | ("HashMap", [key, value]) | ("IndexMap", [key, value]) => {
+                Nested::Map(key, value)
+            }
+            ("Option", [inner]) => Nested::Option(inner),
+            ("BTreeSet", [inner])
+            | ("HashSet", [inner])
+            | ("IndexSet", [inner])
+            | ("LinkedList", [inner])
+            | ("Vec", [inner])
+            | ("VecDeque", [inner]) => Nested::Seq(inner),
+            _ => Nested::Leaf,
+        }
+    }
+
+    // The types within the containers, which are converted by themselves
+    fn leaves(ty: &'a Type) -> Vec<&'a Type> {
+        match Nested::from(ty) {
+            Nested::Pointer(_, inner)
+            | Nested::Map(_, inner)
+            | Nested::Option(inner)
+            | Nested::Seq(inner) => Nested::leaves(inner),
+            Nested::Leaf => vec![ty],
+        }
+    }
+}
+
+// The smart pointers that are stored as their pointee, which are unwrapped by the derive as their
+// conversions cannot be implemented generically in `ext::bson`.
+#[derive(Clone, Copy)]
+enum Pointer {
+    Arc,
+    Box,
+    Rc,
+}
+
+impl Pointer {
+    // The pointee of a smart pointer, i.e. `T` for `Box<T>`
+    fn from(ty: &Type) -> Option<(Self, &Type)> {
+        match Nested::from(ty) {
+            Nested::Pointer(pointer, inner) => Some((pointer, inner)),
+            _ => None,
+        }
+    }
+
+    // Shared pointees are only cloned when there are other references to them
+    fn unwrap(self, value: TokenStream) -> TokenStream {
+        match self {
+            Pointer::Arc => quote! {
+                std::sync::Arc::try_unwrap(#value).unwrap_or_else(|__p| (*__p).clone())
+            },
+            Pointer::Box => quote! { *#value },
+            Pointer::Rc => quote! {
+                std::rc::Rc::try_unwrap(#value).unwrap_or_else(|__p| (*__p).clone())
+            },
+        }
+    }
+
+    fn wrap(self, value: TokenStream) -> TokenStream {
+        match self {
+            Pointer::Arc => quote! { std::sync::Arc::new(#value) },
+            Pointer::Box => quote! { Box::new(#value) },
+            Pointer::Rc => quote! { std::rc::Rc::new(#value) },
+        }
+    }
+}
+
+// Converts the `value` into a `bson::Bson` for `#[bson(nested)]`, unwrapping containers until
+// reaching a type that can be converted by itself.
+pub fn nested_into_bson(ty: &Type, value: TokenStream) -> TokenStream {
+    match Nested::from(ty) {
+        Nested::Pointer(pointer, inner) => nested_into_bson(inner, pointer.unwrap(value)),
+        Nested::Map(_, inner) => {
+            let inner = nested_into_bson(inner, quote! { __v });
+            quote! {
+                _mongo::bson::Bson::Document(
+                    #value
+                        .into_iter()
+                        .map(|(__k, __v)| -> core::result::Result<_, _mongo::ext::bson::ser::Error> {
+                            Ok((String::from(__k), #inner))
+                        })
+                        .collect::<core::result::Result<_mongo::bson::Document, _>>()?,
+                )
+            }
+        }
+        Nested::Option(inner) => {
+            let inner = nested_into_bson(inner, quote! { __v });
+            quote! {
+                match #value {
+                    Some(__v) => #inner,
+                    None => _mongo::bson::Bson::Null,
+                }
+            }
+        }
+        Nested::Seq(inner) => {
+            let inner = nested_into_bson(inner, quote! { __v });
+            quote! {
+                _mongo::bson::Bson::Array(
+                    #value
+                        .into_iter()
+                        .map(|__v| -> core::result::Result<_, _mongo::ext::bson::ser::Error> {
+                            Ok(#inner)
+                        })
+                        .collect::<core::result::Result<Vec<_>, _>>()?,
+                )
+            }
+        }
+        Nested::Leaf => quote_spanned! {ty.span()=>
+            <_mongo::ext::bson::Bson as TryFrom<#ty>>::try_from(#value)?.0
+        },
+    }
+}
+
+// Converts the `bson::Bson` `value` into the type for `#[bson(nested)]`, see `nested_into_bson`.
+pub fn nested_from_bson(ty: &Type, value: TokenStream) -> TokenStream {
+    let invalid = |expected: &str| {
+        let msg = format!(
+            "invalid variant, expected `Bson::{}(...)` but found `{{}}`",
+            expected
+        );
+        quote! {
+            __v => {
+                return Err(<_mongo::bson::de::Error as _mongo::ext::bson::de::ErrorExt>::custom(
+                    format!(#msg, __v),
+                ).into())
+            }
+        }
+    };
+    match Nested::from(ty) {
+        Nested::Pointer(pointer, inner) => pointer.wrap(nested_from_bson(inner, value)),
+        Nested::Map(key, inner) => {
+            let inner = nested_from_bson(inner, quote! { __v });
+            let invalid = invalid("Document");
+            quote! {
+                match #value {
+                    _mongo::bson::Bson::Document(__doc) => __doc
+                        .into_iter()
+                        .map(|(__k, __v)| -> core::result::Result<_, _mongo::ext::bson::de::Error> {
+                            Ok((<#key as TryFrom<String>>::try_from(__k)?, #inner))
+                        })
+                        .collect::<core::result::Result<#ty, _>>()?,
+                    #invalid
+                }
+            }
+        }
+        Nested::Option(inner) => {
+            let inner = nested_from_bson(inner, quote! { __v });
+            quote! {
+                match #value {
+                    _mongo::bson::Bson::Null => None,
+                    __v => Some(#inner),
+                }
+            }
+        }
+        Nested::Seq(inner) => {
+            let inner = nested_from_bson(inner, quote! { __v });
+            let invalid = invalid("Array");
+            quote! {
+                match #value {
+                    _mongo::bson::Bson::Array(__array) => __array
+                        .into_iter()
+                        .map(|__v| -> core::result::Result<_, _mongo::ext::bson::de::Error> {
+                            Ok(#inner)
+                        })
+                        .collect::<core::result::Result<#ty, _>>()?,
+                    #invalid
+                }
+            }
+        }
+        Nested::Leaf => quote_spanned! {ty.span()=>
+            <#ty as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(#value))?
+        },
+    }
+}
+
+pub fn impl_struct_missing_field(f: &Field) -> TokenStream {
+    // Fields with a default are allowed to be missing
+    if f.attrs.default.is_some() {
+        return quote! {};
+    }
+    let member = member_to_ident(&f.member);
+    let msg = format!("'{}' is missing", field_to_id(f));
+    quote! {
+        if #member.is_none() {
+            return Err(_mongo::bson::de::Error::custom(
+                #msg.to_owned(),
+            ).into());
+        }
+    }
+}
+
+pub fn impl_struct_field_value(f: &Field) -> TokenStream {
+    let member = member_to_ident(&f.member);
+    match &f.attrs.default {
+        Some(attr::DefaultValue::Path(path)) => quote! { #member.unwrap_or_else(#path) },
+        Some(attr::DefaultValue::Trait) => quote! { #member.unwrap_or_default() },
+        None => {
+            let msg = format!("'{}' is missing", field_to_id(f));
+            quote! { #member.expect(#msg) }
+        }
     }
 }
 
@@ -448,7 +1161,21 @@ fn is_option(ty: &Type) -> bool {
     false
 }
 
-pub fn member_to_id(member: &Member) -> String {
+pub fn field_to_id(field: &Field) -> String {
+    match &field.attrs.rename {
+        Some(rename) => rename.clone(),
+        None => member_to_id(&field.member),
+    }
+}
+
+fn variant_to_id(variant: &Variant) -> String {
+    match &variant.attrs.rename {
+        Some(rename) => rename.clone(),
+        None => to_snake_case(&variant.ident.to_string()),
+    }
+}
+
+fn member_to_id(member: &Member) -> String {
     match member {
         Member::Named(name) => to_snake_case(&name.to_string()),
         Member::Unnamed(idx) => idx.index.to_string(),
@@ -464,10 +1191,16 @@ fn member_to_ident(member: &Member) -> Ident {
     }
 }
 
-fn try_from_ext_bson_to_type(name: &Ident) -> TokenStream {
+fn try_from_ext_bson_to_type(name: &Ident, bounds: &Bounds) -> TokenStream {
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        from_where,
+        ..
+    } = bounds;
     quote! {
         #[automatically_derived]
-        impl TryFrom<_mongo::ext::bson::Bson> for #name {
+        impl #impl_generics TryFrom<_mongo::ext::bson::Bson> for #name #ty_generics #from_where {
             type Error = _mongo::ext::bson::de::Error;
             fn try_from(bson: _mongo::ext::bson::Bson) -> core::result::Result<Self, Self::Error> {
                 Self::try_from(bson.0)
@@ -476,14 +1209,141 @@ fn try_from_ext_bson_to_type(name: &Ident) -> TokenStream {
     }
 }
 
-fn try_from_type_to_ext_bson(name: &Ident) -> TokenStream {
+fn try_from_type_to_ext_bson(name: &Ident, bounds: &Bounds) -> TokenStream {
+    let Bounds {
+        impl_generics,
+        ty_generics,
+        into_where,
+        ..
+    } = bounds;
     quote! {
         #[automatically_derived]
-        impl TryFrom<#name> for _mongo::ext::bson::Bson {
+        impl #impl_generics TryFrom<#name #ty_generics> for _mongo::ext::bson::Bson #into_where {
             type Error = _mongo::ext::bson::ser::Error;
-            fn try_from(value: #name) -> core::result::Result<Self, Self::Error> {
+            fn try_from(value: #name #ty_generics) -> core::result::Result<Self, Self::Error> {
                 Ok(_mongo::ext::bson::Bson(_mongo::bson::Bson::try_from(value)?))
             }
         }
     }
 }
+
+// The generics of the container, along with the bounds needed to convert it in each direction.
+//
+// Only the fields whose types use the container's type parameters are bounded, and those handled
+// by serde or `with` are left to the bounds on the container itself.
+pub struct Bounds {
+    impl_generics: TokenStream,
+    ty_generics: TokenStream,
+    into_where: TokenStream,
+    from_where: TokenStream,
+}
+
+impl Bounds {
+    pub fn from<'a>(generics: &syn::Generics, fields: impl Iterator<Item = &'a Field<'a>>) -> Self {
+        let params = generics
+            .type_params()
+            .map(|p| p.ident.clone())
+            .collect::<Vec<_>>();
+        let mut into = generics.clone();
+        let mut from = generics.clone();
+        // Nested fields are bounded on the types within their containers
+        let types = fields.flat_map(|f| {
+            if f.attrs.serde || f.attrs.with.is_some() {
+                vec![]
+            } else if f.attrs.nested {
+                Nested::leaves(f.ty)
+            } else {
+                vec![f.ty]
+            }
+        });
+        for ty in types {
+            if !uses_params(ty, &params) {
+                continue;
+            }
+            // Smart pointers are bounded on their pointee, which shared pointers need to clone
+            let ty = match Pointer::from(option_inner(ty).unwrap_or(ty)) {
+                Some((Pointer::Box, inner)) => inner,
+                Some((_, inner)) => {
+                    into.make_where_clause()
+                        .predicates
+                        .push(parse_quote! { #inner: Clone });
+                    inner
+                }
+                None => ty,
+            };
+            into.make_where_clause()
+                .predicates
+                .extend::<[syn::WherePredicate; 2]>([
+                    parse_quote! { _mongo::ext::bson::Bson: TryFrom<#ty> },
+                    parse_quote! {
+                        _mongo::ext::bson::ser::Error:
+                            From<<_mongo::ext::bson::Bson as TryFrom<#ty>>::Error>
+                    },
+                ]);
+            let ty = option_inner(ty).unwrap_or(ty);
+            from.make_where_clause()
+                .predicates
+                .extend::<[syn::WherePredicate; 2]>([
+                    parse_quote! { #ty: TryFrom<_mongo::ext::bson::Bson> },
+                    parse_quote! {
+                        _mongo::ext::bson::de::Error:
+                            From<<#ty as TryFrom<_mongo::ext::bson::Bson>>::Error>
+                    },
+                ]);
+        }
+        let (impl_generics, ty_generics, _) = generics.split_for_impl();
+        Bounds {
+            impl_generics: impl_generics.to_token_stream(),
+            ty_generics: ty_generics.to_token_stream(),
+            into_where: into.split_for_impl().2.to_token_stream(),
+            from_where: from.split_for_impl().2.to_token_stream(),
+        }
+    }
+}
+
+fn uses_params(ty: &Type, params: &[Ident]) -> bool {
+    fn visit(tokens: TokenStream, params: &[Ident]) -> bool {
+        tokens.into_iter().any(|t| match t {
+            TokenTree::Ident(ident) => params.contains(&ident),
+            TokenTree::Group(group) => visit(group.stream(), params),
+            _ => false,
+        })
+    }
+    visit(ty.to_token_stream(), params)
+}
+
+// The borrowed type of a `Cow`, going by the last segment of its path. These are converted from
+// a borrow on the `into` side, and always into an owned value on the `from` side.
+fn cow_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Cow" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+// The inner type of an `Option`, matching the crude check of `is_option`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    if !is_option(ty) {
+        return None;
+    }
+    match &ty {
+        Type::Path(path) => match &path.path.segments.iter().next()?.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
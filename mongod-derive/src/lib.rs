@@ -46,234 +46,1531 @@ mod mongo;
 /// Derives implementations for `TryFrom` so that the decorated type can be converted `to` & `from`
 /// BSON.
 ///
+/// Newtypes are converted transparently as their inner type, while other tuple structs are
+/// converted into documents keyed by the index of each field.
+///
+/// Generic types are supported, where the fields using the type parameters are bounded so that
+/// the type only converts when its fields can.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// struct Envelope<T> {
+///     kind: String,
+///     payload: T,
+/// }
+///
+/// let envelope = Envelope { kind: "count".to_owned(), payload: 1_i32 };
+///
+/// let bson = mongod::bson::Bson::try_from(envelope).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "kind": "count", "payload": 1 }));
+///
+/// let envelope = Envelope::<i32>::try_from(bson).unwrap();
+/// assert_eq!(envelope.payload, 1);
+/// ```
+///
+/// Types can also borrow their data. `Cow` fields are converted into BSON from a borrow, and are
+/// always owned when converted from BSON. Fields holding a reference can only be converted into
+/// BSON, so only `#[bson(into)]` is derived for such types.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::borrow::Cow;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// struct Article<'a> {
+///     body: Cow<'a, str>,
+/// }
+///
+/// #[derive(Bson)]
+/// struct Comment<'a> {
+///     body: &'a str,
+/// }
+///
+/// let text = "foo".to_owned();
+///
+/// let bson = mongod::bson::Bson::try_from(Article { body: Cow::Borrowed(&text) }).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "body": "foo" }));
+///
+/// let article = Article::try_from(bson).unwrap();
+/// assert!(matches!(article.body, Cow::Owned(_)));
+///
+/// let bson = mongod::bson::Bson::try_from(Comment { body: &text }).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "body": "foo" }));
+/// ```
+///
+/// Smart pointers, `Box`, `Rc` & `Arc`, are stored as the value they point to. Shared values are
+/// cloned when converted into BSON if there are other references to them, so their type must
+/// implement `Clone`.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+/// use std::sync::Arc;
+///
+/// #[derive(Bson)]
+/// struct Post {
+///     title: Box<String>,
+///     tags: Arc<Vec<String>>,
+/// }
+///
+/// let tags = Arc::new(vec!["foo".to_owned()]);
+/// let post = Post { title: Box::new("bar".to_owned()), tags: Arc::clone(&tags) };
+///
+/// let bson = mongod::bson::Bson::try_from(post).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "title": "bar", "tags": ["foo"] }));
+///
+/// let post = Post::try_from(bson).unwrap();
+/// assert_eq!(post.tags, tags);
+/// ```
+///
+/// Fields are converted using the [`mongod::ext::bson`][ext-bson] conversions, so a field whose
+/// type lacks them is reported on the field. Such fields can instead be converted using
+/// `#[bson(serde)]` or `#[bson(with = "...")]`, while primitives that have no BSON equivalent,
+/// such as `usize`, are rejected up front.
+///
+/// Bytes, such as `Vec<u8>`, are stored as binary, they can instead be stored as an array of
+/// integers using `#[bson(with = "mongod::ext::bson::as_array")]`.
+///
+/// ```compile_fail
+/// # use mongod_derive::Bson;
+/// #[derive(Bson)]
+/// struct Page {
+///     // error: `usize` has no BSON equivalent, use `u64` instead...
+///     offset: usize,
+/// }
+/// ```
+///
+/// [ext-bson]: https://docs.rs/mongod/latest/mongod/ext/bson/index.html
+///
 /// ## Container Attributes
 ///
-/// - #[bson(from)]: derives `TryFrom` on `Bson` for `type`
-/// - #[bson(into)]: derives `TryFrom` on `type` for `Bson`
+/// - #[bson(from)]: derives `TryFrom` on `Bson` for `type`
+/// - #[bson(into)]: derives `TryFrom` on `type` for `Bson`
+/// - #[bson(repr = "...")]: stores the variants of a unit enum as integers
+/// - #[bson(scalar)]: converts `type` using its own `ToBson` & `FromBson` implementations
+/// - #[bson(skip_none)]: applies `#[bson(skip_none)]` to every field
+/// - #[bson(tag = "...")]: sets the key holding the variant of an enum
+/// - #[bson(tag = "...", content = "...")]: holds the variant & its fields under separate keys
+/// - #[bson(untagged)]: omits the variant of an enum, matching on its fields instead
+/// - #[bson(variant_case = "...")]: sets the case of the variants of an enum
+///
+/// ### `#[bson(from)]`
+///
+/// Tells the derive to only implement the `from` parts of the derive, i.e. deserialising only.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Bson)]
+/// #[bson(from)]
+/// struct User {
+///     name: String,
+/// }
+/// let mut doc = mongod::bson::Document::new();
+/// doc.insert("name", "foo".to_owned());
+/// let bson = mongod::bson::Bson::Document(doc);
+///
+/// let user = User::try_from(bson).unwrap();
+///
+/// println!("{:?}", user);
+/// ```
+///
+/// ### `#[bson(into)]`
+///
+/// Tells the derive to only implement the `into` parts of the derive, i.e. serialising only.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// #[bson(into)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let user = User { name: "foo".to_owned() };
+///
+/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+///
+/// println!("{:?}", bson);
+/// ```
+///
+/// ### `#[bson(tag = "...")]`, `#[bson(content = "...")]` & `#[bson(untagged)]`
+///
+/// Enums where every variant is a unit variant are converted to & from strings, while others are
+/// converted to documents holding the variant's fields along with a `_type` key naming the
+/// variant. These attributes change how the variant is represented.
+///
+/// - `tag`: names the key that holds the variant, instead of `_type`
+/// - `content`: holds the variant's fields in a document under this key, next to the `tag`
+/// - `untagged`: holds only the variant's fields, where unit variants are `null`. When converting
+///   from BSON the first variant whose fields are all present is used
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// use mongod::bson::{bson, Bson};
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(tag = "kind", content = "data")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Square { side: f64 },
+/// }
+///
+/// let bson = Bson::try_from(Shape::Circle { radius: 1.0 }).unwrap();
+/// assert_eq!(bson, bson!({ "kind": "circle", "data": { "radius": 1.0 } }));
+/// assert_eq!(Shape::try_from(bson).unwrap(), Shape::Circle { radius: 1.0 });
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(untagged)]
+/// enum Size {
+///     Circle { radius: f64 },
+///     Square { side: f64 },
+/// }
+///
+/// let bson = Bson::try_from(Size::Square { side: 2.0 }).unwrap();
+/// assert_eq!(bson, bson!({ "side": 2.0 }));
+/// assert_eq!(Size::try_from(bson).unwrap(), Size::Square { side: 2.0 });
+/// ```
+///
+/// ### `#[bson(repr = "...")]`
+///
+/// Tells the derive to store the variants of an enum as their discriminants instead of their
+/// names, for collections that already encode the enum numerically. This is one of `"i32"` or
+/// `"i64"`, and can only be used on enums whose variants are all unit variants. Either BSON
+/// integer is accepted when reading, regardless of the `repr`.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// use mongod::bson::Bson;
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(repr = "i32")]
+/// enum Level {
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// let bson = Bson::try_from(Level::High).unwrap();
+/// assert_eq!(bson, Bson::Int32(10));
+/// assert_eq!(Level::try_from(bson).unwrap(), Level::High);
+/// assert_eq!(Level::try_from(Bson::Int64(1)).unwrap(), Level::Low);
+/// assert!(Level::try_from(Bson::Int32(2)).is_err());
+/// ```
+///
+/// ### `#[bson(scalar)]`
+///
+/// Tells the derive to convert the type using its implementations of
+/// [`ToBson`][to-bson] & [`FromBson`][from-bson] rather than its fields or variants. This is how
+/// downstream types with custom representations are plugged into the conversions used by other
+/// derives, so that they can be used as fields, in filters & in updates.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// use mongod::ext::bson::{de, ser, FromBson, ToBson};
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(scalar)]
+/// struct Version(u16, u16);
+///
+/// impl ToBson for Version {
+///     fn to_bson(self) -> Result<mongod::bson::Bson, ser::Error> {
+///         Ok(mongod::bson::Bson::String(format!("{}.{}", self.0, self.1)))
+///     }
+/// }
+///
+/// impl FromBson for Version {
+///     fn from_bson(bson: mongod::bson::Bson) -> Result<Self, de::Error> {
+///         let s = String::try_from(mongod::ext::bson::Bson(bson))?;
+///         let mut parts = s.split('.').map(str::parse);
+///         match (parts.next(), parts.next(), parts.next()) {
+///             (Some(Ok(major)), Some(Ok(minor)), None) => Ok(Version(major, minor)),
+///             _ => Err(<mongod::bson::de::Error as de::ErrorExt>::custom(format!(
+///                 "invalid version `{}`",
+///                 s
+///             ))
+///             .into()),
+///         }
+///     }
+/// }
+///
+/// #[derive(Bson)]
+/// struct Package {
+///     version: Version,
+/// }
+///
+/// let bson = mongod::bson::Bson::try_from(Package { version: Version(1, 2) }).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "version": "1.2" }));
+/// assert_eq!(Package::try_from(bson).unwrap().version, Version(1, 2));
+/// ```
+///
+/// [to-bson]: https://docs.rs/mongod/latest/mongod/ext/bson/trait.ToBson.html
+/// [from-bson]: https://docs.rs/mongod/latest/mongod/ext/bson/trait.FromBson.html
+///
+/// ### `#[bson(variant_case = "...")]`
+///
+/// Tells the derive to convert the names of the variants to the given case, instead of
+/// snake_case. This is one of `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`,
+/// `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` or `"SCREAMING-KEBAB-CASE"`, matching
+/// serde's `rename_all`. Variants using `#[bson(rename = "...")]` keep their given name.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// use mongod::bson::Bson;
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(variant_case = "SCREAMING_SNAKE_CASE")]
+/// enum Status {
+///     InProgress,
+///     #[bson(rename = "finished")]
+///     Done,
+/// }
+///
+/// let bson = Bson::try_from(Status::InProgress).unwrap();
+/// assert_eq!(bson, Bson::String("IN_PROGRESS".to_owned()));
+/// assert_eq!(Status::try_from(bson).unwrap(), Status::InProgress);
+///
+/// let bson = Bson::try_from(Status::Done).unwrap();
+/// assert_eq!(bson, Bson::String("finished".to_owned()));
+/// ```
+///
+/// ## Variant Attributes
+///
+/// - #[bson(rename = "...")]
+///
+/// ### `#[bson(rename = "...")]`
+///
+/// Tells the derive to use the given name for the decorated variant, instead of the snake_case
+/// of its name.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// enum Role {
+///     #[bson(rename = "ADMIN")]
+///     Admin,
+///     User,
+/// }
+///
+/// let bson = mongod::bson::Bson::try_from(Role::Admin).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!("ADMIN"));
+/// ```
+///
+/// ## Field Attributes
+///
+/// - #[bson(default)] & #[bson(default = "...")]
+/// - #[bson(nested)]
+/// - #[bson(rename = "...")]
+/// - #[bson(serde)]
+/// - #[bson(skip_none)]
+/// - #[bson(with = "...")]
+///
+/// ### `#[bson(default)]` & `#[bson(default = "...")]`
+///
+/// Tells the derive to fill in the decorated field when it is missing from the document, rather
+/// than erroring. The value comes from `Default`, or from calling the function at the given path.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// fn guest() -> String {
+///     "guest".to_owned()
+/// }
+///
+/// #[derive(Bson)]
+/// struct User {
+///     #[bson(default = "guest")]
+///     name: String,
+///     #[bson(default)]
+///     logins: u32,
+/// }
+///
+/// let user = User::try_from(mongod::bson::bson!({})).unwrap();
+///
+/// assert_eq!(user.name, "guest");
+/// assert_eq!(user.logins, 0);
+/// ```
+///
+/// ### `#[bson(nested)]`
+///
+/// Tells the derive to convert the decorated field element by element, so that collections of
+/// types that derive `Bson` themselves can be converted without `#[bson(serde)]`. A field of a
+/// derived type converts by itself, but the conversions of `ext::bson` for `Vec` and the like
+/// cannot make use of the derived conversions as they are fallible.
+///
+/// `Box`, `Option`, `Vec`, `VecDeque`, `LinkedList`, `HashSet`, `BTreeSet`, `IndexSet`, `HashMap`,
+/// `BTreeMap` & `IndexMap` are unwrapped, in any combination, until reaching a type that converts
+/// by itself.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::collections::HashMap;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// struct Address {
+///     city: String,
+/// }
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// struct User {
+///     #[bson(nested)]
+///     addresses: Vec<Address>,
+///     #[bson(nested)]
+///     contacts: Option<HashMap<String, Address>>,
+/// }
+///
+/// let user = User {
+///     addresses: vec![Address { city: "London".to_owned() }],
+///     contacts: None,
+/// };
+///
+/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+/// assert_eq!(
+///     bson,
+///     mongod::bson::bson!({ "addresses": [{ "city": "London" }], "contacts": null }),
+/// );
+/// assert_eq!(
+///     User::try_from(bson).unwrap().addresses,
+///     vec![Address { city: "London".to_owned() }],
+/// );
+/// ```
+///
+/// ### `#[bson(rename = "...")]`
+///
+/// Tells the derive to use the given key for the decorated field, instead of the snake_case of
+/// its name.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// struct User {
+///     name: String,
+///     #[bson(rename = "emailAddress")]
+///     email_address: String,
+/// }
+///
+/// let user = User { name: "foo".to_owned(), email_address: "foo@bar.com".to_owned() };
+///
+/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+///
+/// assert_eq!(bson, mongod::bson::bson!({ "name": "foo", "emailAddress": "foo@bar.com" }));
+/// ```
+///
+/// ### `#[bson(serde)]`
+///
+/// Tells the derive to use `serde` for the decorated field.
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// struct User {
+///     name: String,
+///     #[bson(serde)]
+///     age: u32,
+/// }
+///
+/// let user = User { name: "foo".to_owned(), age: 0 };
+///
+/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+///
+/// println!("{:?}", bson);
+/// ```
+///
+/// ### `#[bson(skip_none)]`
+///
+/// Tells the derive to leave the decorated field out of the document when it converts to `null`,
+/// i.e. when an `Option` is `None`, rather than inserting `null`. A missing field is then treated
+/// as `null`, so documents holding either are accepted. This can also be applied to every field
+/// at once using `#[bson(skip_none)]` on the type.
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Bson)]
+/// struct User {
+///     name: String,
+///     #[bson(skip_none)]
+///     email: Option<String>,
+/// }
+///
+/// let user = User { name: "foo".to_owned(), email: None };
+///
+/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "name": "foo" }));
+///
+/// let user = User::try_from(bson).unwrap();
+/// assert_eq!(user.email, None);
+/// ```
+///
+/// ### `#[bson(with = "...")]`
+///
+/// Tells the derive to convert the decorated field using the `to_bson` & `from_bson` functions
+/// of the given module, rather than its own conversions.
+///
+/// - `fn to_bson(value: T) -> Result<bson::Bson, mongod::ext::bson::ser::Error>`
+/// - `fn from_bson(bson: bson::Bson) -> Result<T, mongod::ext::bson::de::Error>`
+///
+/// When used with `Mongo`, the field's comparators in the derived `Filter` hold the value wrapped
+/// in `mongod::ext::bson::With` so that it is converted using the same function.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use std::convert::TryFrom;
+/// use std::time::Duration;
+///
+/// use mongod::{AsFilter, Filter};
+///
+/// mod millis {
+///     use std::convert::TryFrom;
+///     use std::time::Duration;
+///
+///     use mongod::bson::Bson;
+///     use mongod::ext::bson::{de, ser};
+///
+///     pub fn to_bson(value: Duration) -> Result<Bson, ser::Error> {
+///         Ok(Bson::Int64(value.as_millis() as i64))
+///     }
+///
+///     pub fn from_bson(bson: Bson) -> Result<Duration, de::Error> {
+///         let millis = i64::try_from(mongod::ext::bson::Bson(bson))?;
+///         Ok(Duration::from_millis(millis as u64))
+///     }
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "jobs", filter, fluent)]
+/// pub struct Job {
+///     name: String,
+///     #[bson(with = "millis")]
+///     timeout: Duration,
+/// }
+///
+/// # pub fn main() {
+/// let job = Job { name: "foo".to_owned(), timeout: Duration::from_secs(1) };
+///
+/// let bson = mongod::bson::Bson::try_from(job).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "name": "foo", "timeout": 1000_i64 }));
+///
+/// let job = Job::try_from(bson).unwrap();
+/// assert_eq!(job.timeout, Duration::from_secs(1));
+///
+/// let filter = Job::filter().timeout_gt(Duration::from_secs(2));
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "timeout": { "$gt": 2000_i64 } },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+#[proc_macro_derive(Bson, attributes(bson))]
+pub fn derive_bson(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    bson::expand_derive_bson(&input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
+/// Derives mongo traits on the decorated type.
+///
+/// ## Container Attributes
+///
+/// - `#[mongo(collection = "...")]`: derives the `Collection` trait
+/// - `#[mongo(collection_fn = "...")]`: derives the `Collection` trait with a runtime name
+/// - `#[mongo(field)]`: derives the `AsField` & `Field` traits
+/// - `#[mongo(filter)]`: derives the `AsFilter` & `Filter` traits
+/// - `#[mongo(fluent)]`: derives fluent methods on the derived `Filter` & `Update`
+/// - `#[mongo(index(...))]`: declares an index returned by `Collection::indexes`
+/// - `#[mongo(json_schema)]`: derives the `JsonSchema` trait
+/// - `#[mongo(module = "...")]`: names the module holding the derived types
+/// - `#[mongo(oid)]`: derives the `_id` field for derived `Filter` traits & the `id` field
+/// - `#[mongo(options(...))]`: declares the options returned by `Collection::create_options`
+/// - `#[mongo(query_string)]`: derives parsing of the derived `Filter` from URL query strings
+/// - `#[mongo(types(...))]`: declares the derived types alongside the type instead of in a module
+/// - `#[mongo(update)]`: derives the `AsUpdate` & `Update` traits
+/// - `#[mongo(view(...))]`: derives projections of the type implementing the `View` trait
+///
+/// Newtypes delegate `field`, `filter` & `update` to their inner type, so that they share its
+/// derived `Field`, `Filter` & `Update`, and are converted to & from BSON as their inner type.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Collection, Comparator, Filter};
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", field, filter)]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "admins", field, filter)]
+/// pub struct Admin(User);
+///
+/// # pub fn main() {
+/// let mut filter = Admin::filter();
+/// filter.name = Some(Comparator::Eq("foo".to_owned()));
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "name": { "$eq": "foo" } },
+/// );
+///
+/// let admin = Admin(User { name: "foo".to_owned() });
+/// assert_eq!(admin.into_document().unwrap(), mongod::bson::doc! { "name": "foo" });
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// Other tuple structs can derive `collection` & `field`, where the fields are keyed by their
+/// index, but not `filter` or `update`.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::Collection;
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "pairs", field)]
+/// pub struct Pair(String, u32);
+///
+/// # pub fn main() {
+/// assert_eq!(String::from(pair::Field::_1), "1");
+/// assert_eq!(
+///     Pair("foo".to_owned(), 1).into_document().unwrap(),
+///     mongod::bson::doc! { "0": "foo", "1": 1 },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// Structs with named fields also get a `FIELDS` constant holding the key of each field, taking
+/// any renames into account, so that raw documents, hints & aggregation stages do not need to
+/// repeat them as string literals.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::doc;
+///
+/// #[derive(Bson, Mongo)]
+/// pub struct User {
+///     name: String,
+///     #[bson(rename = "years")]
+///     age: u32,
+/// }
+///
+/// # pub fn main() {
+/// let stage = doc! { "$sort": { User::FIELDS.age: -1 } };
+/// assert_eq!(stage, doc! { "$sort": { "years": -1 } });
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// Generic types can derive `collection` & `field`, but not `filter` or `update` as those would
+/// need to be generic too.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::Collection;
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "events", field)]
+/// pub struct Event<T> {
+///     kind: String,
+///     payload: T,
+/// }
+///
+/// # pub fn main() {
+/// let event = Event { kind: "count".to_owned(), payload: 1_i32 };
+/// assert_eq!(
+///     event.into_document().unwrap(),
+///     mongod::bson::doc! { "kind": "count", "payload": 1 },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(collection = "...")]`
+///
+/// Tells the derive to implement the `Collection` trait where the `"..."` is the name of the
+/// collection.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(collection = "users")]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+/// # }
+/// ```
+///
+/// ### `#[mongo(collection_fn = "...")]`
+///
+/// Tells the derive to implement the `Collection` trait where the `"..."` is the path to a
+/// function returning the name of the collection as a `String`, for names that are only known at
+/// runtime. The `COLLECTION` constant is then the type's name in snake_case, unless
+/// `#[mongo(collection = "...")]` is also given.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::Collection;
+///
+/// fn events() -> String {
+///     format!("events_{}", 2024)
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection_fn = "events")]
+/// pub struct Event {
+///     kind: String,
+/// }
+///
+/// # pub fn main() {
+/// assert_eq!(Event::COLLECTION, "event");
+/// assert_eq!(Event::collection(), "events_2024");
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(field)]`
+///
+/// Tells the derive to implement the `AsField` & `Field` traits.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(field)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// // The derived field enum can be exposed from the derived module which uses the type's name in
+/// // snake_case
+/// use self::user::Field;
+/// # }
+/// ```
+///
+/// ### `#[mongo(filter)]`
+///
+/// Tells the derive to implement the `AsFilter` & `Filter` traits. The derived filter also
+/// implements `Debug`, so the types of filtered fields must implement `Debug`.
+///
+/// The derived filter implements `MergeFilter`, whose `merge` merges another filter into it
+/// field-wise, where the fields set in the other filter take precedence. This allows mandatory
+/// constraints to be layered onto a user supplied filter.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(filter)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// // The derived filter struct can be exposed from the derived module which uses the type's name in
+/// // snake_case
+/// use self::user::Filter;
+///
+/// # pub fn main() {
+/// use mongod::{AsFilter, Comparator, Filter as _, MergeFilter};
+///
+/// let mut user = User::filter();
+/// user.name = Some(Comparator::Eq("foo".to_owned()));
+/// user.age = Some(Comparator::Gt(18));
+/// let mut mandatory = User::filter();
+/// mandatory.age = Some(Comparator::Gte(21));
+///
+/// assert_eq!(
+///     user.merge(mandatory).into_document().unwrap(),
+///     mongod::bson::doc! { "name": { "$eq": "foo" }, "age": { "$gte": 21 } },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(fluent)]`
+///
+/// Tells the derive to implement chainable methods on the derived `Filter` for each of its fields,
+/// named after the field and comparator, i.e. `name_eq`, `age_gt` & `age_in`.
+///
+/// When used with `#[mongo(update)]`, chainable methods are also implemented on the derived
+/// `Update` for each of its fields, named after the update operator and the field, i.e.
+/// `set_age`, `inc_logins` & `unset_email`. These return a derived `Updater` which can be converted
+/// into `Updates`.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::{AsFilter, Filter};
+///
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(filter, fluent)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// # pub fn main() {
+/// let filter = User::filter().name_eq("foo".to_owned()).age_gt(18);
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "name": { "$eq": "foo" }, "age": { "$gt": 18 } },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::{AsUpdate, Updates};
+///
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(update, fluent)]
+/// pub struct User {
+///     age: u32,
+///     logins: u32,
+///     email: Option<String>,
+/// }
+///
+/// # pub fn main() {
+/// let updates: Updates<_> = User::update()
+///     .set_age(30)
+///     .inc_logins(1)
+///     .unset_email()
+///     .into();
+/// assert_eq!(
+///     updates.into_document().unwrap(),
+///     mongod::bson::doc! {
+///         "$inc": { "logins": 1 },
+///         "$set": { "age": 30 },
+///         "$unset": { "email": "" },
+///     },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(index(...))]`
+///
+/// Declares an index on the collection, returned by the derived `Collection::indexes`, and can be
+/// repeated for each index. Requires `collection`.
+///
+/// - `fields(...)`: the fields to index in order, where those prefixed with `-` are descending
+/// - `name = "..."`: the name of the index
+/// - `sparse`: only index the documents that contain the fields
+/// - `unique`: reject documents that duplicate the indexed fields
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::Collection;
+///
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(collection = "users")]
+/// #[mongo(index(fields(name, -created_at), unique, name = "by_name"))]
+/// #[mongo(index(fields(email), sparse))]
+/// pub struct User {
+///     name: String,
+///     email: Option<String>,
+///     created_at: i64,
+/// }
+///
+/// # pub fn main() {
+/// let indexes = User::indexes();
+/// assert_eq!(indexes[0].keys, mongod::bson::doc! { "name": 1, "created_at": -1 });
+/// let options = indexes[0].options.as_ref().unwrap();
+/// assert_eq!(options.name.as_deref(), Some("by_name"));
+/// assert_eq!(options.unique, Some(true));
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// The indexes can then be created using the driver.
+///
+/// ```no_run
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # use mongod::Collection;
+/// # #[derive(mongod_derive::Bson, Mongo)]
+/// # #[mongo(collection = "users", index(fields(name), unique))]
+/// # pub struct User {
+/// #     name: String,
+/// # }
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+/// client
+///     .collection::<User>()
+///     .create_indexes(User::indexes(), None)
+///     .await
+///     .unwrap();
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+///
+/// ### `#[mongo(json_schema)]`
+///
+/// Tells the derive to implement the `JsonSchema` trait, describing the type as a `$jsonSchema`
+/// which can be used to validate the collection on the server. Fields are required unless they are
+/// an `Option` or have a `#[bson(default)]`, while fields using `#[bson(serde)]` or
+/// `#[bson(with = "...")]` are left unconstrained. Unit enums deriving `Bson` describe their
+/// variants as `enum` values.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::doc;
+/// use mongod::JsonSchema;
+///
+/// #[derive(Bson)]
+/// pub enum Role {
+///     Admin,
+///     User,
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", json_schema)]
+/// pub struct User {
+///     name: String,
+///     age: Option<u32>,
+///     role: Role,
+///     tags: Vec<String>,
+/// }
+///
+/// # pub fn main() {
+/// assert_eq!(User::json_schema(), doc! {
+///     "bsonType": "object",
+///     "required": ["name", "role", "tags"],
+///     "properties": {
+///         "name": { "bsonType": "string" },
+///         "age": { "bsonType": ["int", "long", "null"] },
+///         "role": { "bsonType": "string", "enum": ["admin", "user"] },
+///         "tags": { "bsonType": "array", "items": { "bsonType": "string" } },
+///     },
+/// });
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(module = "...")]`
+///
+/// Tells the derive to use the given name for the module holding the derived types, instead of the
+/// type's name in snake_case.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(filter, module = "users")]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// use self::users::Filter;
+/// # }
+/// ```
+///
+/// ### `#[mongo(oid)]`
+///
+/// Tells the derive to implement the `_id` field for derived `Filter` traits.
 ///
-/// ### `#[bson(from)]`
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(filter, oid)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
 ///
-/// Tells the derive to only implement the `from` parts of the derive, i.e. deserialising only.
+/// // The derived filter struct can be exposed from the derived module which uses the type's name in
+/// // snake_case
+/// use self::user::Filter;
+/// # }
+/// ```
+///
+/// A field named `id`, of type `ObjectId` or `Option<ObjectId>`, then holds the document's `_id`.
+/// It is stored as `_id`, populated when converting from a document, and left out of the
+/// derived `Filter` & `Update`. An `id` of `None` is left out of the document so that the `_id`
+/// is generated on insert. This also derives `Collection::id` & `Collection::set_id`, which
+/// `Client::save` uses to insert or replace the document.
 ///
 /// ```
-/// # use mongod_derive::Bson;
-/// use std::convert::TryFrom;
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::oid::ObjectId;
+/// use mongod::Collection;
 ///
-/// #[derive(Debug, Bson)]
-/// #[bson(from)]
-/// struct User {
+/// #[derive(Bson, Clone, Mongo)]
+/// #[mongo(collection = "users", filter, oid)]
+/// pub struct User {
+///     id: Option<ObjectId>,
 ///     name: String,
 /// }
-/// let mut doc = mongod::bson::Document::new();
-/// doc.insert("name", "foo".to_owned());
-/// let bson = mongod::bson::Bson::Document(doc);
 ///
-/// let user = User::try_from(bson).unwrap();
+/// # pub fn main() {
+/// let mut user = User { id: None, name: "foo".to_owned() };
+/// assert_eq!(user.clone().into_document().unwrap(), mongod::bson::doc! { "name": "foo" });
 ///
-/// println!("{:?}", user);
+/// let id = ObjectId::new();
+/// user.set_id(id.into());
+/// assert_eq!(user.id(), Some(id.into()));
+/// assert_eq!(
+///     user.into_document().unwrap(),
+///     mongod::bson::doc! { "_id": id, "name": "foo" },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
 /// ```
 ///
-/// ### `#[bson(into)]`
+/// ### `#[mongo(options(...))]`
 ///
-/// Tells the derive to only implement the `into` parts of the derive, i.e. serialising only.
+/// Tells the derive to return the given options from `Collection::create_options`, which are
+/// used when creating the collection with `Client::create_collection`. The options are:
+///
+/// - `capped`: whether the collection is capped, which also requires a `size`
+/// - `collation = "..."`: the locale of the default collation, where a `_ci` suffix makes it
+///   case-insensitive
+/// - `max = ...`: the maximum number of documents in a capped collection
+/// - `size = ...`: the maximum size in bytes of a capped collection
+///
+/// This requires `#[mongo(collection = "...")]`.
 ///
 /// ```
-/// # use mongod_derive::Bson;
-/// use std::convert::TryFrom;
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::Collection;
 ///
-/// #[derive(Bson)]
-/// #[bson(into)]
-/// struct User {
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "logs", options(capped, size = 1048576, collation = "en_ci"))]
+/// pub struct Log {
+///     line: String,
+/// }
+///
+/// # pub fn main() {
+/// let options = Log::create_options().unwrap();
+/// assert_eq!(options.capped, Some(true));
+/// assert_eq!(options.size, Some(1048576));
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(query_string)]`
+///
+/// Tells the derive to implement `from_query_string` on the derived `Filter`, which parses a URL
+/// query string into the `Filter` and a `Sort`, validating field names against the derived
+/// `Field`. This requires both `#[mongo(field)]` & `#[mongo(filter)]`, see
+/// [`mongod::query_string`](https://docs.rs/mongod/latest/mongod/query_string/index.html) for the
+/// supported syntax. A field can only be filtered once, as a `Comparator` holds a single operator.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::Filter;
+///
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(field, filter, query_string)]
+/// pub struct User {
 ///     name: String,
+///     age: u32,
 /// }
 ///
-/// let user = User { name: "foo".to_owned() };
+/// # pub fn main() {
+/// let (filter, sort) = user::Filter::from_query_string("?name=foo&age[gte]=18&sort=-age").unwrap();
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "name": { "$eq": "foo" }, "age": { "$gte": 18 } },
+/// );
+/// assert_eq!(sort.into_document(), mongod::bson::doc! { "age": -1 });
 ///
-/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+/// assert!(user::Filter::from_query_string("?email=foo").is_err());
+/// assert!(user::Filter::from_query_string("?age[gte]=18&age[lte]=30").is_err());
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
 ///
-/// println!("{:?}", bson);
+/// ### `#[mongo(types)]` & `#[mongo(types(...))]`
+///
+/// Tells the derive to declare the derived types alongside the type, named after it, rather than
+/// in a module. The derived impls are then kept in an unnamed constant, which allows the type to
+/// be derived within a function body without tripping the `non_local_definitions` lint. The
+/// derived types can be renamed using `field = "..."`, `fields = "..."`, `filter = "..."`,
+/// `update = "..."` & `updater = "..."`, they default to `{Type}Field`, `{Type}Fields`,
+/// `{Type}Filter`, `{Type}Update` & `{Type}Updater`, and share the visibility of the type.
+///
+/// This cannot be combined with `#[mongo(module = "...")]`.
+///
+/// ```
+/// use mongod::{AsFilter, Comparator, Filter};
+/// use mongod_derive::{Bson, Mongo};
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(field, filter, types(filter = "UserQuery"))]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// let mut filter: UserQuery = User::filter();
+/// filter.name = Some(Comparator::Eq("foo".to_owned()));
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "name": { "$eq": "foo" } },
+/// );
+/// assert_eq!(String::from(UserField::Name), "name");
+/// ```
+///
+/// ### `#[mongo(update)]`
+///
+/// Tells the derive to implement the `AsUpdate` & `Update` traits.
+///
+/// Each field of the derived `Update` is an `Option` of the field's type, apart from fields that
+/// are already an `Option<T>` which become a `FieldUpdate<T>` so that leaving the field untouched,
+/// setting it to `null` & unsetting it can be told apart.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(update)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// // The derived update struct can be exposed from the derived module which uses the type's name in
+/// // snake_case
+/// use self::user::Update;
+/// # }
+/// ```
+///
+/// ### `#[mongo(view(...))]`
+///
+/// Tells the derive to declare a view for each `Name = [field, ...]`, a struct named after the
+/// type suffixed with the view's name holding only the given fields. The view implements the
+/// `View` trait so that it can be queried using `Find::into_view`, which only fetches its fields.
+/// This requires `#[mongo(collection = "...")]`.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::doc;
+/// use mongod::View;
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", view(Summary = [name, age]))]
+/// pub struct User {
+///     pub name: String,
+///     pub age: u32,
+///     pub email: String,
+/// }
+///
+/// # pub fn main() {
+/// assert_eq!(UserSummary::projection(), doc! { "_id": 0, "name": 1, "age": 1 });
+///
+/// let summary = UserSummary::from_document(doc! { "name": "foo", "age": 42 }).unwrap();
+/// assert_eq!(summary.name, "foo");
+///
+/// let find = mongod::query::Find::<User>::new().into_view::<UserSummary>();
+/// # }
+/// # }
+/// # wrap::main();
 /// ```
 ///
 /// ## Field Attributes
 ///
-/// - #[bson(serde)]
+/// - `#[mongo(filter)]`: tells the derive to filter the field using its own derived `Filter`
+/// - `#[mongo(id)]`: tells the derive that the field holds the document's `_id`
+/// - `#[mongo(references = "...")]`: tells the derive that the field references another collection
+/// - `#[mongo(rename = "...")]`: tells the derive to use the given key for the field
+/// - `#[mongo(serde)]`: tells the derive that the field should be handled using serde
+/// - `#[mongo(skip)]`: tells the derive to skip the field for `field`, `filter` & `update`
+/// - `#[mongo(skip_field)]`, `#[mongo(skip_filter)]` & `#[mongo(skip_update)]`: tells the derive
+///   to skip the field for only one of `field`, `filter` or `update`
+/// - `#[mongo(validate(...))]`: tells the derive to check the field's value before it is sent
 ///
-/// ### `#[bson(serde)]`
+/// ### `#[mongo(filter)]`
+///
+/// Tells the derive to embed the field type's derived `Filter` within the derived `Filter`, so
+/// that it can be filtered field-by-field using dot-notation. The field type must also derive
+/// `Mongo` with `#[mongo(filter)]`, or be an `Option` of such a type.
 ///
-/// Tells the derive to use `serde` for the decorated field.
 /// ```
-/// # use mongod_derive::Bson;
-/// use std::convert::TryFrom;
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Comparator, Filter};
 ///
-/// #[derive(Bson)]
-/// struct User {
+/// #[derive(Bson, Mongo)]
+/// #[mongo(filter)]
+/// pub struct Address {
+///     city: String,
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", filter)]
+/// pub struct User {
 ///     name: String,
-///     #[bson(serde)]
-///     age: u32,
+///     #[mongo(filter)]
+///     address: Address,
+///     #[mongo(filter)]
+///     billing: Option<Address>,
 /// }
 ///
-/// let user = User { name: "foo".to_owned(), age: 0 };
+/// # pub fn main() {
+/// let mut filter = User::filter();
+/// filter.address.city = Some(Comparator::Eq("London".to_owned()));
+/// filter.billing.city = Some(Comparator::Ne("Paris".to_owned()));
 ///
-/// let bson = mongod::bson::Bson::try_from(user).unwrap();
+/// let doc = filter.into_document().unwrap();
+/// assert_eq!(doc, mongod::bson::doc! {
+///     "address.city": { "$eq": "London" },
+///     "billing.city": { "$ne": "Paris" },
+/// });
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(id)]`
+///
+/// Tells the derive that the field holds the document's `_id`, allowing natural keys such as an
+/// email address to be used instead of a generated `ObjectId`. The field is stored as `_id`, is
+/// filtered on as `_id` by the derived `Filter`, replacing the `_id` derived by `oid`, and is left
+/// out of the derived `Update` as the `_id` of a document cannot change. This also derives
+/// `Collection::id` & `Collection::set_id`, so that `Client::get` & `Client::save` work with the
+/// field, which can be of any type converting to & from BSON such as an `ObjectId`, `String`,
+/// `Uuid` or integer.
 ///
-/// println!("{:?}", bson);
 /// ```
-#[proc_macro_derive(Bson, attributes(bson))]
-pub fn derive_bson(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    bson::expand_derive_bson(&input)
-        .unwrap_or_else(to_compile_errors)
-        .into()
-}
-
-/// Derives mongo traits on the decorated type.
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Collection, Comparator, Filter};
 ///
-/// ## Container Attributes
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", filter)]
+/// pub struct User {
+///     #[mongo(id)]
+///     email: String,
+///     name: String,
+/// }
 ///
-/// - `#[mongo(collection = "...")]`: derives the `Collection` trait
-/// - `#[mongo(field)]`: derives the `AsField` & `Field` traits
-/// - `#[mongo(filter)]`: derives the `AsFilter` & `Filter` traits
-/// - `#[mongo(oid)]`: derives the `_id` field for derived `Field` traits
-/// - `#[mongo(update)]`: derives the `AsUpdate` & `Update` traits
+/// # pub fn main() {
+/// let user = User { email: "foo@bar.com".to_owned(), name: "foo".to_owned() };
+/// assert_eq!(user.id(), Some("foo@bar.com".into()));
+/// assert_eq!(
+///     user.into_document().unwrap(),
+///     mongod::bson::doc! { "_id": "foo@bar.com", "name": "foo" },
+/// );
 ///
-/// ### `#[mongo(collection = "...")]`
+/// let mut filter = User::filter();
+/// filter.email = Some(Comparator::Eq("foo@bar.com".to_owned()));
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "_id": { "$eq": "foo@bar.com" } },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
 ///
-/// Tells the derive to implement the `Collection` trait where the `"..."` is the name of the
-/// collection.
+/// ### `#[mongo(references = "...")]`
+///
+/// Tells the derive that the field holds the `ObjectId` of a document of the given `Collection`,
+/// deriving a `{field}_ref` method that returns it as a `mongod::Ref`, which can then be resolved
+/// using `Client::resolve` or `Client::resolve_many`. The field can be an `ObjectId`, an
+/// `Option<ObjectId>` or a `Vec<ObjectId>`, alternatively a field can be a `Ref` itself.
 ///
 /// ```
 /// # mod wrap {
-/// # use mongod_derive::Mongo;
-/// # #[derive(mongod_derive::Bson)]
-/// #[derive(Mongo)]
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::oid::ObjectId;
+/// use mongod::Ref;
+///
+/// #[derive(Bson, Mongo)]
 /// #[mongo(collection = "users")]
 /// pub struct User {
 ///     name: String,
-///     age: u32,
 /// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "posts")]
+/// pub struct Post {
+///     #[mongo(references = "User")]
+///     author: ObjectId,
+///     #[mongo(references = "User")]
+///     reviewers: Vec<ObjectId>,
+/// }
+///
+/// # pub fn main() {
+/// let id = ObjectId::new();
+/// let post = Post { author: id, reviewers: vec![] };
+/// let author: Ref<User> = post.author_ref();
+/// assert_eq!(author.id(), id);
+/// assert!(post.reviewers_ref().is_empty());
+/// # }
 /// # }
+/// # wrap::main();
 /// ```
 ///
-/// ### `#[mongo(field)]`
+/// ### `#[mongo(rename = "...")]`
 ///
-/// Tells the derive to implement the `AsField` & `Field` traits.
+/// Tells the derive to use the given key for the field in the derived `Field`, `Filter` & `Update`
+/// instead of the snake_case of its name. This is the same as `#[bson(rename = "...")]`, which
+/// both derives also understand, but when using `#[mongo(bson = "serde")]` the field must also be
+/// renamed for serde.
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Comparator, Filter};
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", field, filter)]
+/// pub struct User {
+///     name: String,
+///     #[mongo(rename = "emailAddress")]
+///     email_address: String,
+/// }
+///
+/// # pub fn main() {
+/// assert_eq!(String::from(user::Field::EmailAddress), "emailAddress");
+///
+/// let mut filter = User::filter();
+/// filter.email_address = Some(Comparator::Eq("foo@bar.com".to_owned()));
+///
+/// let doc = filter.into_document().unwrap();
+/// assert_eq!(doc, mongod::bson::doc! { "emailAddress": { "$eq": "foo@bar.com" } });
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+///
+/// ### `#[mongo(serde)]`
+///
+/// Tells the derive that the field should be handled using serde
 ///
 /// ```
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
-/// #[mongo(field)]
+/// #[mongo(collection = "users")]
 /// pub struct User {
 ///     name: String,
+///     #[mongo(serde)]
 ///     age: u32,
 /// }
-///
-/// // The derived field enum can be exposed from the derived module which uses the type's name in
-/// // snake_case
-/// use self::user::Field;
 /// # }
 /// ```
 ///
-/// ### `#[mongo(filter)]`
+/// ### `#[mongo(skip)]`
 ///
-/// Tells the derive to implement the `AsFilter` & `Filter` traits.
+/// Tells the derive to skip the field for `field`, `filter` & `update`
 ///
 /// ```
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
-/// #[mongo(filter)]
+/// #[mongo(collection = "users")]
 /// pub struct User {
 ///     name: String,
+///     #[mongo(skip)]
 ///     age: u32,
 /// }
-///
-/// // The derived filter struct can be exposed from the derived module which uses the type's name in
-/// // snake_case
-/// use self::user::Filter;
 /// # }
 /// ```
 ///
-/// ### `#[mongo(oid)]`
+/// ### `#[mongo(skip_field)]`, `#[mongo(skip_filter)]` & `#[mongo(skip_update)]`
 ///
-/// Tells the derive to implement the `_id` field for derived `Filter` traits.
+/// Tells the derive to skip the field for just the derived `Field`, `Filter` or `Update`
+/// respectively, and can be combined. For example, a password hash can be updated but never
+/// filtered on.
 ///
 /// ```
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
+/// use mongod::{AsUpdate, Update};
+///
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
-/// #[mongo(filter, oid)]
+/// #[mongo(collection = "users", field, filter, update)]
 /// pub struct User {
 ///     name: String,
-///     age: u32,
+///     #[mongo(skip_field, skip_filter)]
+///     password_hash: String,
 /// }
 ///
-/// // The derived filter struct can be exposed from the derived module which uses the type's name in
-/// // snake_case
-/// use self::user::Filter;
+/// # pub fn main() {
+/// let mut update = User::update();
+/// update.password_hash = Some("...".to_owned());
+/// assert_eq!(
+///     update.into_document().unwrap(),
+///     mongod::bson::doc! { "password_hash": "..." },
+/// );
+///
+/// assert!("password_hash".parse::<user::Field>().is_err());
+/// # }
 /// # }
+/// # wrap::main();
 /// ```
 ///
-/// ### `#[mongo(update)]`
-///
-/// Tells the derive to implement the `AsUpdate` & `Update` traits.
+/// Fields that are written when the document is inserted but never updated afterwards, such as a
+/// creation date or a natural key, can be marked with `#[mongo(skip_update)]` so that updating
+/// them is a compile time error, while the remaining fields are still updated. Note that this does
+/// not apply to replacements, which always write the whole document.
 ///
 /// ```
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
+/// use mongod::{AsUpdate, Update};
+///
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
-/// #[mongo(update)]
+/// #[mongo(collection = "users", update)]
 /// pub struct User {
+///     #[mongo(skip_update)]
+///     username: String,
 ///     name: String,
-///     age: u32,
 /// }
 ///
-/// // The derived update struct can be exposed from the derived module which uses the type's name in
-/// // snake_case
-/// use self::user::Update;
+/// # pub fn main() {
+/// let mut update = User::update();
+/// update.name = Some("foo".to_owned());
+/// assert_eq!(update.into_document().unwrap(), mongod::bson::doc! { "name": "foo" });
+/// # }
 /// # }
+/// # wrap::main();
 /// ```
 ///
-/// ## Field Attributes
+/// ```compile_fail
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::AsUpdate;
 ///
-/// - `#[mongo(serde)]`: tells the derive that the field should be handled using serde
-/// - `#[mongo(skip)]`: tells the derive to skip the field for `field`, `filter` & `update`
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(collection = "users", update)]
+/// pub struct User {
+///     #[mongo(skip_update)]
+///     username: String,
+///     name: String,
+/// }
 ///
-/// ### `#[mongo(serde)]`
+/// # pub fn main() {
+/// let mut update = User::update();
+/// update.username = Some("foo".to_owned());
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
 ///
-/// Tells the derive that the field should be handled using serde
+/// ### `#[mongo(validate(...))]`
+///
+/// Tells the derive to check the field's value in `Collection::into_document`, and when it is set
+/// by the derived `Update`, returning an `ErrorKind::InvalidDocument` error naming the field
+/// should a check fail. Optional fields are only checked when present. The checks are:
+///
+/// - `min = ...` & `max = ...`: bounds the value of the field, the bounds must be of the field's
+///   type, i.e. `0.0` for a float
+/// - `length(min = ..., max = ...)`: bounds the length of a string, in characters, or of a
+///   collection
+/// - `regex = "..."`: requires a string to match the pattern, this needs the `regex` feature of
+///   `mongod`
 ///
 /// ```
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
+/// use mongod::{Collection, ErrorKind};
+///
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
 /// #[mongo(collection = "users")]
 /// pub struct User {
+///     #[mongo(validate(length(min = 1, max = 32)))]
 ///     name: String,
-///     #[mongo(serde)]
-///     age: u32,
+///     #[mongo(validate(min = 0, max = 150))]
+///     age: Option<u32>,
 /// }
+///
+/// # pub fn main() {
+/// let user = User { name: "foo".to_owned(), age: Some(200) };
+/// let err = user.into_document().unwrap_err();
+/// assert!(matches!(err.kind(), ErrorKind::InvalidDocument));
+/// assert_eq!(err.to_string(), "invalid document: field `age` must be at most 150");
+/// # }
 /// # }
+/// # wrap::main();
 /// ```
 ///
-/// ### `#[mongo(skip)]`
-///
-/// Tells the derive to skip the field for `field`, `filter` & `update`
+/// A bound that does not fit the field's type is a compile time error.
 ///
-/// ```
+/// ```compile_fail
 /// # mod wrap {
 /// # use mongod_derive::Mongo;
 /// # #[derive(mongod_derive::Bson)]
 /// #[derive(Mongo)]
 /// #[mongo(collection = "users")]
 /// pub struct User {
-///     name: String,
-///     #[mongo(skip)]
+///     // error: cannot apply unary operator `-` to type `u32`
+///     #[mongo(validate(min = -1))]
 ///     age: u32,
 /// }
 /// # }
@@ -1,18 +1,40 @@
 use inflector::cases::pascalcase::to_pascal_case;
 use inflector::cases::snakecase::to_snake_case;
-use proc_macro2::{Span, TokenStream};
-use quote::{quote, ToTokens};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::{Ident, Member};
 
 use crate::ast::{attr, Container, Data, Field, Style, BSON, MONGO};
-use crate::bson::member_to_id;
+use crate::bson::{
+    field_to_id, impl_struct_field_value, impl_struct_missing_field,
+    impl_struct_try_from_bson_field, is_pointer, nested_from_bson, nested_into_bson,
+    pointer_into_bson,
+};
 
 pub fn expand_derive_mongo(input: &syn::DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
     let container = Container::from(input)?;
 
-    let body = match &container.data {
+    let companions = Companions::from(&container.ident, &input.vis, &container.attrs);
+    let (defs, body, views) = match &container.data {
         Data::Struct(style, fields) => {
-            impl_struct(&container.ident, style, fields, &container.attrs)
+            let (defs, body) = impl_struct(
+                &container.ident,
+                container.generics,
+                style,
+                fields,
+                &container.attrs,
+                &companions,
+            )?;
+            let (views, view_body) = impl_views(
+                &container.ident,
+                &input.vis,
+                container.generics,
+                style,
+                fields,
+                &container.attrs,
+            )?;
+            (defs, quote! { #body #view_body }, views)
         }
         _ => {
             return Err(vec![syn::Error::new_spanned(
@@ -22,11 +44,6 @@ pub fn expand_derive_mongo(input: &syn::DeriveInput) -> Result<TokenStream, Vec<
         }
     };
 
-    let namespace = Ident::new(
-        &to_snake_case(&container.ident.to_string()),
-        input.ident.span(),
-    );
-
     let serde = if container.attrs.bson == attr::BsonMode::Serde {
         quote! {
             extern crate serde as _serde;
@@ -35,9 +52,62 @@ pub fn expand_derive_mongo(input: &syn::DeriveInput) -> Result<TokenStream, Vec<
         quote! {}
     };
 
+    if container.attrs.types.is_some() {
+        // The companion types are declared next to the type, which keeps the derive usable within
+        // functions, while everything else is hidden in an unnamed constant referring to them
+        // using their usual names
+        let aliases = defs.iter().map(|(internal, _)| {
+            let external = companions.name(internal);
+            quote! {
+                type #internal = #external;
+            }
+        });
+        let defs = defs.iter().map(|(_, def)| {
+            let def = with_crate_paths(def.clone());
+            quote! {
+                #[allow(missing_docs)]
+                #def
+            }
+        });
+        return Ok(quote! {
+            #(#defs)*
+            #(#views)*
+            #[allow(
+                non_upper_case_globals,
+                unused_attributes,
+                unused_qualifications,
+                clippy::unnecessary_fallible_conversions
+            )]
+            const _: () = {
+                extern crate mongod as _mongo;
+                #serde
+
+                use std::convert::TryFrom;
+
+                #(#aliases)*
+
+                #body
+            };
+        });
+    }
+
+    let namespace = container.attrs.module.clone().unwrap_or_else(|| {
+        Ident::new(
+            &to_snake_case(&container.ident.to_string()),
+            input.ident.span(),
+        )
+    });
+    let defs = defs.into_iter().map(|(_, def)| def);
+
     // We dont use unnamed constants because we need to allow the namespace to be pollutable
     Ok(quote! {
-        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        #(#views)*
+        #[allow(
+            non_upper_case_globals,
+            unused_attributes,
+            unused_qualifications,
+            clippy::unnecessary_fallible_conversions
+        )]
         mod #namespace {
             extern crate mongod as _mongo;
             #serde
@@ -46,17 +116,252 @@ pub fn expand_derive_mongo(input: &syn::DeriveInput) -> Result<TokenStream, Vec<
 
             use super::*;
 
+            #(#defs)*
             #body
         };
     })
 }
 
+// A derived type definition, along with the name it is referred to by within the expansion
+type Companion = (Ident, TokenStream);
+
+// The names & visibility of the companion types, which are only renamed when using
+// `#[mongo(types)]`
+struct Companions {
+    field: Ident,
+    fields: Ident,
+    filter: Ident,
+    update: Ident,
+    updater: Ident,
+    vis: TokenStream,
+    krate: &'static str,
+}
+
+impl Companions {
+    fn from(name: &Ident, vis: &syn::Visibility, attrs: &attr::Container) -> Self {
+        match &attrs.types {
+            Some(types) => {
+                let default =
+                    |suffix: &str| Ident::new(&format!("{}{}", name, suffix), name.span());
+                Companions {
+                    field: types.field.clone().unwrap_or_else(|| default("Field")),
+                    fields: types.fields.clone().unwrap_or_else(|| default("Fields")),
+                    filter: types.filter.clone().unwrap_or_else(|| default("Filter")),
+                    update: types.update.clone().unwrap_or_else(|| default("Update")),
+                    updater: types.updater.clone().unwrap_or_else(|| default("Updater")),
+                    vis: vis.to_token_stream(),
+                    krate: "::mongod",
+                }
+            }
+            None => Companions {
+                field: Ident::new("Field", Span::call_site()),
+                fields: Ident::new("Fields", Span::call_site()),
+                filter: Ident::new("Filter", Span::call_site()),
+                update: Ident::new("Update", Span::call_site()),
+                updater: Ident::new("Updater", Span::call_site()),
+                vis: quote! { pub },
+                krate: "_mongo",
+            },
+        }
+    }
+
+    // The declared name of the companion type usually referred to as `internal`
+    fn name(&self, internal: &Ident) -> &Ident {
+        match internal.to_string().as_str() {
+            "Field" => &self.field,
+            "Fields" => &self.fields,
+            "Filter" => &self.filter,
+            "Update" => &self.update,
+            _ => &self.updater,
+        }
+    }
+}
+
+// Replaces the `_mongo` & `_serde` crate aliases, which only exist within the expansion, with their
+// absolute paths
+fn with_crate_paths(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) if ident == "_mongo" || ident == "_serde" => {
+                let krate = Ident::new(
+                    if ident == "_mongo" { "mongod" } else { "serde" },
+                    ident.span(),
+                );
+                quote! { ::#krate }
+            }
+            TokenTree::Group(group) => {
+                let mut inner =
+                    proc_macro2::Group::new(group.delimiter(), with_crate_paths(group.stream()));
+                inner.set_span(group.span());
+                TokenTree::Group(inner).into_token_stream()
+            }
+            token => token.into_token_stream(),
+        })
+        .collect()
+}
+
 fn impl_struct(
     name: &Ident,
-    _style: &Style,
+    generics: &syn::Generics,
+    style: &Style,
     fields: &[Field],
     attrs: &attr::Container,
-) -> proc_macro2::TokenStream {
+    companions: &Companions,
+) -> Result<(Vec<Companion>, TokenStream), Vec<syn::Error>> {
+    // Newtypes delegate to the inner type rather than deriving their own companion types
+    let newtype = match (style, fields) {
+        (Style::Tuple, [field]) => Some(field.ty),
+        _ => None,
+    };
+    if newtype.is_some() && (attrs.fluent || attrs.oid || attrs.query_string) {
+        return Err(vec![syn::Error::new_spanned(
+            name,
+            "#[derive(Mongo)] cannot derive `fluent`, `oid` or `query_string` for newtypes, derive them on the inner type instead",
+        )]);
+    }
+    if newtype.is_none() && *style == Style::Tuple && (attrs.filter || attrs.update) {
+        return Err(vec![syn::Error::new_spanned(
+            name,
+            "#[derive(Mongo)] cannot derive `filter` or `update` for tuple structs",
+        )]);
+    }
+    // The derived `Filter` & `Update` mirror the fields, so they cannot be derived for generic
+    // types without becoming generic themselves
+    if newtype.is_none() && !generics.params.is_empty() && (attrs.filter || attrs.update) {
+        return Err(vec![syn::Error::new_spanned(
+            generics,
+            "#[derive(Mongo)] cannot derive `filter` or `update` for generic types",
+        )]);
+    }
+    if attrs.collection.is_some()
+        && attrs.bson == attr::BsonMode::Convert
+        && fields
+            .iter()
+            .any(|f| matches!(f.ty, syn::Type::Reference(_)))
+    {
+        return Err(vec![syn::Error::new_spanned(
+            name,
+            "#[derive(Mongo)] cannot derive `collection` for types with borrowed fields, use `Cow` instead",
+        )]);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // Builds the generics for a newtype's impl delegating to the inner type's `__T`
+    let delegate = |param: syn::GenericParam, bound: syn::WherePredicate| {
+        let mut generics = generics.clone();
+        generics.params.push(param);
+        generics.make_where_clause().predicates.push(bound);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        (
+            impl_generics.to_token_stream(),
+            where_clause.to_token_stream(),
+        )
+    };
+    let mut defs = vec![];
+    let mut errors = vec![];
+    let indexes = attrs
+        .indexes
+        .iter()
+        .map(|index| {
+            let keys = index
+                .fields
+                .iter()
+                .filter_map(|(ident, descending)| {
+                    let field = fields
+                        .iter()
+                        .find(|f| matches!(&f.member, Member::Named(name) if name == ident));
+                    match field {
+                        Some(f) => {
+                            let id = field_to_id(f);
+                            let order: i32 = if *descending { -1 } else { 1 };
+                            Some(quote! { #id: #order })
+                        }
+                        None => {
+                            errors.push(syn::Error::new_spanned(
+                                ident,
+                                format!("unknown field `{}` in mongo index", ident),
+                            ));
+                            None
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut options = vec![];
+            if let Some(name) = &index.name {
+                options.push(quote! { .name(#name.to_owned()) });
+            }
+            if index.sparse {
+                options.push(quote! { .sparse(true) });
+            }
+            if index.unique {
+                options.push(quote! { .unique(true) });
+            }
+            let options = if options.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    .options(_mongo::db::options::IndexOptions::builder()#(#options)*.build())
+                }
+            };
+            quote! {
+                _mongo::db::IndexModel::builder()
+                    .keys(_mongo::bson::doc! { #(#keys),* })
+                    #options
+                    .build()
+            }
+        })
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let indexes = if indexes.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn indexes() -> Vec<_mongo::db::IndexModel> {
+                vec![#(#indexes),*]
+            }
+        }
+    };
+    // The field holding the document's `_id`, which is converted to & from BSON as any other field
+    let id = fields.iter().find(|f| f.attrs.id).map(|f| {
+        let member = &f.member;
+        let ty = &f.ty;
+        let get = quote! {
+            match <_mongo::ext::bson::Bson as TryFrom<#ty>>::try_from(self.#member.clone()) {
+                Ok(_mongo::ext::bson::Bson(_mongo::bson::Bson::Null)) | Err(_) => None,
+                Ok(id) => Some(id.0),
+            }
+        };
+        if let Some(inner) = option_inner(f.ty) {
+            (
+                get,
+                quote! {
+                    if let Ok(id) = <#inner as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(id)) {
+                        self.#member = Some(id);
+                    }
+                },
+                // Unsaved documents must not be stored with a `null` `_id`
+                quote! {
+                    if let Some(_mongo::bson::Bson::Null) = doc.get("_id") {
+                        doc.remove("_id");
+                    }
+                },
+            )
+        } else {
+            (
+                get,
+                quote! {
+                    if let Ok(id) = <#ty as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(id)) {
+                        self.#member = id;
+                    }
+                },
+                quote! {},
+            )
+        }
+    });
+    // The derived `_id` of `oid` is left out when a field already filters the `_id`
+    let oid = attrs.oid && !fields.iter().any(|f| f.attrs.id && !f.attrs.skip_filter);
     let collection = if let Some(col) = &attrs.collection {
         let from = if attrs.bson == attr::BsonMode::Serde {
             quote! {
@@ -76,56 +381,273 @@ fn impl_struct(
                 let b = _mongo::bson::Bson::try_from(self).map_err(_mongo::Error::invalid_document)?;
             }
         };
+        // Generic types can only be converted when their type parameters can be
+        let mut generics = generics.clone();
+        if generics.type_params().next().is_some() {
+            let predicates = &mut generics.make_where_clause().predicates;
+            if attrs.bson == attr::BsonMode::Serde {
+                predicates.push(parse_quote! {
+                    Self: _serde::Serialize + _serde::de::DeserializeOwned
+                });
+            } else {
+                predicates.push(parse_quote! {
+                    Self: TryFrom<_mongo::bson::Bson, Error = _mongo::ext::bson::de::Error>
+                });
+                predicates.push(parse_quote! {
+                    _mongo::bson::Bson: TryFrom<Self, Error = _mongo::ext::bson::ser::Error>
+                });
+            }
+        }
+        let where_clause = generics.split_for_impl().2;
+        // Optional fields are only checked when present
+        let validations = fields.iter().filter_map(|f| {
+            let checks = field_validation(f)?;
+            let member = &f.member;
+            Some(if option_inner(f.ty).is_some() {
+                quote! {
+                    if let Some(__value) = &self.#member {
+                        #checks
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let __value = &self.#member;
+                        #checks
+                    }
+                }
+            })
+        });
+        let (id, strip) = match &id {
+            Some((get, set, strip)) => (
+                quote! {
+                    fn id(&self) -> Option<_mongo::bson::Bson> {
+                        #get
+                    }
+
+                    fn set_id(&mut self, id: _mongo::bson::Bson) {
+                        #set
+                    }
+                },
+                strip.clone(),
+            ),
+            None => (quote! {}, quote! {}),
+        };
+        let create_options = match &attrs.options {
+            Some(options) => {
+                let mut setters = vec![];
+                if let Some(capped) = &options.capped {
+                    setters.push(quote! { .capped(#capped) });
+                }
+                if let Some(collation) = &options.collation {
+                    let value = collation.value();
+                    let collation = match value.strip_suffix("_ci") {
+                        Some(locale) => quote! {
+                            _mongo::db::options::Collation::builder()
+                                .locale(#locale)
+                                .strength(_mongo::db::options::CollationStrength::Secondary)
+                                .build()
+                        },
+                        None => quote! {
+                            _mongo::db::options::Collation::builder().locale(#value).build()
+                        },
+                    };
+                    setters.push(quote! { .collation(#collation) });
+                }
+                if let Some(max) = &options.max {
+                    setters.push(quote! { .max({ let max: u64 = #max; max }) });
+                }
+                if let Some(size) = &options.size {
+                    setters.push(quote! { .size({ let size: u64 = #size; size }) });
+                }
+                quote! {
+                    fn create_options() -> Option<_mongo::db::options::CreateCollectionOptions> {
+                        Some(
+                            _mongo::db::options::CreateCollectionOptions::builder()
+                                #(#setters)*
+                                .build()
+                        )
+                    }
+                }
+            }
+            None => quote! {},
+        };
+        let collection_fn = match &attrs.collection_fn {
+            Some(path) => quote! {
+                fn collection() -> String {
+                    #path()
+                }
+            },
+            None => quote! {},
+        };
         quote! {
             #[automatically_derived]
-            impl _mongo::Collection for #name {
+            impl #impl_generics _mongo::Collection for #name #ty_generics #where_clause {
                 const COLLECTION: &'static str = #col;
+                #collection_fn
+                #create_options
 
                 fn from_document(document: _mongo::bson::Document) -> core::result::Result<Self, _mongo::Error> {
                     #from
                 }
 
                 fn into_document(self) -> core::result::Result<_mongo::bson::Document, _mongo::Error> {
+                    #(#validations)*
                     #into
                     match b {
-                        _mongo::bson::Bson::Document(doc) => Ok(doc),
+                        #[allow(unused_mut)]
+                        _mongo::bson::Bson::Document(mut doc) => {
+                            #strip
+                            Ok(doc)
+                        }
                         _ => Err(_mongo::Error::invalid_document("not a bson document")),
                     }
                 }
+
+                #id
+
+                #indexes
             }
         }
     } else {
         quote! {}
     };
 
-    let field = if attrs.field {
-        let matches = fields.iter().map(|f| {
-            let (id, name) = match &f.member {
-                Member::Named(name) => (to_pascal_case(&name.to_string()), stringify!(name)),
-                Member::Unnamed(idx) => (idx.index.to_string(), stringify!(idx)),
+    // Typed accessors for the fields holding the `ObjectId` of documents in other collections
+    let references = fields
+        .iter()
+        .filter_map(|f| {
+            let target = f.attrs.references.as_ref()?;
+            let member = &f.member;
+            let method = match member {
+                Member::Named(name) => Ident::new(&format!("{}_ref", name), name.span()),
+                Member::Unnamed(idx) => Ident::new(&format!("_{}_ref", idx.index), idx.span),
+            };
+            let doc = format!(
+                "A typed reference to the document referenced by `{}`.",
+                field_to_id(f)
+            );
+            let (ty, value) = if option_inner(f.ty).is_some() {
+                (
+                    quote! { Option<_mongo::Ref<#target>> },
+                    quote! { self.#member.map(_mongo::Ref::new) },
+                )
+            } else if is_vec(f.ty) {
+                (
+                    quote! { Vec<_mongo::Ref<#target>> },
+                    quote! { self.#member.iter().copied().map(_mongo::Ref::new).collect() },
+                )
+            } else {
+                (
+                    quote! { _mongo::Ref<#target> },
+                    quote! { _mongo::Ref::new(self.#member) },
+                )
+            };
+            Some(quote! {
+                #[doc = #doc]
+                pub fn #method(&self) -> #ty {
+                    #value
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let references = if references.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#references)*
+            }
+        }
+    };
+
+    // The keys of the fields, so that raw documents need not repeat them as string literals
+    let keys = if *style == Style::Struct {
+        let members = fields.iter().map(|f| &f.member).collect::<Vec<_>>();
+        let ids = fields.iter().map(field_to_id);
+        let doc = format!("The keys of the fields of a `{}`.", name);
+        let (ident, vis) = (&companions.fields, &companions.vis);
+        defs.push((
+            Ident::new("Fields", Span::call_site()),
+            quote! {
+                #[doc = #doc]
+                #[automatically_derived]
+                #[derive(Clone, Copy, Debug)]
+                #vis struct #ident {
+                    #(pub #members: &'static str),*
+                }
+            },
+        ));
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// The keys of the fields as they are stored in the document.
+                pub const FIELDS: Fields = Fields {
+                    #(#members: #ids),*
+                };
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let field = if let (true, Some(inner)) = (attrs.field, newtype) {
+        let (impl_generics, where_clause) = delegate(
+            parse_quote!(__T: _mongo::Field + Into<String>),
+            parse_quote!(#inner: _mongo::AsField<__T>),
+        );
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics _mongo::AsField<__T> for #name #ty_generics #where_clause {}
+        }
+    } else if attrs.field {
+        let matches = fields.iter().filter(|f| !f.attrs.skip_field).map(|f| {
+            let id = match &f.member {
+                Member::Named(name) => to_pascal_case(&name.to_string()),
+                Member::Unnamed(idx) => format!("_{}", idx.index),
             };
             let id = Ident::new(&id, Span::call_site());
+            let name = field_to_id(f);
             quote! {
                 Field::#id => #name.to_owned()
             }
         });
-        let variants = fields.iter().map(|f| {
+        let parses = fields.iter().filter(|f| !f.attrs.skip_field).map(|f| {
+            let id = match &f.member {
+                Member::Named(name) => to_pascal_case(&name.to_string()),
+                Member::Unnamed(idx) => format!("_{}", idx.index),
+            };
+            let id = Ident::new(&id, Span::call_site());
+            let name = field_to_id(f);
+            quote! {
+                #name => Ok(Field::#id)
+            }
+        });
+        let variants = fields.iter().filter(|f| !f.attrs.skip_field).map(|f| {
             let id = match &f.member {
                 Member::Named(name) => to_pascal_case(&name.to_string()),
-                Member::Unnamed(idx) => idx.index.to_string(),
+                Member::Unnamed(idx) => format!("_{}", idx.index),
             };
             let id = Ident::new(&id, Span::call_site());
             quote! {
                 #id
             }
         });
+        let (ident, vis) = (&companions.field, &companions.vis);
+        defs.push((
+            Ident::new("Field", Span::call_site()),
+            quote! {
+                #[automatically_derived]
+                #[allow(non_camel_case_types)]
+                #vis enum #ident {
+                    #(#variants),*
+                }
+            },
+        ));
         quote! {
             #[automatically_derived]
-            pub enum Field {
-                #(#variants),*
-            }
-            #[automatically_derived]
-            impl _mongo::AsField<Field> for #name {}
+            impl #impl_generics _mongo::AsField<Field> for #name #ty_generics #where_clause {}
             #[automatically_derived]
             impl _mongo::Field for Field {}
             #[automatically_derived]
@@ -136,14 +658,52 @@ fn impl_struct(
                     }
                 }
             }
+            #[automatically_derived]
+            impl core::str::FromStr for Field {
+                type Err = _mongo::Error;
+                fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#parses,)*
+                        _ => Err(_mongo::Error::invalid_query_string(format!("unknown field `{}`", s))),
+                    }
+                }
+            }
         }
     } else {
         quote! {}
     };
 
-    let filter = if attrs.filter {
+    let filter = if let (true, Some(inner)) = (attrs.filter, newtype) {
+        let (impl_generics, where_clause) = delegate(
+            parse_quote!(__T: _mongo::Filter),
+            parse_quote!(#inner: _mongo::AsFilter<__T>),
+        );
+        let (nested_generics, _, nested_where) = generics.split_for_impl();
+        let mut nested_where = nested_where.cloned().unwrap_or_else(|| parse_quote!(where));
+        nested_where
+            .predicates
+            .push(parse_quote!(#inner: _mongo::NestedFilter));
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics _mongo::AsFilter<__T> for #name #ty_generics #where_clause {
+                fn filter() -> __T {
+                    <#inner as _mongo::AsFilter<__T>>::filter()
+                }
+                fn into_filter(self) -> __T {
+                    _mongo::AsFilter::into_filter(self.0)
+                }
+            }
+            #[automatically_derived]
+            impl #nested_generics _mongo::NestedFilter for #name #ty_generics #nested_where {
+                type Filter = <#inner as _mongo::NestedFilter>::Filter;
+                fn into_nested_filter(self) -> Self::Filter {
+                    _mongo::NestedFilter::into_nested_filter(self.0)
+                }
+            }
+        }
+    } else if attrs.filter {
         let filter_fields = fields.iter().filter_map(|f| {
-            if f.attrs.skip {
+            if f.attrs.skip_filter {
                 return None;
             }
             let ty = &f.ty;
@@ -151,36 +711,69 @@ fn impl_struct(
                 Member::Named(name) => name,
                 _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
             };
-            let inner = if f.attrs.serde || attrs.bson == attr::BsonMode::Serde {
+            if f.attrs.filter {
+                return Some(quote! {
+                    pub #name: <#ty as _mongo::NestedFilter>::Filter
+                });
+            }
+            let inner = if f.attrs.with.is_some() || f.attrs.nested || is_pointer(ty) {
+                quote! { _mongo::ext::bson::With<#ty> }
+            } else if f.attrs.serde || attrs.bson == attr::BsonMode::Serde {
                 quote! { _mongo::ext::bson::Ser<#ty> }
             } else {
                 quote! { #ty }
             };
-            Some(quote! {
+            // Spanned to the field's type so that an unsupported type is reported on the field
+            Some(quote_spanned! {ty.span()=>
                 pub #name: Option<_mongo::Comparator<#inner>>
             })
         });
         let into_bson = fields.iter().filter_map(|f| {
-            if f.attrs.skip {
+            if f.attrs.skip_filter {
                 return None;
             }
             let member = &f.member;
-            let id = member_to_id(&f.member);
+            let id = field_to_id(f);
+            if f.attrs.filter {
+                // Nested filters are flattened into dot-notation keys
+                return Some(quote! {
+                    let __value: _mongo::ext::bson::Bson = core::convert::TryInto::try_into(value.#member)?;
+                    if let _mongo::bson::Bson::Document(__doc) = __value.0 {
+                        for (__key, __value) in __doc {
+                            doc.insert(format!("{}.{}", #id, __key), __value);
+                        }
+                    }
+                });
+            }
+            let convert = quote_spanned! {f.ty.span()=>
+                _mongo::ext::bson::Bson::try_from(__value)?.0
+            };
             Some(quote! {
                 if let Some(__value) = value.#member {
-                    doc.insert(#id, _mongo::ext::bson::Bson::try_from(__value)?.0);
+                    doc.insert(#id, #convert);
                 }
             })
         });
         let into_filter = fields.iter().filter_map(|f| {
-            if f.attrs.skip {
+            if f.attrs.skip_filter {
                 return None;
             }
             let name = match &f.member {
                 Member::Named(name) => name,
                 _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
             };
-            let inner = if f.attrs.serde || attrs.bson == attr::BsonMode::Serde {
+            if f.attrs.filter {
+                return Some(quote! {
+                    #name: _mongo::NestedFilter::into_nested_filter(self.#name)
+                });
+            }
+            let inner = if let Some(with) = &f.attrs.with {
+                quote! { _mongo::ext::bson::With::new(self.#name, |v| Ok(#with::to_bson(v)?)) }
+            } else if f.attrs.nested {
+                nested_with(f.ty, quote! { self.#name })
+            } else if is_pointer(f.ty) {
+                pointer_with(f.ty, quote! { self.#name })
+            } else if f.attrs.serde || attrs.bson == attr::BsonMode::Serde {
                 quote! { _mongo::ext::bson::Ser(self.#name) }
             } else {
                 quote! { self.#name }
@@ -189,14 +782,191 @@ fn impl_struct(
                 #name: Some(_mongo::Comparator::Eq(#inner))
             })
         });
-        let filter_field_oid = if attrs.oid {
+        let merges = fields.iter().filter_map(|f| {
+            if f.attrs.skip_filter {
+                return None;
+            }
+            let member = &f.member;
+            if f.attrs.filter {
+                return Some(quote! {
+                    self.#member = _mongo::MergeFilter::merge(self.#member, other.#member);
+                });
+            }
+            Some(quote! {
+                if other.#member.is_some() {
+                    self.#member = other.#member;
+                }
+            })
+        });
+        let merge_oid = if oid {
+            quote! {
+                if other._id.is_some() {
+                    self._id = other._id;
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let fluent = if attrs.fluent {
+            let methods = fields.iter().filter_map(|f| {
+                if f.attrs.skip_filter {
+                    return None;
+                }
+                let ty = &f.ty;
+                let name = match &f.member {
+                    Member::Named(name) => name,
+                    _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
+                };
+                if f.attrs.filter {
+                    return Some(quote! {
+                        pub fn #name(mut self, filter: <#ty as _mongo::NestedFilter>::Filter) -> Self {
+                            self.#name = filter;
+                            self
+                        }
+                    });
+                }
+                let serde = f.attrs.serde || attrs.bson == attr::BsonMode::Serde;
+                let with = &f.attrs.with;
+                let wrap = |value: TokenStream| {
+                    if let Some(with) = with {
+                        quote! { _mongo::ext::bson::With::new(#value, |v| Ok(#with::to_bson(v)?)) }
+                    } else if f.attrs.nested {
+                        nested_with(ty, value)
+                    } else if is_pointer(ty) {
+                        pointer_with(ty, value)
+                    } else if serde {
+                        quote! { _mongo::ext::bson::Ser(#value) }
+                    } else {
+                        value
+                    }
+                };
+                let value = wrap(quote! { value });
+                let values = wrap(quote! { v });
+                let single = ["eq", "gt", "gte", "lt", "lte", "ne"].iter().map(|op| {
+                    let method = Ident::new(&format!("{}_{}", name, op), name.span());
+                    let variant = Ident::new(&to_pascal_case(op), Span::call_site());
+                    quote! {
+                        pub fn #method(mut self, value: #ty) -> Self {
+                            self.#name = Some(_mongo::Comparator::#variant(#value));
+                            self
+                        }
+                    }
+                });
+                let many = ["in", "nin"].iter().map(|op| {
+                    let method = Ident::new(&format!("{}_{}", name, op), name.span());
+                    let variant = Ident::new(&to_pascal_case(op), Span::call_site());
+                    quote! {
+                        pub fn #method(mut self, values: Vec<#ty>) -> Self {
+                            self.#name = Some(_mongo::Comparator::#variant(
+                                values.into_iter().map(|v| #values).collect(),
+                            ));
+                            self
+                        }
+                    }
+                });
+                Some(quote! {
+                    #(#single)*
+                    #(#many)*
+                })
+            });
+            quote! {
+                #[automatically_derived]
+                #[allow(clippy::wrong_self_convention)]
+                impl Filter {
+                    #(#methods)*
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let query_string = if attrs.query_string {
+            let arms = fields.iter().filter_map(|f| {
+                if f.attrs.skip_field || f.attrs.skip_filter || f.attrs.filter {
+                    return None;
+                }
+                let ty = &f.ty;
+                let name = match &f.member {
+                    Member::Named(name) => name,
+                    _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
+                };
+                let id = Ident::new(&to_pascal_case(&name.to_string()), Span::call_site());
+                let convert = if let Some(with) = &f.attrs.with {
+                    quote! {
+                        |b| #with::from_bson(b).map(|v| {
+                            _mongo::ext::bson::With::new(v, |v| Ok(#with::to_bson(v)?))
+                        })
+                    }
+                } else if f.attrs.nested {
+                    let from = nested_from_bson(ty, quote! { b });
+                    let with = nested_with(ty, from);
+                    quote! {
+                        |b| -> core::result::Result<_, _mongo::ext::bson::de::Error> { Ok(#with) }
+                    }
+                } else if f.attrs.serde || attrs.bson == attr::BsonMode::Serde {
+                    quote! {
+                        |b| _mongo::bson::from_bson::<#ty>(b).map(_mongo::ext::bson::Ser)
+                    }
+                } else {
+                    quote! {
+                        |b| <#ty as TryFrom<_mongo::ext::bson::Bson>>::try_from(_mongo::ext::bson::Bson(b))
+                    }
+                };
+                Some(quote! {
+                    Field::#id => {
+                        if filter.#name.is_some() {
+                            return Err(_mongo::Error::invalid_query_string(format!(
+                                "field `{}` is filtered more than once",
+                                param.field
+                            )));
+                        }
+                        filter.#name = Some(_mongo::query_string::comparator(
+                            param.op.as_deref(),
+                            &param.value,
+                            #convert,
+                        )?);
+                    }
+                })
+            });
+            quote! {
+                #[automatically_derived]
+                impl Filter {
+                    /// Parses a URL query string into a `Filter` and `Sort`.
+                    pub fn from_query_string(
+                        query: &str,
+                    ) -> core::result::Result<(Self, _mongo::Sort<Field>), _mongo::Error> {
+                        let mut filter = Self::default();
+                        let mut sort = _mongo::Sort::new();
+                        for param in _mongo::query_string::parse(query)? {
+                            if param.field == _mongo::query_string::SORT {
+                                sort = _mongo::query_string::sort(&param.value)?;
+                                continue;
+                            }
+                            #[allow(unreachable_patterns)]
+                            match param.field.parse::<Field>()? {
+                                #(#arms)*
+                                _ => {
+                                    return Err(_mongo::Error::invalid_query_string(format!(
+                                        "field `{}` cannot be filtered",
+                                        param.field
+                                    )))
+                                }
+                            }
+                        }
+                        Ok((filter, sort))
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let filter_field_oid = if oid {
             quote! {
                 pub _id: Option<_mongo::Comparator<_mongo::bson::oid::ObjectId>>,
             }
         } else {
             quote! {}
         };
-        let into_bson_oid = if attrs.oid {
+        let into_bson_oid = if oid {
             quote! {
                 if let Some(__value) = value._id {
                     doc.insert("_id", _mongo::ext::bson::Bson::try_from(__value)?.0);
@@ -205,20 +975,26 @@ fn impl_struct(
         } else {
             quote! {}
         };
-        let into_filter_oid = if attrs.oid {
+        let into_filter_oid = if oid {
             quote! {
                 _id: None,
             }
         } else {
             quote! {}
         };
+        let (ident, vis) = (&companions.filter, &companions.vis);
+        defs.push((
+            Ident::new("Filter", Span::call_site()),
+            quote! {
+                #[automatically_derived]
+                #[derive(Debug, Default)]
+                #vis struct #ident {
+                    #filter_field_oid
+                    #(#filter_fields),*
+                }
+            },
+        ));
         quote! {
-            #[automatically_derived]
-            #[derive(Default)]
-            pub struct Filter {
-                #filter_field_oid
-                #(#filter_fields),*
-            }
             #[automatically_derived]
             impl TryFrom<Filter> for _mongo::bson::Bson {
                 type Error = _mongo::ext::bson::ser::Error;
@@ -261,12 +1037,45 @@ fn impl_struct(
                     }
                 }
             }
+            #[automatically_derived]
+            impl _mongo::NestedFilter for #name {
+                type Filter = Filter;
+                fn into_nested_filter(self) -> Filter {
+                    _mongo::AsFilter::into_filter(self)
+                }
+            }
+            #[automatically_derived]
+            impl _mongo::MergeFilter for Filter {
+                fn merge(mut self, other: Self) -> Self {
+                    #merge_oid
+                    #(#merges)*
+                    self
+                }
+            }
+            #fluent
+            #query_string
         }
     } else {
         quote! {}
     };
 
-    let update = if attrs.update {
+    let update = if let (true, Some(inner)) = (attrs.update, newtype) {
+        let (impl_generics, where_clause) = delegate(
+            parse_quote!(__T: _mongo::Update),
+            parse_quote!(#inner: _mongo::AsUpdate<__T>),
+        );
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics _mongo::AsUpdate<__T> for #name #ty_generics #where_clause {
+                fn update() -> __T {
+                    <#inner as _mongo::AsUpdate<__T>>::update()
+                }
+                fn into_update(self) -> __T {
+                    _mongo::AsUpdate::into_update(self.0)
+                }
+            }
+        }
+    } else if attrs.update {
         let (derive, bson, into) = if attrs.bson == attr::BsonMode::Serde {
             (
                 quote! {
@@ -279,21 +1088,45 @@ fn impl_struct(
             )
         } else {
             let into_bson = fields.iter().filter_map(|f| {
-                if f.attrs.skip {
+                if f.attrs.skip_update {
                     return None;
                 }
                 let member = &f.member;
-                let id = member_to_id(&f.member);
-                if f.attrs.serde {
-                    Some(quote! {
-                    if let Some(__value) = value.#member {
-                        doc.insert(#id, _mongo::bson::to_bson(&__value)?);
+                let id = field_to_id(f);
+                let span = f.ty.span();
+                let convert = if let Some(with) = &f.attrs.with {
+                    if option_inner(f.ty).is_some() {
+                        quote_spanned! {span=> #with::to_bson(Some(__value))? }
+                    } else {
+                        quote_spanned! {span=> #with::to_bson(__value)? }
                     }
+                } else if f.attrs.nested {
+                    nested_into_bson(option_inner(f.ty).unwrap_or(f.ty), quote! { __value })
+                } else if f.attrs.serde {
+                    quote_spanned! {span=> _mongo::bson::to_bson(&__value)? }
+                } else if let Some(into) =
+                    pointer_into_bson(option_inner(f.ty).unwrap_or(f.ty), quote! { __value })
+                {
+                    into
+                } else {
+                    quote_spanned! {span=> _mongo::ext::bson::Bson::try_from(__value)?.0 }
+                };
+                if option_inner(f.ty).is_some() {
+                    Some(quote! {
+                        match value.#member {
+                            _mongo::FieldUpdate::Set(__value) => {
+                                doc.insert(#id, #convert);
+                            }
+                            _mongo::FieldUpdate::Null => {
+                                doc.insert(#id, _mongo::bson::Bson::Null);
+                            }
+                            _mongo::FieldUpdate::Keep | _mongo::FieldUpdate::Unset => {}
+                        }
                     })
                 } else {
                     Some(quote! {
                         if let Some(__value) = value.#member {
-                            doc.insert(#id, _mongo::ext::bson::Bson::try_from(__value)?.0);
+                            doc.insert(#id, #convert);
                         }
                     })
                 }
@@ -325,8 +1158,121 @@ fn impl_struct(
                 },
             )
         };
+        let fluent = if attrs.fluent {
+            let fluent_fields = fields
+                .iter()
+                .filter(|f| !f.attrs.skip_update)
+                .map(|f| {
+                    let name = match &f.member {
+                        Member::Named(name) => name,
+                        _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
+                    };
+                    (name, f.ty, field_to_id(f))
+                })
+                .collect::<Vec<_>>();
+            let ops = ["inc", "max", "min", "mul", "set", "set_on_insert"];
+            let starts = fluent_fields.iter().flat_map(|(name, ty, _)| {
+                ops.iter().map(move |op| {
+                    let method = Ident::new(&format!("{}_{}", op, name), name.span());
+                    quote! {
+                        pub fn #method(self, value: #ty) -> Updater {
+                            Updater::from(self).#method(value)
+                        }
+                    }
+                })
+            });
+            let chains = fluent_fields.iter().flat_map(|(name, ty, _)| {
+                ops.iter().map(move |op| {
+                    let method = Ident::new(&format!("{}_{}", op, name), name.span());
+                    let op = Ident::new(op, Span::call_site());
+                    let value = if option_inner(ty).is_some() {
+                        quote! { value.into() }
+                    } else {
+                        quote! { Some(value) }
+                    };
+                    quote! {
+                        pub fn #method(mut self, value: #ty) -> Self {
+                            self.0.#op.get_or_insert_with(Update::default).#name = #value;
+                            self
+                        }
+                    }
+                })
+            });
+            let unset_starts = fluent_fields.iter().map(|(name, _, _)| {
+                let method = Ident::new(&format!("unset_{}", name), name.span());
+                quote! {
+                    pub fn #method(self) -> Updater {
+                        Updater::from(self).#method()
+                    }
+                }
+            });
+            let unset_chains = fluent_fields.iter().map(|(name, _, id)| {
+                let method = Ident::new(&format!("unset_{}", name), name.span());
+                quote! {
+                    pub fn #method(mut self) -> Self {
+                        self.0.unset_fields.push(#id.to_owned());
+                        self
+                    }
+                }
+            });
+            let is_empty = fluent_fields.iter().map(|(name, ty, _)| {
+                if option_inner(ty).is_some() {
+                    quote! { update.#name.is_keep() }
+                } else {
+                    quote! { update.#name.is_none() }
+                }
+            });
+            let (ident, vis, update) = (&companions.updater, &companions.vis, &companions.update);
+            defs.push((
+                Ident::new("Updater", Span::call_site()),
+                quote! {
+                    /// A chainable builder of `Updates`.
+                    #[automatically_derived]
+                    #[derive(Default)]
+                    #vis struct #ident(pub _mongo::Updates<#update>);
+                },
+            ));
+            quote! {
+                #[automatically_derived]
+                impl Update {
+                    #(#starts)*
+                    #(#unset_starts)*
+                }
+                #[automatically_derived]
+                impl Updater {
+                    #(#chains)*
+                    #(#unset_chains)*
+
+                    /// Returns the built `Updates`.
+                    pub fn into_updates(self) -> _mongo::Updates<Update> {
+                        self.0
+                    }
+                }
+                #[automatically_derived]
+                impl From<Update> for Updater {
+                    fn from(update: Update) -> Self {
+                        // Any fields already present on the update are treated as being set
+                        if true #(&& #is_empty)* {
+                            return Updater::default();
+                        }
+                        Self(_mongo::Updates {
+                            set: Some(update),
+                            ..Default::default()
+                        })
+                    }
+                }
+                #[automatically_derived]
+                impl From<Updater> for _mongo::Updates<Update> {
+                    fn from(updater: Updater) -> Self {
+                        updater.0
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
         let update_fields = fields.iter().filter_map(|f| {
-            if f.attrs.skip {
+            if f.attrs.skip_update {
                 return None;
             }
             let ty = &f.ty;
@@ -342,37 +1288,91 @@ fn impl_struct(
                 .iter()
                 .filter(|a| !(a.path().is_ident(BSON) || a.path().is_ident(MONGO)))
                 .collect::<Vec<_>>();
+            // Optional fields get a `FieldUpdate` so that `null` & unset can be told apart
+            let (ty, skip) = match option_inner(ty) {
+                Some(inner) => (
+                    quote! { _mongo::FieldUpdate<#inner> },
+                    format!("{}::FieldUpdate::is_skipped", companions.krate),
+                ),
+                None => (quote! { Option<#ty> }, "Option::is_none".to_owned()),
+            };
             if attrs.bson == attr::BsonMode::Serde {
                 Some(quote! {
                     #(#raw_attrs),*
-                    #[serde(skip_serializing_if="Option::is_none")]
-                    pub #name: Option<#ty>
+                    #[serde(skip_serializing_if=#skip)]
+                    pub #name: #ty
                 })
             } else {
                 Some(quote! {
                     #(#raw_attrs),*
-                    pub #name: Option<#ty>
+                    pub #name: #ty
                 })
             }
         });
         let into_update = fields.iter().filter_map(|f| {
-            if f.attrs.skip {
+            if f.attrs.skip_update {
                 return None;
             }
             let name = match &f.member {
                 Member::Named(name) => name,
                 _ => panic!("#[derive(Mongo)] can only be derived on named structs"),
             };
+            if option_inner(f.ty).is_some() {
+                Some(quote! {
+                    #name: self.#name.into()
+                })
+            } else {
+                Some(quote! {
+                    #name: Some(self.#name)
+                })
+            }
+        });
+        let unset_fields = fields.iter().filter_map(|f| {
+            if f.attrs.skip_update || option_inner(f.ty).is_none() {
+                return None;
+            }
+            let member = &f.member;
+            let id = field_to_id(f);
             Some(quote! {
-                #name: Some(self.#name)
+                if self.#member.is_unset() {
+                    unset.push(#id.to_owned());
+                }
             })
         });
-        quote! {
-            #[automatically_derived]
-            #derive
-            pub struct Update {
-                #(#update_fields),*
+        // Only the values being set are checked, as the other operators take operands rather
+        // than the field's value
+        let validations = fields.iter().filter_map(|f| {
+            if f.attrs.skip_update {
+                return None;
             }
+            let checks = field_validation(f)?;
+            let member = &f.member;
+            Some(if option_inner(f.ty).is_some() {
+                quote! {
+                    if let _mongo::FieldUpdate::Set(__value) = &self.#member {
+                        #checks
+                    }
+                }
+            } else {
+                quote! {
+                    if let Some(__value) = &self.#member {
+                        #checks
+                    }
+                }
+            })
+        });
+        let (ident, vis) = (&companions.update, &companions.vis);
+        defs.push((
+            Ident::new("Update", Span::call_site()),
+            quote! {
+                #[automatically_derived]
+                #derive
+                #vis struct #ident {
+                    #(#update_fields),*
+                }
+            },
+        ));
+        quote! {
             #bson
             #[automatically_derived]
             impl _mongo::Update for Update {
@@ -386,6 +1386,12 @@ fn impl_struct(
                         _ => Err(_mongo::Error::invalid_document("not a bson document")),
                     }
                 }
+                fn into_set_document(self) -> core::result::Result<(_mongo::bson::Document, Vec<String>), _mongo::Error> {
+                    #(#validations)*
+                    let mut unset = Vec::new();
+                    #(#unset_fields)*
+                    Ok((_mongo::Update::into_document(self)?, unset))
+                }
             }
             #[automatically_derived]
             impl _mongo::AsUpdate<Update> for #name {
@@ -398,15 +1404,328 @@ fn impl_struct(
                     }
                 }
             }
+            #fluent
+        }
+    } else {
+        quote! {}
+    };
+
+    let json_schema = if let (true, Some(inner)) = (attrs.json_schema, newtype) {
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#inner: _mongo::JsonSchema));
+        let where_clause = generics.split_for_impl().2;
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics _mongo::JsonSchema for #name #ty_generics #where_clause {
+                fn json_schema() -> _mongo::bson::Document {
+                    <#inner as _mongo::JsonSchema>::json_schema()
+                }
+                fn required() -> bool {
+                    <#inner as _mongo::JsonSchema>::required()
+                }
+            }
+        }
+    } else if attrs.json_schema {
+        let properties = fields.iter().map(|f| {
+            let ty = &f.ty;
+            let id = field_to_id(f);
+            // Custom conversions can produce anything, so their fields are left unconstrained
+            let (schema, required) = if f.attrs.with.is_some() || f.attrs.serde {
+                let required = option_inner(ty).is_none();
+                (
+                    quote! { _mongo::bson::Document::new() },
+                    quote! { #required },
+                )
+            } else {
+                (
+                    quote! { <#ty as _mongo::JsonSchema>::json_schema() },
+                    quote! { <#ty as _mongo::JsonSchema>::required() },
+                )
+            };
+            let required = if f.attrs.default.is_some() {
+                quote! {}
+            } else {
+                quote! {
+                    if #required {
+                        required.push(_mongo::bson::Bson::String(#id.to_owned()));
+                    }
+                }
+            };
+            quote! {
+                properties.insert(#id, #schema);
+                #required
+            }
+        });
+        let mut generics = generics.clone();
+        let params = generics
+            .type_params()
+            .map(|p| p.ident.clone())
+            .collect::<Vec<_>>();
+        for param in params {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#param: _mongo::JsonSchema));
+        }
+        let where_clause = generics.split_for_impl().2;
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics _mongo::JsonSchema for #name #ty_generics #where_clause {
+                fn json_schema() -> _mongo::bson::Document {
+                    #[allow(unused_mut)]
+                    let mut required: Vec<_mongo::bson::Bson> = Vec::new();
+                    let mut properties = _mongo::bson::Document::new();
+                    #(#properties)*
+                    let mut schema = _mongo::bson::doc! { "bsonType": "object" };
+                    // The server rejects an empty `required`
+                    if !required.is_empty() {
+                        schema.insert("required", required);
+                    }
+                    schema.insert("properties", properties);
+                    schema
+                }
+            }
         }
     } else {
         quote! {}
     };
 
+    Ok((
+        defs,
+        quote! {
+            #collection
+            #field
+            #filter
+            #update
+            #json_schema
+            #references
+            #keys
+        },
+    ))
+}
+
+// Builds the views declared using `#[mongo(view(...))]`, returning their definitions, which are
+// always declared next to the type, along with their `View` impls
+fn impl_views(
+    name: &Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    style: &Style,
+    fields: &[Field],
+    attrs: &attr::Container,
+) -> Result<(Vec<TokenStream>, TokenStream), Vec<syn::Error>> {
+    if attrs.views.is_empty() {
+        return Ok((vec![], quote! {}));
+    }
+    if attrs.collection.is_none() {
+        return Err(vec![syn::Error::new_spanned(
+            name,
+            "mongo container attribute `view` requires `collection`",
+        )]);
+    }
+    if *style != Style::Struct || !generics.params.is_empty() {
+        return Err(vec![syn::Error::new_spanned(
+            name,
+            "#[derive(Mongo)] can only derive `view` for non-generic named structs",
+        )]);
+    }
+    let mut defs = vec![];
+    let mut impls = vec![];
+    let mut errors = vec![];
+    for view in &attrs.views {
+        let fields = view
+            .fields
+            .iter()
+            .filter_map(|ident| {
+                let field = fields
+                    .iter()
+                    .find(|f| matches!(&f.member, Member::Named(name) if name == ident));
+                if field.is_none() {
+                    errors.push(syn::Error::new_spanned(
+                        ident,
+                        format!("unknown field `{}` in mongo view `{}`", ident, view.name),
+                    ));
+                }
+                field
+            })
+            .collect::<Vec<_>>();
+        let ident = Ident::new(&format!("{}{}", name, view.name), view.name.span());
+        let doc = format!("The `{}` view of [`{}`].", view.name, name);
+        let view_fields = fields.iter().map(|f| {
+            let (ty, member, vis) = (&f.ty, &f.member, &f.raw.vis);
+            // Pass the attrs along, stripping the local attrs as the conversion is derived here
+            let raw_attrs = f
+                .raw
+                .attrs
+                .iter()
+                .filter(|a| !(a.path().is_ident(BSON) || a.path().is_ident(MONGO)));
+            quote! {
+                #(#raw_attrs)*
+                #vis #member: #ty
+            }
+        });
+        let derive = if attrs.bson == attr::BsonMode::Serde {
+            quote! { #[derive(_serde::Deserialize)] }
+        } else {
+            quote! {}
+        };
+        defs.push(with_crate_paths(quote! {
+            #[doc = #doc]
+            #derive
+            #vis struct #ident {
+                #(#view_fields),*
+            }
+        }));
+
+        // The `_id` is always returned unless it is excluded
+        let mut projection = vec![];
+        if !fields.iter().any(|f| field_to_id(f) == "_id") {
+            projection.push(quote! { doc.insert("_id", 0); });
+        }
+        projection.extend(fields.iter().map(|f| {
+            let id = field_to_id(f);
+            quote! { doc.insert(#id, 1); }
+        }));
+        let from = if attrs.bson == attr::BsonMode::Serde {
+            quote! {
+                _mongo::bson::from_document(document).map_err(_mongo::Error::invalid_document)
+            }
+        } else {
+            let options = fields.iter().map(|f| {
+                let member = &f.member;
+                let ty = &f.ty;
+                quote! {
+                    let mut #member: Option<#ty> = None;
+                }
+            });
+            let values = fields.iter().map(|f| impl_struct_try_from_bson_field(f));
+            let missing = fields.iter().map(|f| impl_struct_missing_field(f));
+            let expects = fields.iter().map(|f| {
+                let member = &f.member;
+                let value = impl_struct_field_value(f);
+                quote! {
+                    #member: #value
+                }
+            });
+            quote! {
+                fn convert(
+                    mut doc: _mongo::bson::Document,
+                ) -> core::result::Result<#ident, _mongo::ext::bson::de::Error> {
+                    use _mongo::ext::bson::de::ErrorExt;
+                    #(#options)*
+                    #(#values)*
+                    #(#missing)*
+                    Ok(#ident {
+                        #(#expects),*
+                    })
+                }
+                convert(document).map_err(_mongo::Error::invalid_document)
+            }
+        };
+        impls.push(quote! {
+            #[automatically_derived]
+            impl _mongo::View for #ident {
+                type Collection = #name;
+                fn projection() -> _mongo::bson::Document {
+                    let mut doc = _mongo::bson::Document::new();
+                    #(#projection)*
+                    doc
+                }
+                fn from_document(document: _mongo::bson::Document) -> core::result::Result<Self, _mongo::Error> {
+                    #from
+                }
+            }
+        });
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok((defs, quote! { #(#impls)* }))
+}
+
+// Wraps the `value` of a `#[bson(nested)]` field along with its conversion, as used by `with`
+fn nested_with(ty: &syn::Type, value: TokenStream) -> TokenStream {
+    let into = nested_into_bson(ty, quote! { v });
     quote! {
-        #collection
-        #field
-        #filter
-        #update
+        _mongo::ext::bson::With::new(#value, |v| Ok(#into))
+    }
+}
+
+// Wraps the `value` of a smart pointer field along with its conversion, see `nested_with`
+fn pointer_with(ty: &syn::Type, value: TokenStream) -> TokenStream {
+    let into = pointer_into_bson(ty, quote! { v });
+    quote! {
+        _mongo::ext::bson::With::new(#value, |v| Ok(#into))
+    }
+}
+
+// Builds the checks declared by `#[mongo(validate(...))]` on `__value`, a reference to the field's
+// value
+fn field_validation(f: &Field) -> Option<TokenStream> {
+    let validate = f.attrs.validate.as_ref()?;
+    let id = field_to_id(f);
+    let mut checks = vec![];
+    if let Some(min) = &validate.min {
+        checks.push(quote_spanned! {min.span()=>
+            _mongo::validate::min(#id, __value, #min)?;
+        });
+    }
+    if let Some(max) = &validate.max {
+        checks.push(quote_spanned! {max.span()=>
+            _mongo::validate::max(#id, __value, #max)?;
+        });
+    }
+    if let Some((min, max)) = &validate.length {
+        let min = min
+            .as_ref()
+            .map_or(quote! { None }, |min| quote! { Some(#min) });
+        let max = max
+            .as_ref()
+            .map_or(quote! { None }, |max| quote! { Some(#max) });
+        checks.push(quote! {
+            _mongo::validate::length(#id, __value, #min, #max)?;
+        });
+    }
+    if let Some(regex) = &validate.regex {
+        checks.push(quote! {
+            static __PATTERN: _mongo::validate::Pattern = _mongo::validate::Pattern::new(#regex);
+            __PATTERN.check(#id, __value)?;
+        });
+    }
+    Some(quote! {
+        #(#checks)*
+    })
+}
+
+// Whether the type is a `Vec`, going by the last segment of its path.
+fn is_vec(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            matches!(path.path.segments.last(), Some(segment) if segment.ident == "Vec")
+        }
+        _ => false,
+    }
+}
+
+// Returns the inner type of an `Option`, going by the last segment of its path.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
     }
 }
@@ -0,0 +1,160 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bson::Document;
+use futures::Stream;
+use mongodb::change_stream::event::{ChangeStreamEvent, ResumeToken};
+
+use crate::change_stream::{ChangeEvent, TokenStore};
+use crate::collection::Collection;
+
+type Event = ChangeStreamEvent<Document>;
+
+/// A typed change stream.
+///
+/// This wraps the `ChangeStream` so that it can automatically return typed `ChangeEvent`s. The
+/// stream only ends should it be closed, i.e. by the collection being dropped.
+///
+/// # Resuming
+///
+/// The token to resume watching after the last event is returned by `resume_token`, and can be
+/// persisted automatically by attaching a `TokenStore` using `store`.
+pub struct ChangeStream<C>
+where
+    C: Collection,
+{
+    stream: mongodb::change_stream::ChangeStream<Event>,
+    resume_token: Option<ResumeToken>,
+    store: Option<Box<dyn TokenStore + Send>>,
+    unsaved: bool,
+    document_type: PhantomData<C>,
+}
+
+impl<C> ChangeStream<C>
+where
+    C: Collection,
+{
+    /// Allow access to the wrapped [`mongodb::change_stream::ChangeStream`](https://docs.rs/mongodb/2.8.2/mongodb/change_stream/struct.ChangeStream.html).
+    pub fn into_inner(self) -> mongodb::change_stream::ChangeStream<Event> {
+        self.stream
+    }
+
+    /// Returns the token to resume watching after the last event returned by this stream.
+    pub fn resume_token(&self) -> Option<&ResumeToken> {
+        self.resume_token.as_ref()
+    }
+
+    /// Saves the token of the last event returned by this stream to its store, if any.
+    ///
+    /// This is done automatically when the next event is requested, it only needs to be called
+    /// when the stream is to be dropped once an event has been handled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the token could not be saved to the store.
+    pub fn save_token(&mut self) -> crate::Result<()> {
+        if let (Some(store), Some(token), true) = (
+            self.store.as_mut(),
+            self.resume_token.as_ref(),
+            self.unsaved,
+        ) {
+            store.save(token)?;
+        }
+        self.unsaved = false;
+        Ok(())
+    }
+
+    /// Attaches a `TokenStore` to this stream, which is used to persist the resume token.
+    ///
+    /// The token of an event is saved once the next event is requested, meaning that it has been
+    /// handled, so that watching can be resumed using `Watch::resume_from` without missing any
+    /// events. Should the stream be dropped without the token being saved, the last event is
+    /// returned again once resumed.
+    ///
+    /// # Notes
+    ///
+    /// The token is saved from within `poll_next`, blocking the executor thread for as long as
+    /// `TokenStore::save` takes, so the store must be cheap and must not block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// # #[derive(Debug, Bson, Mongo)]
+    /// # #[mongo(collection="users", field, filter, update)]
+    /// # pub struct User {
+    /// #     name: String,
+    /// # }
+    /// # async fn doc<S>(store: S) -> Result<(), mongod::Error>
+    /// # where
+    /// #     S: mongod::change_stream::TokenStore + Send + 'static,
+    /// # {
+    /// use futures::stream::StreamExt;
+    ///
+    /// let client = mongod::Client::new();
+    ///
+    /// let mut stream = mongod::query::Watch::<User>::new()
+    ///     .resume_from(&store)?
+    ///     .query(&client)
+    ///     .await?
+    ///     .store(store);
+    /// while let Some(res) = stream.next().await {
+    ///     println!("{:?}", res?.change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn store<S>(mut self, store: S) -> Self
+    where
+        S: TokenStore + Send + 'static,
+    {
+        self.store = Some(Box::new(store));
+        self
+    }
+}
+
+impl<C> From<mongodb::change_stream::ChangeStream<Event>> for ChangeStream<C>
+where
+    C: Collection,
+{
+    fn from(stream: mongodb::change_stream::ChangeStream<Event>) -> Self {
+        ChangeStream {
+            stream,
+            resume_token: None,
+            store: None,
+            unsaved: false,
+            document_type: PhantomData,
+        }
+    }
+}
+
+impl<C> Stream for ChangeStream<C>
+where
+    C: Collection,
+{
+    type Item = crate::Result<ChangeEvent<C>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // NOTE: `TokenStore::save` is sync, and is documented as having to be cheap
+        if let Err(e) = self.save_token() {
+            return Poll::Ready(Some(Err(e)));
+        }
+        let next = Pin::new(&mut self.stream).poll_next(cx);
+        match next {
+            Poll::Ready(opt) => Poll::Ready(opt.map(|result| {
+                let event = result.map_err(crate::error::mongodb)?;
+                let token = self.stream.resume_token();
+                self.resume_token = Some(token.unwrap_or_else(|| event.id.clone()));
+                self.unsaved = true;
+                ChangeEvent::try_from(event)
+            })),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<C> Unpin for ChangeStream<C> where C: Collection {}
@@ -2,10 +2,13 @@ use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use bson::{oid::ObjectId, Document};
+use bson::document::ValueAccessError;
+use bson::{Bson, Document};
 use futures::Stream;
+use mongodb::ClientSession;
 
 use crate::collection::Collection;
+use crate::view::View;
 
 /// A typed cursor.
 ///
@@ -44,15 +47,19 @@ impl<T> Stream for TypedCursor<T>
 where
     T: Collection,
 {
-    type Item = crate::Result<(ObjectId, T)>;
+    type Item = crate::Result<(Bson, T)>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let next = Pin::new(&mut self.cursor).poll_next(cx);
         match next {
             Poll::Ready(opt) => Poll::Ready(opt.map(|result| {
                 let doc = result.map_err(crate::error::mongodb)?;
-                let oid = doc.get_object_id("_id").map_err(crate::error::bson)?;
-                Ok((oid, T::from_document(doc)?))
+                let id = doc
+                    .get("_id")
+                    .cloned()
+                    .ok_or(ValueAccessError::NotPresent)
+                    .map_err(crate::error::bson)?;
+                Ok((id, T::from_document(doc)?))
             })),
             Poll::Pending => Poll::Pending,
         }
@@ -60,3 +67,136 @@ where
 }
 
 impl<T> Unpin for TypedCursor<T> where T: Collection {}
+
+/// A cursor of views.
+///
+/// This wraps the `Cursor` so that it can automatically return projected views of documents.
+pub struct ViewCursor<V>
+where
+    V: View,
+{
+    cursor: mongodb::Cursor<Document>,
+    view_type: PhantomData<V>,
+}
+
+impl<V> ViewCursor<V>
+where
+    V: View,
+{
+    /// Allow access to the wrapped [`mongodb::Cursor`](https://docs.rs/mongodb/2.0.0/mongodb/struct.Cursor.html).
+    pub fn into_inner(self) -> mongodb::Cursor<Document> {
+        self.cursor
+    }
+}
+
+impl<V> From<mongodb::Cursor<Document>> for ViewCursor<V>
+where
+    V: View,
+{
+    fn from(cursor: mongodb::Cursor<Document>) -> Self {
+        ViewCursor {
+            cursor,
+            view_type: PhantomData,
+        }
+    }
+}
+
+impl<V> Stream for ViewCursor<V>
+where
+    V: View,
+{
+    type Item = crate::Result<V>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = Pin::new(&mut self.cursor).poll_next(cx);
+        match next {
+            Poll::Ready(opt) => Poll::Ready(opt.map(|result| {
+                let doc = result.map_err(crate::error::mongodb)?;
+                V::from_document(doc)
+            })),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<V> Unpin for ViewCursor<V> where V: View {}
+
+/// A cursor bound to a `ClientSession`.
+///
+/// The cursor borrows its session for as long as it is alive, so that the documents read through
+/// it are causally consistent with the other operations made in the session. As the session must
+/// be passed to the wrapped cursor when fetching documents, this is iterated using `next` rather
+/// than being a `Stream`.
+pub struct SessionCursor<'s> {
+    cursor: mongodb::SessionCursor<Document>,
+    session: &'s mut ClientSession,
+}
+
+impl<'s> SessionCursor<'s> {
+    pub(crate) fn new(
+        cursor: mongodb::SessionCursor<Document>,
+        session: &'s mut ClientSession,
+    ) -> Self {
+        Self { cursor, session }
+    }
+
+    /// Allow access to the wrapped [`mongodb::SessionCursor`](https://docs.rs/mongodb/2.8.2/mongodb/struct.SessionCursor.html),
+    /// along with its session.
+    pub fn into_inner(self) -> (mongodb::SessionCursor<Document>, &'s mut ClientSession) {
+        (self.cursor, self.session)
+    }
+
+    /// Returns the next document, or `None` once the cursor has been exhausted.
+    pub async fn next(&mut self) -> Option<crate::Result<Document>> {
+        self.cursor
+            .next(self.session)
+            .await
+            .map(|result| result.map_err(crate::error::mongodb))
+    }
+}
+
+/// A typed cursor bound to a `ClientSession`.
+///
+/// This wraps the `SessionCursor` so that it can automatically return typed documents.
+pub struct TypedSessionCursor<'s, T>
+where
+    T: Collection,
+{
+    cursor: SessionCursor<'s>,
+    document_type: PhantomData<T>,
+}
+
+impl<'s, T> TypedSessionCursor<'s, T>
+where
+    T: Collection,
+{
+    /// Allow access to the wrapped `SessionCursor`.
+    pub fn into_inner(self) -> SessionCursor<'s> {
+        self.cursor
+    }
+
+    /// Returns the next document, or `None` once the cursor has been exhausted.
+    pub async fn next(&mut self) -> Option<crate::Result<(Bson, T)>> {
+        self.cursor.next().await.map(|result| {
+            let doc = result?;
+            let id = doc
+                .get("_id")
+                .cloned()
+                .ok_or(ValueAccessError::NotPresent)
+                .map_err(crate::error::bson)?;
+            Ok((id, T::from_document(doc)?))
+        })
+    }
+}
+
+impl<'s, T> From<SessionCursor<'s>> for TypedSessionCursor<'s, T>
+where
+    T: Collection,
+{
+    fn from(cursor: SessionCursor<'s>) -> Self {
+        TypedSessionCursor {
+            cursor,
+            document_type: PhantomData,
+        }
+    }
+}
@@ -1,5 +1,9 @@
+pub use self::change_stream::ChangeStream;
 pub use self::client::{Client, ClientBuilder};
-pub use self::cursor::TypedCursor;
+pub use self::cursor::{SessionCursor, TypedCursor, TypedSessionCursor, ViewCursor};
 
+mod change_stream;
 pub mod client;
 mod cursor;
+#[cfg(feature = "cert-key-password")]
+mod tls;
@@ -0,0 +1,148 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use openssl::pkey::PKey;
+use openssl::x509::X509;
+
+/// A decrypted certificate key file, which is removed once dropped.
+///
+/// The mongodb driver can only load an unencrypted certificate key file, so the key is decrypted
+/// into a file that only needs to live until the driver has built its TLS connector. On Linux the
+/// file is unnamed, so the plaintext key never appears in the filesystem and cannot be left behind
+/// should the process crash, it is instead loaded through `/proc/self/fd`. Elsewhere it is written
+/// to a private temporary directory, readable only by the current user.
+pub(crate) struct DecryptedKey {
+    path: PathBuf,
+    file: File,
+    #[cfg(not(target_os = "linux"))]
+    _dir: tempfile::TempDir,
+}
+
+impl DecryptedKey {
+    /// The path the driver should load the decrypted key from.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[cfg(target_os = "linux")]
+    fn create() -> std::io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = tempfile::tempfile()?;
+        Ok(Self {
+            path: PathBuf::from(format!("/proc/self/fd/{}", file.as_raw_fd())),
+            file,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn create() -> std::io::Result<Self> {
+        let dir = tempfile::Builder::new().prefix("mongod-").tempdir()?;
+        let path = dir.path().join("cert_key.pem");
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        Ok(Self {
+            file: options.open(&path)?,
+            path,
+            _dir: dir,
+        })
+    }
+}
+
+pub(crate) fn decrypt_cert_key(path: &Path, password: &str) -> crate::Result<DecryptedKey> {
+    let pem = std::fs::read(path).map_err(crate::error::builder)?;
+    let key = PKey::private_key_from_pem_passphrase(&pem, password.as_bytes())
+        .map_err(crate::error::builder)?;
+    let certs = X509::stack_from_pem(&pem).map_err(crate::error::builder)?;
+
+    let mut decrypted = DecryptedKey::create().map_err(crate::error::builder)?;
+    let file = &mut decrypted.file;
+    for cert in certs {
+        file.write_all(&cert.to_pem().map_err(crate::error::builder)?)
+            .map_err(crate::error::builder)?;
+    }
+    file.write_all(
+        &key.private_key_to_pem_pkcs8()
+            .map_err(crate::error::builder)?,
+    )
+    .map_err(crate::error::builder)?;
+    file.flush().map_err(crate::error::builder)?;
+    Ok(decrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::rsa::Rsa;
+    use openssl::symm::Cipher;
+    use openssl::x509::X509NameBuilder;
+    use tempfile::NamedTempFile;
+
+    fn encrypted_cert_key() -> (NamedTempFile, X509, PKey<openssl::pkey::Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "mongod").unwrap();
+        let name = name.build();
+        let mut cert = X509::builder().unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        let cert = cert.build();
+
+        let mut encrypted = NamedTempFile::new().unwrap();
+        encrypted.write_all(&cert.to_pem().unwrap()).unwrap();
+        encrypted
+            .write_all(
+                &key.private_key_to_pem_pkcs8_passphrase(Cipher::aes_256_cbc(), b"foo")
+                    .unwrap(),
+            )
+            .unwrap();
+        (encrypted, cert, key)
+    }
+
+    #[test]
+    fn decrypt_cert_key_file() {
+        let (encrypted, cert, key) = encrypted_cert_key();
+
+        assert!(decrypt_cert_key(encrypted.path(), "bar").is_err());
+
+        let decrypted = decrypt_cert_key(encrypted.path(), "foo").unwrap();
+        let pem = std::fs::read(decrypted.path()).unwrap();
+        assert_eq!(X509::from_pem(&pem).unwrap(), cert);
+        assert!(PKey::private_key_from_pem(&pem).unwrap().public_eq(&key));
+
+        // The plaintext key must not be left behind once the driver has loaded it, should the
+        // descriptor have been reused by a concurrent test its contents will differ
+        let path = decrypted.path().to_owned();
+        drop(decrypted);
+        assert_ne!(std::fs::read(path).ok(), Some(pem));
+    }
+
+    #[test]
+    fn build_with_cert_key_password() {
+        let (encrypted, _, _) = encrypted_cert_key();
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let _guard = rt.enter();
+        let builder = || {
+            crate::Client::builder()
+                .uri("mongodb://127.0.0.1:1")
+                .cert_key(encrypted.path().to_str().unwrap())
+        };
+        assert!(builder().cert_key_password("bar").build().is_err());
+        assert!(builder().cert_key_password("foo").build().is_ok());
+    }
+}
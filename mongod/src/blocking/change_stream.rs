@@ -0,0 +1,275 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bson::Document;
+use futures::stream::StreamExt;
+use mongodb::change_stream::event::{ChangeStreamEvent, ResumeToken};
+
+use super::client::recv;
+use crate::change_stream::{ChangeEvent, TokenStore};
+use crate::collection::Collection;
+
+type Event = ChangeStreamEvent<Document>;
+type Response = Option<crate::Result<(Event, Option<ResumeToken>)>>;
+type Responder = std::sync::mpsc::Sender<Response>;
+
+/// A blocking wrapper around the raw `mongodb::change_stream::ChangeStream`.
+pub(crate) struct RawChangeStream {
+    closed: bool,
+    timeout: Option<Duration>,
+    tx: tokio::sync::mpsc::UnboundedSender<Responder>,
+}
+
+impl RawChangeStream {
+    pub(crate) fn new(stream: mongodb::change_stream::ChangeStream<Event>) -> Self {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Responder>();
+        let f = async move {
+            let mut stream = stream;
+            let mut pending = None;
+            while let Some(tx) = rx.recv().await {
+                let resp = match pending.take() {
+                    Some(resp) => resp,
+                    None => stream.next().await.map(|n| {
+                        n.map(|event| (event, stream.resume_token()))
+                            .map_err(crate::error::mongodb)
+                    }),
+                };
+                // NOTE: Should the request have timed out the event is kept for the next one
+                if let Err(e) = tx.send(resp) {
+                    pending = Some(e.0);
+                }
+            }
+        };
+        tokio::spawn(f);
+        Self::from(tx)
+    }
+
+    pub(crate) fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn next(&mut self) -> Response {
+        if self.closed {
+            return None;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        let res = match self.tx.send(tx) {
+            Ok(()) => recv(&rx, self.timeout),
+            Err(_) => Err(crate::error::runtime(
+                "failed to send request to blocking thread",
+            )),
+        };
+        match res {
+            Ok(resp) => {
+                self.closed = resp.is_none();
+                resp
+            }
+            Err(e) => {
+                self.closed = !matches!(e.kind(), crate::ErrorKind::Timeout);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl From<tokio::sync::mpsc::UnboundedSender<Responder>> for RawChangeStream {
+    fn from(tx: tokio::sync::mpsc::UnboundedSender<Responder>) -> Self {
+        Self {
+            closed: false,
+            timeout: None,
+            tx,
+        }
+    }
+}
+
+/// A blocking change stream.
+///
+/// This iterates over the changes made to a collection, blocking until the next change occurs.
+/// The iterator only ends should the stream be closed, i.e. by the collection being dropped, or
+/// once an error is returned for the client being shut down. Waiting for a change is subject to
+/// the timeout of the client that opened the stream, a change that timed out is returned by the
+/// next call instead.
+///
+/// # Resuming
+///
+/// The token to resume watching after the last event is returned by `resume_token`, and can be
+/// persisted automatically by attaching a `TokenStore` using `store`.
+pub struct ChangeStream<C>
+where
+    C: Collection,
+{
+    stream: RawChangeStream,
+    resume_token: Option<ResumeToken>,
+    store: Option<Box<dyn TokenStore + Send>>,
+    unsaved: bool,
+    document_type: PhantomData<C>,
+}
+
+impl<C> ChangeStream<C>
+where
+    C: Collection,
+{
+    /// Returns the token to resume watching after the last event returned by this stream.
+    pub fn resume_token(&self) -> Option<&ResumeToken> {
+        self.resume_token.as_ref()
+    }
+
+    /// Saves the token of the last event returned by this stream to its store, if any.
+    ///
+    /// This is done automatically when the next event is requested, it only needs to be called
+    /// when the stream is to be dropped once an event has been handled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the token could not be saved to the store.
+    pub fn save_token(&mut self) -> crate::Result<()> {
+        if let (Some(store), Some(token), true) = (
+            self.store.as_mut(),
+            self.resume_token.as_ref(),
+            self.unsaved,
+        ) {
+            store.save(token)?;
+        }
+        self.unsaved = false;
+        Ok(())
+    }
+
+    /// Attaches a `TokenStore` to this stream, which is used to persist the resume token.
+    ///
+    /// The token of an event is saved once the next event is requested, meaning that it has been
+    /// handled, so that watching can be resumed using `Watch::resume_from` without missing any
+    /// events. Should the stream be dropped without the token being saved, the last event is
+    /// returned again once resumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// # #[derive(Debug, Bson, Mongo)]
+    /// # #[mongo(collection="users", field, filter, update)]
+    /// # pub struct User {
+    /// #     name: String,
+    /// # }
+    /// # fn doc<S>(store: S) -> Result<(), mongod::Error>
+    /// # where
+    /// #     S: mongod::change_stream::TokenStore + Send + 'static,
+    /// # {
+    /// let client = mongod::blocking::Client::new();
+    ///
+    /// let stream = mongod::query::Watch::<User>::new()
+    ///     .resume_from(&store)?
+    ///     .blocking(&client)?
+    ///     .store(store);
+    /// for res in stream {
+    ///     println!("{:?}", res?.change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn store<S>(mut self, store: S) -> Self
+    where
+        S: TokenStore + Send + 'static,
+    {
+        self.store = Some(Box::new(store));
+        self
+    }
+}
+
+impl<C> From<RawChangeStream> for ChangeStream<C>
+where
+    C: Collection,
+{
+    fn from(stream: RawChangeStream) -> Self {
+        ChangeStream {
+            stream,
+            resume_token: None,
+            store: None,
+            unsaved: false,
+            document_type: PhantomData,
+        }
+    }
+}
+
+impl<C> Iterator for ChangeStream<C>
+where
+    C: Collection,
+{
+    type Item = crate::Result<ChangeEvent<C>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.save_token() {
+            return Some(Err(e));
+        }
+        self.stream.next().map(|res| {
+            let (event, token) = res?;
+            self.resume_token = Some(token.unwrap_or_else(|| event.id.clone()));
+            self.unsaved = true;
+            ChangeEvent::try_from(event)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::tests::User;
+
+    #[derive(Clone, Default)]
+    struct MemoryStore(Arc<Mutex<Vec<ResumeToken>>>);
+
+    impl TokenStore for MemoryStore {
+        fn load(&self) -> crate::Result<Option<ResumeToken>> {
+            Ok(self.0.lock().unwrap().last().cloned())
+        }
+
+        fn save(&mut self, token: &ResumeToken) -> crate::Result<()> {
+            self.0.lock().unwrap().push(token.clone());
+            Ok(())
+        }
+    }
+
+    fn token(data: &str) -> ResumeToken {
+        bson::from_document(doc! { "_data": data }).unwrap()
+    }
+
+    #[test]
+    fn store_token() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Responder>();
+        std::thread::spawn(move || {
+            for name in &["a", "b"] {
+                let event = bson::from_document(doc! {
+                    "_id": { "_data": *name },
+                    "operationType": "insert",
+                    "fullDocument": { "name": *name },
+                })
+                .unwrap();
+                let tx = rx.blocking_recv().unwrap();
+                tx.send(Some(Ok((event, Some(token(name)))))).unwrap();
+            }
+        });
+
+        let store = MemoryStore::default();
+        let mut stream = ChangeStream::<User>::from(RawChangeStream::from(tx)).store(store.clone());
+        assert!(stream.resume_token().is_none());
+
+        stream.next().unwrap().unwrap();
+        assert_eq!(stream.resume_token(), Some(&token("a")));
+        assert_eq!(store.load().unwrap(), None);
+
+        stream.next().unwrap().unwrap();
+        assert_eq!(stream.resume_token(), Some(&token("b")));
+        assert_eq!(store.load().unwrap(), Some(token("a")));
+
+        stream.save_token().unwrap();
+        stream.save_token().unwrap();
+        assert_eq!(*store.0.lock().unwrap(), vec![token("a"), token("b")]);
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+}
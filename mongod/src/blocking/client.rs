@@ -1,25 +1,42 @@
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::Arc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::thread;
+use std::time::Duration;
 
 use bson::oid::ObjectId;
 use bson::Document;
+use futures::future::{select, Either, FutureExt};
 use mongodb::options::{
-    DeleteOptions, FindOptions, InsertManyOptions, ReplaceOptions, UpdateOptions,
+    AggregateOptions, ChangeStreamOptions, ClientOptions, CreateCollectionOptions, DeleteOptions,
+    FindOneAndDeleteOptions, FindOneAndReplaceOptions, FindOneAndUpdateOptions, FindOptions,
+    GridFsBucketOptions, GridFsUploadOptions, InsertManyOptions, ReadConcern, ReadPreference,
+    ReplaceOptions, ResolverConfig, UpdateOptions, WriteConcern,
 };
 use mongodb::results::{DeleteResult, InsertManyResult, UpdateResult};
+use mongodb::IndexModel;
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
 
+use super::change_stream::{ChangeStream, RawChangeStream};
 use super::cursor::{Cursor, TypedCursor};
+use super::database::{self, Database};
+use super::gridfs::{DownloadStream, UploadStream};
 use crate::collection::Collection;
 use crate::filter::{AsFilter, Filter};
 use crate::query;
 use crate::r#async;
+use crate::reference::Ref;
 use crate::update::{AsUpdate, Update, Updates};
 
 /// A `ClientBuilder` can be used to create a `Client` with custom configuration.
+#[derive(Clone)]
 pub struct ClientBuilder {
     builder: r#async::ClientBuilder,
+    request_timeout: Option<Duration>,
+    respawn: bool,
+    runtime: Option<Handle>,
 }
 
 impl Default for ClientBuilder {
@@ -35,17 +52,58 @@ impl ClientBuilder {
     pub fn new() -> Self {
         Self {
             builder: r#async::ClientBuilder::new(),
+            request_timeout: None,
+            respawn: false,
+            runtime: None,
         }
     }
 
+    /// Constructs a new `ClientBuilder` from a configuration file.
+    ///
+    /// The format of the file is determined by its extension, TOML is used for `.toml` files and
+    /// YAML for `.yaml` or `.yml` files. The following keys are supported, each mapping to the
+    /// builder method of the same name: `uri`, `database`, `hosts`, `username`, `password`, `ca`,
+    /// `cert_key`, `cert_key_password`, `tls_allow_invalid_hostnames`, `max_pool_size` and
+    /// `min_pool_size`. A `password` without a `username` is used for the username given in the
+    /// uri.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `toml` and/or `yaml` features to be enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::ClientBuilder::from_file("./config/mongo.toml")?
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file cannot be read, is of an unsupported format, or is invalid.
+    #[cfg(any(feature = "toml", feature = "yaml"))]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        Ok(Self {
+            builder: r#async::ClientBuilder::from_file(path)?,
+            request_timeout: None,
+            respawn: false,
+            runtime: None,
+        })
+    }
+
     /// Returns a `Client` built from this `ClientBuilder` configuration.
     ///
     /// # Errors
     ///
     /// This method fails if the `mongodb::Client` cannot be initialised.
     pub fn build(self) -> crate::Result<Client> {
+        let timeout = self.request_timeout;
         Ok(Client {
             inner: Arc::new(ClientInner::new(self, None)?),
+            timeout,
         })
     }
 
@@ -70,6 +128,33 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets this client to authenticate using the MONGODB-AWS mechanism.
+    ///
+    /// When credentials are set with `auth` they are used as the AWS access key id and secret
+    /// access key, otherwise they are sourced from the environment, falling back to the ECS task or
+    /// EC2 instance role. The session token is only needed when using temporary credentials.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `aws-auth` feature to be enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .uri("mongodb+srv://cluster0.example.com")
+    ///         .aws_auth(None::<String>)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "aws-auth")]
+    pub fn aws_auth<T: Display>(mut self, session_token: Option<T>) -> Self {
+        self.builder = self.builder.aws_auth(session_token);
+        self
+    }
+
     /// Sets the CA file that should be used by this client for TLS.
     ///
     /// # Example
@@ -104,8 +189,103 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the maximum number of connections that this client should pool per server.
+    ///
+    /// This overrides the `maxPoolSize` option given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .max_pool_size(20)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_pool_size(mut self, size: u32) -> Self {
+        self.builder = self.builder.max_pool_size(size);
+        self
+    }
+
+    /// Sets the minimum number of connections that this client should pool per server.
+    ///
+    /// This overrides the `minPoolSize` option given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .min_pool_size(5)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn min_pool_size(mut self, size: u32) -> Self {
+        self.builder = self.builder.min_pool_size(size);
+        self
+    }
+
+    /// Sets the password that should be used to decrypt the certificate key file.
+    ///
+    /// This overrides the `tlsCertificateKeyFilePassword` option given in the uri.
+    ///
+    /// # Security
+    ///
+    /// The mongodb driver can only load an unencrypted key from a file, so the key is decrypted
+    /// into a temporary file that is removed as soon as the client has been built. On Linux the
+    /// file is unnamed and never visible in the filesystem, elsewhere it is written to a private
+    /// directory readable only by the current user, and could be left behind should the process
+    /// be killed while the client is being built.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cert-key-password` feature to be enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .cert_key("./certs/foo.pem")
+    ///         .cert_key_password("bar")
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cert_key_password<I: Into<String>>(mut self, password: I) -> Self {
+        self.builder = self.builder.cert_key_password(password);
+        self
+    }
+
+    /// Sets the `mongodb::options::ClientOptions` that should be used by this client.
+    ///
+    /// This allows anything supported by the mongodb driver to be configured, the other builder
+    /// methods are still applied on top of these options. It cannot be used along side `uri`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let mut options = mongod::options::ClientOptions::default();
+    ///     options.app_name = Some("foo".to_owned());
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .client_options(options)
+    ///         .database("bar")
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn client_options(mut self, options: ClientOptions) -> Self {
+        self.builder = self.builder.client_options(options);
+        self
+    }
+
     /// Sets the database that should be used by this client.
     ///
+    /// This overrides the database given in the uri path, when neither are set `db` is used.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -121,8 +301,222 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the seed hosts that this client should use to connect to a mongo deployment.
+    ///
+    /// This overrides any hosts given in the uri, and allows a replica set to be discovered from
+    /// multiple seeds so that the client can fail over should one of them be unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .hosts(["a:27017", "b:27017", "c:27017"])
+    ///         .uri("mongodb://localhost/?replicaSet=rs0")
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hosts<I, H>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+        H: Into<String>,
+    {
+        self.builder = self.builder.hosts(hosts);
+        self
+    }
+
+    /// Sets the default read concern that should be used by this client.
+    ///
+    /// This overrides the `readConcernLevel` option given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .read_concern(mongod::options::ReadConcern::majority())
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_concern(mut self, concern: ReadConcern) -> Self {
+        self.builder = self.builder.read_concern(concern);
+        self
+    }
+
+    /// Sets the default read preference that should be used by this client.
+    ///
+    /// This overrides the `readPreference` options given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     use mongod::options::{ReadPreference, ReadPreferenceOptions};
+    ///
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .read_preference(ReadPreference::SecondaryPreferred {
+    ///             options: ReadPreferenceOptions::default(),
+    ///         })
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_preference(mut self, preference: ReadPreference) -> Self {
+        self.builder = self.builder.read_preference(preference);
+        self
+    }
+
+    /// Sets the DNS resolver that this client should use for `mongodb+srv` lookups.
+    ///
+    /// By default the system's resolver configuration is used. It cannot be used along side
+    /// `client_options`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .resolver_config(mongod::options::ResolverConfig::cloudflare())
+    ///         .uri("mongodb+srv://cluster0.example.com")
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolver_config(mut self, config: ResolverConfig) -> Self {
+        self.builder = self.builder.resolver_config(config);
+        self
+    }
+
+    /// Sets the timeout for requests made by this client.
+    ///
+    /// Should a request not complete in time it fails with a timeout error, rather than blocking
+    /// the calling thread indefinitely. This can be overridden for individual requests using
+    /// `Client::with_timeout`. The cursors, change streams and GridFS streams opened by a client
+    /// also apply the timeout to each batch, change or chunk they wait on.
+    ///
+    /// # Notes
+    ///
+    /// The timeout only stops the calling thread from waiting, the request itself carries on in
+    /// the background. This means that a write which timed out may still be applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .request_timeout(std::time::Duration::from_secs(10))
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether this client should respawn its runtime should it die.
+    ///
+    /// By default, once the runtime has died, i.e. because it panicked, all requests fail. When
+    /// enabled, the next request made after its death respawns the runtime and reconnects, the
+    /// requests in flight at the time still fail along with any cursors and streams.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .respawn(true)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn respawn(mut self, enable: bool) -> Self {
+        self.respawn = enable;
+        self
+    }
+
+    /// Sets the runtime that this client should run its requests on.
+    ///
+    /// By default each client spawns its own thread running a current-thread runtime, setting a
+    /// runtime allows many clients, or an application already using Tokio, to share its threads
+    /// instead.
+    ///
+    /// # Notes
+    ///
+    /// The runtime must be driven independently of the thread using the client, such as a
+    /// multi-thread runtime, and the client must not be used from within the runtime's tasks.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn doc(handle: tokio::runtime::Handle) -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .runtime(handle)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn runtime(mut self, handle: Handle) -> Self {
+        self.runtime = Some(handle);
+        self
+    }
+
+    /// Sets the socket timeout that should be used by this client.
+    ///
+    /// Operations that take longer than this to complete fail with a timeout error. This overrides
+    /// the `socketTimeoutMS` option given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .socket_timeout(std::time::Duration::from_secs(30))
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn socket_timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.socket_timeout(timeout);
+        self
+    }
+
+    /// Sets whether this client should allow servers to present certificates for other hostnames.
+    ///
+    /// This overrides the `tlsAllowInvalidHostnames` option given in the uri, and should only be
+    /// used when connecting to deployments whose certificates do not cover the hosts in use.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `openssl-tls` feature to be enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .ca("./certs/internal-ca.pem")
+    ///         .tls_allow_invalid_hostnames(true)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tls_allow_invalid_hostnames(mut self, allow: bool) -> Self {
+        self.builder = self.builder.tls_allow_invalid_hostnames(allow);
+        self
+    }
+
     /// Sets the uri that this client should use to connect to a mongo instance.
     ///
+    /// Both the `mongodb://` and `mongodb+srv://` schemes are supported, when using the latter the
+    /// hosts and any TXT record options are resolved when the client is built.
+    ///
+    /// The `uuidRepresentation` option is validate only, it does not configure how uuids are
+    /// converted, instead building the client fails should it not match the representation set
+    /// using `ext::bson::set_uuid_representation`.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -137,6 +531,50 @@ impl ClientBuilder {
         self.builder = self.builder.uri(uri);
         self
     }
+
+    /// Sets the default write concern that should be used by this client.
+    ///
+    /// This overrides the `w`, `wTimeoutMS` & `journal` options given in the uri.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     use mongod::options::{Acknowledgment, WriteConcern};
+    ///
+    ///     let mut concern = WriteConcern::default();
+    ///     concern.w = Some(Acknowledgment::Majority);
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .write_concern(concern)
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_concern(mut self, concern: WriteConcern) -> Self {
+        self.builder = self.builder.write_concern(concern);
+        self
+    }
+
+    /// Sets this client to authenticate using the MONGODB-X509 mechanism.
+    ///
+    /// The certificate key file is presented to the server both for TLS and to identify the user,
+    /// so this is the same as setting `cert_key` along with the auth mechanism.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    ///     let _client = mongod::blocking::Client::builder()
+    ///         .ca("./certs/ca.pem")
+    ///         .x509("./certs/foo.pem")
+    ///         .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn x509<I: Into<String>>(mut self, cert_key_path: I) -> Self {
+        self.builder = self.builder.x509(cert_key_path);
+        self
+    }
 }
 
 /// A synchronous `Client` to query mongo with.
@@ -149,6 +587,7 @@ impl ClientBuilder {
 #[derive(Clone)]
 pub struct Client {
     inner: Arc<ClientInner>,
+    timeout: Option<Duration>,
 }
 
 impl Default for Client {
@@ -159,24 +598,67 @@ impl Default for Client {
 
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum Request {
-    Delete(bool, &'static str, Document, DeleteOptions),
-    Find(&'static str, Option<Document>, FindOptions),
-    Insert(&'static str, Vec<Document>, InsertManyOptions),
-    Replace(&'static str, Document, Document, ReplaceOptions),
-    Update(bool, &'static str, Document, Document, UpdateOptions),
+    Aggregate(String, Vec<Document>, AggregateOptions),
+    BulkWrite(String, Vec<query::WriteModel>, query::BulkOptions),
+    CreateCollection(String, Option<CreateCollectionOptions>, Vec<IndexModel>),
+    CreateIndexes(String, Vec<IndexModel>),
+    Delete(bool, String, Document, DeleteOptions),
+    DropCollection(String),
+    DropDatabase,
+    DropIndex(String, String),
+    Find(String, Option<Document>, FindOptions),
+    FindOneAndDelete(String, Document, FindOneAndDeleteOptions),
+    FindOneAndReplace(String, Document, Document, FindOneAndReplaceOptions),
+    FindOneAndUpdate(String, Document, Document, FindOneAndUpdateOptions),
+    GridFsDelete(GridFsBucketOptions, bson::Bson),
+    GridFsDownload(GridFsBucketOptions, bson::Bson),
+    GridFsUpload(GridFsBucketOptions, String, Option<GridFsUploadOptions>),
+    Insert(String, Vec<Document>, InsertManyOptions),
+    ListCollectionNames(Option<Document>),
+    ListIndexNames(String),
+    Replace(String, Document, Document, ReplaceOptions),
+    RunCommand(Document),
+    SyncIndexes(String, Vec<IndexModel>),
+    Update(bool, String, Document, Document, UpdateOptions),
+    Watch(String, Vec<Document>, ChangeStreamOptions),
 }
 pub(crate) enum Response {
+    Aggregate(Cursor),
+    BulkWrite(query::BulkResult),
+    CreateCollection,
+    CreateIndexes(Vec<String>),
     Delete(DeleteResult),
+    DropCollection,
+    DropDatabase,
+    DropIndex,
     Find(Cursor),
+    FindOneAndDelete(Option<Document>),
+    FindOneAndReplace(Option<Document>),
+    FindOneAndUpdate(Option<Document>),
+    GridFsDelete,
+    GridFsDownload(DownloadStream),
+    GridFsUpload(UploadStream),
     Insert(InsertManyResult),
+    ListCollectionNames(Vec<String>),
+    ListIndexNames(Vec<String>),
     Replace(UpdateResult),
+    RunCommand(Document),
+    SyncIndexes,
     Update(UpdateResult),
+    Watch(RawChangeStream),
 }
 type OneshotResponse = std::sync::mpsc::Sender<crate::Result<Response>>;
 type ThreadSender = tokio::sync::mpsc::UnboundedSender<(Request, OneshotResponse)>;
 
 struct ClientInner {
-    _thread: Option<thread::JoinHandle<()>>,
+    database: String,
+    respawn: Option<(ClientBuilder, Option<r#async::Client>)>,
+    worker: Mutex<Worker>,
+}
+
+struct Worker {
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<thread::JoinHandle<()>>,
     tx: ThreadSender,
 }
 
@@ -210,9 +692,77 @@ impl Client {
                 ClientBuilder::new(),
                 Some(crate::r#async::Client::from_client(client, database)),
             )?),
+            timeout: None,
         })
     }
 
+    /// Returns a copy of this client whose requests time out after the given duration.
+    ///
+    /// The copy shares its connection pool with this client, which allows the timeout to be set
+    /// for individual requests.
+    ///
+    /// # Notes
+    ///
+    /// As with `ClientBuilder::request_timeout`, a request that timed out is not cancelled, so a
+    /// write may still be applied.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn doc() -> Result<(), mongod::Error> {
+    /// let client = mongod::blocking::Client::new();
+    ///
+    /// let names = client
+    ///     .with_timeout(std::time::Duration::from_secs(1))
+    ///     .database()
+    ///     .list_collection_names(None)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeout: Some(timeout),
+        }
+    }
+
+    /// Returns a handle to the collection for the given type.
+    pub fn collection<C>(&self) -> database::Collection
+    where
+        C: Collection,
+    {
+        self.database().collection::<C>()
+    }
+
+    /// Returns a handle to the database used by this client.
+    pub fn database(&self) -> Database {
+        Database::new(self.clone(), self.inner.database.clone())
+    }
+
+    /// Creates the collection for the given type using its
+    /// [`create_options`](`Collection::create_options`), along with its
+    /// [`indexes`](`Collection::indexes`).
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the collection already exists.
+    pub fn create_collection<C>(&self) -> crate::Result<()>
+    where
+        C: Collection,
+    {
+        let resp = self.execute(Request::CreateCollection(
+            C::collection(),
+            C::create_options(),
+            C::indexes(),
+        ))?;
+        if let Response::CreateCollection = resp {
+            return Ok(());
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+
     /// Convenience method to delete documents from a collection using a given filter.
     ///
     /// # Errors
@@ -275,7 +825,7 @@ impl Client {
     /// # Errors
     ///
     /// This method fails if the mongodb encountered an error, or if the found document is invalid.
-    pub fn find_one<C, F>(&self, filter: F) -> crate::Result<Option<(ObjectId, C)>>
+    pub fn find_one<C, F>(&self, filter: F) -> crate::Result<Option<(bson::Bson, C)>>
     where
         C: AsFilter<F> + Collection,
         F: Filter,
@@ -289,23 +839,73 @@ impl Client {
         Ok(None)
     }
 
+    /// Convenience method to get a document from a collection using its `_id`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if the found document is invalid.
+    pub fn get<C, I>(&self, id: I) -> crate::Result<Option<C>>
+    where
+        C: Collection,
+        I: Into<bson::Bson>,
+    {
+        let mut cursor = query::Find::<C>::new().id(id.into()).blocking(self)?;
+        if let Some(res) = cursor.next() {
+            let (_, document) = res?;
+            return Ok(Some(document));
+        }
+        Ok(None)
+    }
+
+    /// Convenience method to fetch the document referenced by a `Ref`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if the found document is invalid.
+    pub fn resolve<C>(&self, reference: &Ref<C>) -> crate::Result<Option<C>>
+    where
+        C: Collection,
+    {
+        self.get(reference.id())
+    }
+
+    /// Convenience method to fetch the documents referenced by many `Ref`s in a single query.
+    ///
+    /// The documents are keyed by their `_id`, references to missing documents are left out.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if a found document is invalid.
+    pub fn resolve_many<C>(&self, references: &[Ref<C>]) -> crate::Result<HashMap<ObjectId, C>>
+    where
+        C: Collection,
+    {
+        let mut documents = HashMap::with_capacity(references.len());
+        if references.is_empty() {
+            return Ok(documents);
+        }
+        let cursor = query::Find::<C>::new()
+            .raw_filter(Ref::filter(references))
+            .blocking(self)?;
+        for res in cursor {
+            // NOTE: A `Ref` only ever holds an `ObjectId`, so the found documents' `_id`s are too
+            if let (bson::Bson::ObjectId(id), document) = res? {
+                documents.insert(id, document);
+            }
+        }
+        Ok(documents)
+    }
+
     /// Convenience method to insert documents in a collection.
     ///
     /// # Errors
     ///
     /// This method fails if the mongodb encountered an error, or if the found document is invalid.
-    pub fn insert<C>(&self, documents: Vec<C>) -> crate::Result<HashMap<usize, ObjectId>>
+    pub fn insert<C>(&self, documents: Vec<C>) -> crate::Result<HashMap<usize, bson::Bson>>
     where
         C: Collection,
     {
-        let result = query::Insert::new().blocking(self, documents)?;
-        Ok(result
-            .into_iter()
-            .filter_map(|(k, v)| match v {
-                bson::Bson::ObjectId(id) => Some((k, id)),
-                _ => None,
-            })
-            .collect())
+        query::Insert::new().blocking(self, documents)
     }
 
     /// Convenience method to insert a document in a collection.
@@ -313,13 +913,13 @@ impl Client {
     /// # Errors
     ///
     /// This method fails if the mongodb encountered an error, or if the found document is invalid.
-    pub fn insert_one<C>(&self, document: C) -> crate::Result<ObjectId>
+    pub fn insert_one<C>(&self, document: C) -> crate::Result<bson::Bson>
     where
         C: Collection,
     {
         // NOTE: We don't wanna make another builder so we just eat the cost of allocating a vec...
         let result = query::Insert::new().blocking(self, vec![document])?;
-        if let Some((_, bson::Bson::ObjectId(id))) = result.into_iter().next() {
+        if let Some((_, id)) = result.into_iter().next() {
             return Ok(id);
         }
         Err(crate::error::mongodb(
@@ -342,6 +942,64 @@ impl Client {
             .blocking(self, document)
     }
 
+    /// Convenience method to save a document in a collection.
+    ///
+    /// Documents without an `_id` are inserted and then given the inserted `_id`, while those with
+    /// an `_id` replace the stored document, or are inserted should it not exist.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if the document is invalid.
+    pub fn save<C>(&self, document: &mut C) -> crate::Result<bson::Bson>
+    where
+        C: Collection + Clone,
+    {
+        match document.id() {
+            Some(id) => {
+                query::Replace::<C>::new()
+                    .id(id.clone())
+                    .upsert(true)
+                    .blocking(self, document.clone())?;
+                Ok(id)
+            }
+            None => {
+                let result = query::Insert::new().blocking(self, vec![document.clone()])?;
+                match result.into_iter().next() {
+                    Some((_, id)) => {
+                        document.set_id(id.clone());
+                        Ok(id)
+                    }
+                    None => Err(crate::error::mongodb(
+                        "failed to insert document into mongo",
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Synchronises the indexes on the collection for the given type with its
+    /// [`indexes`](`Collection::indexes`).
+    ///
+    /// Missing indexes are created and those that are no longer declared are dropped, other than
+    /// the `_id` index. Indexes are matched by name, so an index whose options have changed must
+    /// also be renamed for it to be recreated.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn sync_indexes<C>(&self) -> crate::Result<()>
+    where
+        C: Collection,
+    {
+        let resp = self.execute(Request::SyncIndexes(C::collection(), C::indexes()))?;
+        if let Response::SyncIndexes = resp {
+            return Ok(());
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+
     /// Convenience method to update documents in a collection.
     ///
     /// # Errors
@@ -420,117 +1078,513 @@ impl Client {
         Ok(false)
     }
 
+    /// Convenience method to watch the changes made to a collection.
+    ///
+    /// The returned stream blocks on each iteration until the next change occurs. If you need to
+    /// filter the changes or resume watching, use the `Watch` querier.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the deployment is not a
+    /// replica set.
+    pub fn watch<C>(&self) -> crate::Result<ChangeStream<C>>
+    where
+        C: Collection,
+    {
+        query::Watch::<C>::new().blocking(self)
+    }
+
+    /// Pings the mongo deployment, checking that this client can talk to it.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the server is unreachable.
+    pub fn ping(&self) -> crate::Result<()> {
+        let resp = self.execute(Request::RunCommand(doc! { "ping": 1 }))?;
+        if let Response::RunCommand(_) = resp {
+            return Ok(());
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+
+    /// Shuts down the client, joining its runtime thread.
+    ///
+    /// Requests in flight are cancelled, after which all requests fail, including those made by
+    /// clones of this client. Unless the client was built with a `runtime`, its cursors and
+    /// streams are also cancelled. This is done automatically when the last clone of the client is
+    /// dropped.
+    pub fn shutdown(&self) {
+        self.inner.shutdown();
+    }
+
     pub(crate) fn execute(&self, req: Request) -> crate::Result<Response> {
         let (tx, rx) = std::sync::mpsc::channel();
         self.inner
-            .tx
+            .sender()?
             .send((req, tx))
             .map_err(|_| crate::error::runtime("failed to send request to blocking thread"))?;
-        rx.recv().map_err(crate::error::runtime)?
+        // NOTE: The streams keep making requests to the runtime, so they inherit the timeout of
+        // the request that opened them
+        Ok(match recv(&rx, self.timeout)?? {
+            Response::Aggregate(cursor) => Response::Aggregate(cursor.timeout(self.timeout)),
+            Response::Find(cursor) => Response::Find(cursor.timeout(self.timeout)),
+            Response::GridFsDownload(stream) => {
+                Response::GridFsDownload(stream.timeout(self.timeout))
+            }
+            Response::GridFsUpload(stream) => Response::GridFsUpload(stream.timeout(self.timeout)),
+            Response::Watch(stream) => Response::Watch(stream.timeout(self.timeout)),
+            resp => resp,
+        })
     }
 }
 
 impl ClientInner {
-    fn new(builder: ClientBuilder, client: Option<crate::r#async::Client>) -> crate::Result<Self> {
+    fn new(builder: ClientBuilder, client: Option<r#async::Client>) -> crate::Result<Self> {
+        let respawn = if builder.respawn {
+            Some((builder.clone(), client.clone()))
+        } else {
+            None
+        };
+        let (database, worker) = Worker::spawn(builder, client)?;
+        Ok(Self {
+            database,
+            respawn,
+            worker: Mutex::new(worker),
+        })
+    }
+
+    fn sender(&self) -> crate::Result<ThreadSender> {
+        let mut worker = lock(&self.worker);
+        // A closed channel without a shutdown means that the runtime died
+        if worker.tx.is_closed() && worker.shutdown.is_some() {
+            if let Some((builder, client)) = &self.respawn {
+                if let Some(handle) = worker.thread.take() {
+                    if handle.join().is_err() {
+                        error!("blocking runtime thread panicked");
+                    }
+                }
+                warn!("respawning blocking runtime");
+                *worker = Worker::spawn(builder.clone(), client.clone())?.1;
+            }
+        }
+        Ok(worker.tx.clone())
+    }
+
+    fn shutdown(&self) {
+        let mut worker = lock(&self.worker);
+        if let Some(tx) = worker.shutdown.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = worker.thread.take() {
+            // The client could be dropped by a task on its own runtime, which cannot join itself
+            if handle.thread().id() != thread::current().id() && handle.join().is_err() {
+                error!("blocking runtime thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for ClientInner {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl Worker {
+    fn spawn(
+        builder: ClientBuilder,
+        client: Option<r#async::Client>,
+    ) -> crate::Result<(String, Self)> {
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(Request, OneshotResponse)>();
-        let (spawn_tx, spawn_rx) = std::sync::mpsc::channel::<crate::Result<()>>();
-        let handle = thread::Builder::new()
-            .name("mongo-blocking-runtime".into())
-            .spawn(move || {
-                let rt = match tokio::runtime::Builder::new_current_thread()
-                    .enable_all()
-                    .build()
-                    .map_err(crate::error::builder)
-                {
-                    Ok(rt) => rt,
+        let (spawn_tx, spawn_rx) = std::sync::mpsc::channel::<crate::Result<String>>();
+        let runtime = builder.runtime.clone();
+        let rt_tx = spawn_tx.clone();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let shutdown_rx = shutdown_rx.shared();
+        let shutdown = shutdown_rx.clone();
+        let serve = async move {
+            let client = match client {
+                Some(client) => client,
+                None => match builder.builder.build().map_err(crate::error::builder) {
+                    Ok(client) => client,
                     Err(e) => {
                         if let Err(e) = spawn_tx.send(Err(e)) {
-                            error!("failed to communicate runtime builder: {:?}", e);
+                            error!("failed to create async client: {:?}", e);
                         }
                         return;
                     }
-                };
-                let f = async move {
-                    let client = match client {
-                        Some(client) => client,
-                        None => match builder.builder.build().map_err(crate::error::builder) {
-                            Ok(client) => client,
+                },
+            };
+            let database = client.database();
+            if let Err(e) = spawn_tx.send(Ok(database.name().to_owned())) {
+                error!("failed to communicate successful startup: {:?}", e);
+                return;
+            }
+            while let Some((req, req_tx)) = rx.recv().await {
+                // NOTE: Each request is served by a task of its own, so that one which hangs does
+                // not hold up the others, while still being cancelled on shutdown
+                let f = Self::serve(client.clone(), database.clone(), req);
+                let shutdown = shutdown.clone();
+                tokio::spawn(async move {
+                    if let Either::Left((resp, _)) = select(Box::pin(f), shutdown).await {
+                        let _ = req_tx.send(resp);
+                    }
+                });
+            }
+        };
+        // Stop serving on shutdown, cancelling the requests in flight
+        let f = async move {
+            select(Box::pin(serve), shutdown_rx).await;
+        };
+        let handle = match runtime {
+            Some(runtime) => {
+                runtime.spawn(f);
+                None
+            }
+            None => Some(
+                thread::Builder::new()
+                    .name("mongo-blocking-runtime".into())
+                    .spawn(move || {
+                        let rt = match tokio::runtime::Builder::new_current_thread()
+                            .enable_all()
+                            .build()
+                            .map_err(crate::error::builder)
+                        {
+                            Ok(rt) => rt,
                             Err(e) => {
-                                if let Err(e) = spawn_tx.send(Err(e)) {
-                                    error!("failed to create async client: {:?}", e);
+                                if let Err(e) = rt_tx.send(Err(e)) {
+                                    error!("failed to communicate runtime builder: {:?}", e);
                                 }
                                 return;
                             }
-                        },
-                    };
-                    if let Err(e) = spawn_tx.send(Ok(())) {
-                        error!("failed to communicate successful startup: {:?}", e);
-                        return;
-                    }
-                    let database = client.database();
-                    while let Some((req, req_tx)) = rx.recv().await {
-                        let resp = match req {
-                            Request::Delete(many, collection, filter, options) => if many {
-                                database
-                                    .collection::<Document>(collection)
-                                    .delete_many(filter, options)
-                                    .await
-                            } else {
-                                database
-                                    .collection::<Document>(collection)
-                                    .delete_one(filter, options)
-                                    .await
-                            }
-                            .map(Response::Delete)
-                            .map_err(crate::error::mongodb),
-                            Request::Find(collection, filter, options) => {
-                                match database.collection(collection).find(filter, options).await {
-                                    Ok(c) => Ok(Response::Find(Cursor::new(c))),
-                                    Err(e) => Err(crate::error::mongodb(e)),
-                                }
-                            }
-                            Request::Insert(collection, documents, options) => database
-                                .collection(collection)
-                                .insert_many(documents, options)
-                                .await
-                                .map(Response::Insert)
-                                .map_err(crate::error::mongodb),
-                            Request::Replace(collection, filter, documents, options) => database
-                                .collection(collection)
-                                .replace_one(filter, documents, options)
-                                .await
-                                .map(Response::Replace)
-                                .map_err(crate::error::mongodb),
-                            Request::Update(many, collection, filter, updates, options) => {
-                                if many {
-                                    database
-                                        .collection::<Document>(collection)
-                                        .update_many(filter, updates, options)
-                                        .await
-                                } else {
-                                    database
-                                        .collection::<Document>(collection)
-                                        .update_one(filter, updates, options)
-                                        .await
-                                }
-                                .map(Response::Update)
-                                .map_err(crate::error::mongodb)
-                            }
                         };
-                        let _ = req_tx.send(resp);
-                    }
-                };
-                rt.block_on(f);
-            })
-            .map_err(crate::error::builder)?;
+                        rt.block_on(f);
+                    })
+                    .map_err(crate::error::builder)?,
+            ),
+        };
 
-        if let Err(e) = spawn_rx.recv().map_err(crate::error::builder)? {
-            return Err(e);
+        let database = spawn_rx.recv().map_err(crate::error::builder)??;
+
+        Ok((
+            database,
+            Self {
+                shutdown: Some(shutdown_tx),
+                thread: handle,
+                tx,
+            },
+        ))
+    }
+
+    async fn serve(
+        client: r#async::Client,
+        database: mongodb::Database,
+        req: Request,
+    ) -> crate::Result<Response> {
+        match req {
+            Request::Aggregate(collection, pipeline, options) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .aggregate(pipeline, options),
+                )
+                .await
+                .map(|c| Response::Aggregate(Cursor::new(c))),
+            Request::BulkWrite(collection, models, options) => {
+                query::bulk_write(&client, collection, models, options)
+                    .await
+                    .map(Response::BulkWrite)
+            }
+            Request::CreateCollection(name, options, indexes) => client
+                .create_collection_with(name, options, indexes)
+                .await
+                .map(|_| Response::CreateCollection),
+            Request::CreateIndexes(collection, indexes) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .create_indexes(indexes, None),
+                )
+                .await
+                .map(|r| Response::CreateIndexes(r.index_names)),
+            Request::Delete(many, collection, filter, options) => {
+                let collection = database.collection::<Document>(&collection);
+                if many {
+                    client
+                        .with_timeout(collection.delete_many(filter, options))
+                        .await
+                } else {
+                    client
+                        .with_timeout(collection.delete_one(filter, options))
+                        .await
+                }
+                .map(Response::Delete)
+            }
+            Request::DropCollection(collection) => client
+                .with_timeout(database.collection::<Document>(&collection).drop(None))
+                .await
+                .map(|_| Response::DropCollection),
+            Request::DropDatabase => client
+                .with_timeout(database.drop(None))
+                .await
+                .map(|_| Response::DropDatabase),
+            Request::DropIndex(collection, index) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .drop_index(index, None),
+                )
+                .await
+                .map(|_| Response::DropIndex),
+            Request::Find(collection, filter, options) => client
+                .with_timeout(database.collection(&collection).find(filter, options))
+                .await
+                .map(|c| Response::Find(Cursor::new(c))),
+            Request::FindOneAndDelete(collection, filter, options) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .find_one_and_delete(filter, options),
+                )
+                .await
+                .map(Response::FindOneAndDelete),
+            Request::FindOneAndReplace(collection, filter, document, options) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .find_one_and_replace(filter, document, options),
+                )
+                .await
+                .map(Response::FindOneAndReplace),
+            Request::FindOneAndUpdate(collection, filter, updates, options) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .find_one_and_update(filter, updates, options),
+                )
+                .await
+                .map(Response::FindOneAndUpdate),
+            Request::GridFsDelete(options, id) => client
+                .with_timeout(database.gridfs_bucket(options).delete(id))
+                .await
+                .map(|_| Response::GridFsDelete),
+            Request::GridFsDownload(options, id) => client
+                .with_timeout(database.gridfs_bucket(options).open_download_stream(id))
+                .await
+                .map(|s| Response::GridFsDownload(DownloadStream::new(s))),
+            Request::GridFsUpload(options, filename, upload_options) => {
+                let stream = database
+                    .gridfs_bucket(options)
+                    .open_upload_stream(filename, upload_options);
+                Ok(Response::GridFsUpload(UploadStream::new(stream)))
+            }
+            Request::Insert(collection, documents, options) => client
+                .with_timeout(
+                    database
+                        .collection(&collection)
+                        .insert_many(documents, options),
+                )
+                .await
+                .map(Response::Insert),
+            Request::ListCollectionNames(filter) => client
+                .with_timeout(database.list_collection_names(filter))
+                .await
+                .map(Response::ListCollectionNames),
+            Request::ListIndexNames(collection) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .list_index_names(),
+                )
+                .await
+                .map(Response::ListIndexNames),
+            Request::Replace(collection, filter, documents, options) => client
+                .with_timeout(
+                    database
+                        .collection(&collection)
+                        .replace_one(filter, documents, options),
+                )
+                .await
+                .map(Response::Replace),
+            Request::RunCommand(command) => client
+                .with_timeout(database.run_command(command, None))
+                .await
+                .map(Response::RunCommand),
+            Request::SyncIndexes(collection, indexes) => client
+                .sync_indexes_with(collection, indexes)
+                .await
+                .map(|_| Response::SyncIndexes),
+            Request::Update(many, collection, filter, updates, options) => {
+                let collection = database.collection::<Document>(&collection);
+                if many {
+                    client
+                        .with_timeout(collection.update_many(filter, updates, options))
+                        .await
+                } else {
+                    client
+                        .with_timeout(collection.update_one(filter, updates, options))
+                        .await
+                }
+                .map(Response::Update)
+            }
+            Request::Watch(collection, pipeline, options) => client
+                .with_timeout(
+                    database
+                        .collection::<Document>(&collection)
+                        .watch(pipeline, options),
+                )
+                .await
+                .map(|s| Response::Watch(RawChangeStream::new(s))),
         }
+    }
+}
 
-        Ok(Self {
-            _thread: Some(handle),
-            tx,
-        })
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Waits for the response to a request sent to the runtime, failing with a timeout error should
+// it not arrive in time.
+pub(crate) fn recv<T>(
+    rx: &std::sync::mpsc::Receiver<T>,
+    timeout: Option<Duration>,
+) -> crate::Result<T> {
+    match timeout {
+        Some(timeout) => rx.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => crate::error::timeout(e),
+            RecvTimeoutError::Disconnected => crate::error::runtime(e),
+        }),
+        None => rx.recv().map_err(crate::error::runtime),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_runtime() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let handle = rt.handle().clone();
+        thread::spawn(move || rt.block_on(futures::future::pending::<()>()));
+
+        let a = Client::builder()
+            .database("a")
+            .runtime(handle.clone())
+            .build()
+            .unwrap();
+        let b = Client::builder()
+            .database("b")
+            .runtime(handle)
+            .build()
+            .unwrap();
+        assert!(lock(&a.inner.worker).thread.is_none());
+        assert_eq!(a.database().name(), "a");
+        assert_eq!(b.database().name(), "b");
+    }
+
+    #[test]
+    fn request_timeout() {
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1")
+            .build()
+            .unwrap();
+        let err = client
+            .with_timeout(Duration::from_millis(50))
+            .database()
+            .list_collection_names(None)
+            .unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Timeout));
+    }
+
+    #[test]
+    fn concurrent_requests() {
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1/?serverSelectionTimeoutMS=2000")
+            .build()
+            .unwrap();
+        let hung = client.clone();
+        let handle = thread::spawn(move || hung.ping());
+        // NOTE: Give the hanging request time to reach the runtime first
+        thread::sleep(Duration::from_millis(100));
+        let stream = client
+            .with_timeout(Duration::from_millis(500))
+            .database()
+            .gridfs_bucket(None)
+            .open_upload_stream("foo", None);
+        assert!(stream.is_ok());
+        assert!(handle.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn stream_timeout() {
+        use std::io::Write;
+
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1")
+            .build()
+            .unwrap();
+        let mut stream = client
+            .with_timeout(Duration::from_millis(200))
+            .database()
+            .gridfs_bucket(None)
+            .open_upload_stream("foo", None)
+            .unwrap();
+        // NOTE: Writing a whole chunk makes the stream insert it, which hangs without a server
+        let err = stream.write_all(&[0; 256 * 1024]).unwrap_err();
+        let err = err
+            .into_inner()
+            .unwrap()
+            .downcast::<crate::Error>()
+            .unwrap();
+        assert!(matches!(err.kind(), crate::ErrorKind::Timeout));
+    }
+
+    #[test]
+    fn shutdown() {
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1")
+            .build()
+            .unwrap();
+        client.shutdown();
+        assert!(lock(&client.inner.worker).thread.is_none());
+        let err = client.database().list_collection_names(None).unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Runtime));
+    }
+
+    #[test]
+    fn respawn() {
+        fn kill(client: &Client) {
+            let mut worker = lock(&client.inner.worker);
+            let (tx, _) = oneshot::channel();
+            let _ = worker.shutdown.replace(tx).unwrap().send(());
+            worker.thread.take().unwrap().join().unwrap();
+        }
+
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1")
+            .build()
+            .unwrap();
+        kill(&client);
+        let err = client.ping().unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Runtime));
+
+        let client = Client::builder()
+            .uri("mongodb://127.0.0.1:1")
+            .respawn(true)
+            .build()
+            .unwrap();
+        kill(&client);
+        let err = client
+            .with_timeout(Duration::from_millis(50))
+            .ping()
+            .unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Timeout));
+        assert!(lock(&client.inner.worker).thread.is_some());
     }
 }
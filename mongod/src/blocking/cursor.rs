@@ -1,23 +1,43 @@
+use std::collections::VecDeque;
 use std::marker::PhantomData;
+use std::time::Duration;
 
-use bson::{oid::ObjectId, Document};
+use bson::document::ValueAccessError;
+use bson::{Bson, Document};
+use futures::future::FutureExt;
 use futures::stream::StreamExt;
+use serde::de::DeserializeOwned;
 
+use super::client::recv;
 use crate::collection::Collection;
+use crate::view::View;
+
+// The default number of documents fetched from the runtime in a single round trip.
+const FETCH_SIZE: usize = 128;
 
 #[derive(Debug)]
 enum Request {
-    Next,
+    Next(usize),
 }
 enum Response {
-    Next(Option<crate::Result<Document>>),
+    Next(Vec<crate::Result<Document>>),
 }
 
 /// A blocking version of the [`mongodb::Cursor`](https://docs.rs/mongodb/2.0.0/mongodb/struct.Cursor.html).
 ///
 /// This wraps the async `Cursor` so that is can be called in a synchronous fashion, please see the
 /// asynchronous description for more information about the cursor.
+///
+/// Documents are fetched from the runtime in batches, draining those already buffered by the
+/// async `Cursor`, so that iterating does not need a round trip per document. Dropping the
+/// `Cursor` closes the async `Cursor`, and should the client be shut down iteration ends with an
+/// error. Fetching a batch is subject to the timeout of the client that opened the `Cursor`, a
+/// batch that timed out is returned by the next call instead.
 pub struct Cursor {
+    buffer: VecDeque<crate::Result<Document>>,
+    exhausted: bool,
+    fetch_size: usize,
+    timeout: Option<Duration>,
     tx: tokio::sync::mpsc::UnboundedSender<(Request, std::sync::mpsc::Sender<Response>)>,
 }
 
@@ -27,35 +47,130 @@ impl Cursor {
             tokio::sync::mpsc::unbounded_channel::<(Request, std::sync::mpsc::Sender<Response>)>();
         let f = async move {
             let mut cursor = cursor;
+            let mut pending = None;
             while let Some((req, tx)) = rx.recv().await {
-                match req {
-                    Request::Next => {
-                        let resp = cursor
-                            .next()
-                            .await
-                            .map(|n| n.map_err(crate::error::mongodb));
-                        let _ = tx.send(Response::Next(resp));
+                let resp = match (pending.take(), req) {
+                    (Some(resp), _) => resp,
+                    (None, Request::Next(size)) => {
+                        let mut batch = Vec::new();
+                        if let Some(n) = cursor.next().await {
+                            batch.push(n.map_err(crate::error::mongodb));
+                            // Only take the documents that are ready, not waiting on a getMore
+                            while batch.len() < size {
+                                match cursor.next().now_or_never() {
+                                    Some(Some(Ok(n))) => batch.push(Ok(n)),
+                                    Some(Some(Err(e))) => {
+                                        batch.push(Err(crate::error::mongodb(e)));
+                                        break;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                        }
+                        Response::Next(batch)
                     }
                 };
+                // NOTE: Should the request have timed out the batch is kept for the next one
+                if let Err(e) = tx.send(resp) {
+                    pending = Some(e.0);
+                }
             }
         };
         tokio::spawn(f);
-        Self { tx }
+        Self {
+            buffer: VecDeque::new(),
+            exhausted: false,
+            fetch_size: FETCH_SIZE,
+            timeout: None,
+            tx,
+        }
+    }
+
+    pub(crate) fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of documents fetched from the runtime in a single round trip.
+    ///
+    /// This does not change the number of documents the server returns per batch, which is set
+    /// using the querier's `batch_size`.
+    pub fn fetch_size(mut self, size: usize) -> Self {
+        self.fetch_size = size.max(1);
+        self
+    }
+
+    /// Maps this cursor into one that deserializes its documents into `T`.
+    ///
+    /// This allows documents that do not belong to a `Collection`, such as aggregation results or
+    /// projections, to be decoded into ad-hoc types.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// #[derive(Debug, Bson, Mongo)]
+    /// #[mongo(collection="users", field, filter, update)]
+    /// pub struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Count {
+    ///     #[serde(rename = "_id")]
+    ///     name: String,
+    ///     count: i64,
+    /// }
+    ///
+    /// # fn doc() -> Result<(), mongod::Error> {
+    /// let client = mongod::blocking::Client::new();
+    ///
+    /// let counts = mongod::query::Aggregate::<User>::new()
+    ///     .stage(mongod::bson::doc! { "$group": { "_id": "$name", "count": { "$sum": 1 } } })
+    ///     .blocking(&client)?
+    ///     .map_into::<Count>();
+    /// for res in counts {
+    ///     println!("{:?}", res?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn map_into<T>(self) -> DeserializeCursor<T>
+    where
+        T: DeserializeOwned,
+    {
+        DeserializeCursor {
+            cursor: self,
+            document_type: PhantomData,
+        }
     }
 }
 
 impl Iterator for Cursor {
     type Item = crate::Result<Document>;
     fn next(&mut self) -> Option<Self::Item> {
-        let (tx, rx) = std::sync::mpsc::channel();
-        self.tx
-            .send((Request::Next, tx))
-            .expect("core thread panicked");
-        let res = rx
-            .recv()
-            .expect("could not get response from mongo runtime");
-        let Response::Next(c) = res;
-        c
+        if self.buffer.is_empty() && !self.exhausted {
+            let (tx, rx) = std::sync::mpsc::channel();
+            if self.tx.send((Request::Next(self.fetch_size), tx)).is_err() {
+                self.exhausted = true;
+                return Some(Err(crate::error::runtime(
+                    "failed to send request to blocking thread",
+                )));
+            }
+            match recv(&rx, self.timeout) {
+                Ok(Response::Next(batch)) => {
+                    self.exhausted = batch.is_empty();
+                    self.buffer.extend(batch);
+                }
+                Err(e) => {
+                    self.exhausted = !matches!(e.kind(), crate::ErrorKind::Timeout);
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.buffer.pop_front()
     }
 }
 
@@ -78,6 +193,56 @@ where
     pub fn into_inner(self) -> Cursor {
         self.cursor
     }
+
+    /// Sets the maximum number of documents fetched from the runtime in a single round trip.
+    pub fn fetch_size(mut self, size: usize) -> Self {
+        self.cursor = self.cursor.fetch_size(size);
+        self
+    }
+
+    /// Returns an iterator over the documents of this cursor in chunks of `size`.
+    ///
+    /// Each chunk is fetched from the runtime in a single round trip where possible, so setting the
+    /// querier's `batch_size` to the same size also aligns the chunks with the batches returned by
+    /// the server. The last chunk may be shorter, and should an error occur the documents read
+    /// before it are returned first.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// #[derive(Debug, Bson, Mongo)]
+    /// #[mongo(collection="users", field, filter, update)]
+    /// pub struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// # fn doc() -> Result<(), mongod::Error> {
+    /// let client = mongod::blocking::Client::new();
+    ///
+    /// let cursor = mongod::query::Find::<User>::new()
+    ///     .batch_size(100)
+    ///     .blocking(&client)?;
+    /// for chunk in cursor.chunks(100) {
+    ///     println!("{} users", chunk?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub fn chunks(self, size: usize) -> Chunks<T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            cursor: self.fetch_size(size),
+            error: None,
+            size,
+        }
+    }
 }
 
 impl<T> From<Cursor> for TypedCursor<T>
@@ -96,14 +261,235 @@ impl<T> Iterator for TypedCursor<T>
 where
     T: Collection,
 {
-    type Item = crate::Result<(ObjectId, T)>;
+    type Item = crate::Result<(Bson, T)>;
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.cursor.next();
 
         next.map(|res| {
             let doc = res?;
-            let oid = doc.get_object_id("_id").map_err(crate::error::bson)?;
-            Ok((oid, T::from_document(doc)?))
+            let id = doc
+                .get("_id")
+                .cloned()
+                .ok_or(ValueAccessError::NotPresent)
+                .map_err(crate::error::bson)?;
+            Ok((id, T::from_document(doc)?))
         })
     }
 }
+
+/// An iterator over the documents of a `TypedCursor` in chunks, see `TypedCursor::chunks`.
+pub struct Chunks<T>
+where
+    T: Collection,
+{
+    cursor: TypedCursor<T>,
+    error: Option<crate::Error>,
+    size: usize,
+}
+
+impl<T> Iterator for Chunks<T>
+where
+    T: Collection,
+{
+    type Item = crate::Result<Vec<(Bson, T)>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+        let mut chunk = Vec::with_capacity(self.size);
+        while chunk.len() < self.size {
+            match self.cursor.next() {
+                Some(Ok(item)) => chunk.push(item),
+                Some(Err(e)) if chunk.is_empty() => return Some(Err(e)),
+                Some(Err(e)) => {
+                    self.error = Some(e);
+                    break;
+                }
+                None => break,
+            }
+        }
+        if chunk.is_empty() {
+            return None;
+        }
+        Some(Ok(chunk))
+    }
+}
+
+/// A blocking cursor of deserialized documents.
+///
+/// This wraps the blocking `Cursor` so that it can automatically deserialize documents into any
+/// type, see `Cursor::map_into`.
+pub struct DeserializeCursor<T>
+where
+    T: DeserializeOwned,
+{
+    cursor: Cursor,
+    document_type: PhantomData<T>,
+}
+
+impl<T> DeserializeCursor<T>
+where
+    T: DeserializeOwned,
+{
+    /// Allow access to the wrapped blocking `Cursor`
+    pub fn into_inner(self) -> Cursor {
+        self.cursor
+    }
+
+    /// Sets the maximum number of documents fetched from the runtime in a single round trip.
+    pub fn fetch_size(mut self, size: usize) -> Self {
+        self.cursor = self.cursor.fetch_size(size);
+        self
+    }
+}
+
+impl<T> Iterator for DeserializeCursor<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = crate::Result<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor
+            .next()
+            .map(|res| bson::from_document(res?).map_err(crate::error::bson))
+    }
+}
+
+/// A blocking cursor of views.
+///
+/// This wraps the blocking `Cursor` so that is can automatically return projected views of
+/// documents.
+pub struct ViewCursor<V>
+where
+    V: View,
+{
+    cursor: Cursor,
+    view_type: PhantomData<V>,
+}
+
+impl<V> ViewCursor<V>
+where
+    V: View,
+{
+    /// Allow access to the wrapped blocking `Cursor`
+    pub fn into_inner(self) -> Cursor {
+        self.cursor
+    }
+
+    /// Sets the maximum number of documents fetched from the runtime in a single round trip.
+    pub fn fetch_size(mut self, size: usize) -> Self {
+        self.cursor = self.cursor.fetch_size(size);
+        self
+    }
+}
+
+impl<V> From<Cursor> for ViewCursor<V>
+where
+    V: View,
+{
+    fn from(cursor: Cursor) -> Self {
+        ViewCursor {
+            cursor,
+            view_type: PhantomData,
+        }
+    }
+}
+
+impl<V> Iterator for ViewCursor<V>
+where
+    V: View,
+{
+    type Item = crate::Result<V>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|res| V::from_document(res?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::User;
+
+    fn cursor(documents: Vec<crate::Result<Document>>) -> Cursor {
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        Cursor {
+            buffer: documents.into(),
+            exhausted: true,
+            fetch_size: FETCH_SIZE,
+            timeout: None,
+            tx,
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let (tx, mut rx) =
+            tokio::sync::mpsc::unbounded_channel::<(Request, std::sync::mpsc::Sender<Response>)>();
+        let mut cursor = Cursor {
+            buffer: VecDeque::new(),
+            exhausted: false,
+            fetch_size: FETCH_SIZE,
+            timeout: Some(std::time::Duration::from_millis(10)),
+            tx,
+        };
+        let err = cursor.next().unwrap().unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Timeout));
+
+        let (_, tx) = rx.try_recv().unwrap();
+        tx.send(Response::Next(vec![Ok(doc! { "name": "a" })]))
+            .unwrap_err();
+        drop(rx);
+        let err = cursor.next().unwrap().unwrap_err();
+        assert!(matches!(err.kind(), crate::ErrorKind::Runtime));
+        assert!(cursor.next().is_none());
+    }
+
+    #[test]
+    fn chunks() {
+        let documents = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| Ok(doc! { "_id": bson::oid::ObjectId::new(), "name": *name }))
+            .chain(vec![Err(crate::error::runtime("foo"))])
+            .collect();
+        let mut chunks = TypedCursor::<User>::from(cursor(documents)).chunks(2);
+        let mut names = || -> crate::Result<Vec<String>> {
+            Ok(chunks
+                .next()
+                .unwrap()?
+                .into_iter()
+                .map(|(_, u)| u.name)
+                .collect())
+        };
+        assert_eq!(names().unwrap(), vec!["a", "b"]);
+        assert_eq!(names().unwrap(), vec!["c", "d"]);
+        assert_eq!(names().unwrap(), vec!["e"]);
+        assert!(names().is_err());
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn map_into() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Count {
+            #[serde(rename = "_id")]
+            name: String,
+            count: i64,
+        }
+
+        let mut counts = cursor(vec![
+            Ok(doc! { "_id": "foo", "count": 2_i64 }),
+            Ok(doc! { "_id": "bar" }),
+        ])
+        .map_into::<Count>();
+        assert_eq!(
+            counts.next().unwrap().unwrap(),
+            Count {
+                name: "foo".to_owned(),
+                count: 2,
+            }
+        );
+        assert!(counts.next().unwrap().is_err());
+        assert!(counts.next().is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use bson::Document;
+use mongodb::options::GridFsBucketOptions;
+use mongodb::IndexModel;
+
+use super::client::{Client, Request, Response};
+use super::gridfs::Bucket;
+
+/// A blocking handle to the database used by a `Client`.
+///
+/// This is the blocking equivalent of the `mongodb::Database` returned by the async `Client`, its
+/// operations are run on the client's runtime thread.
+#[derive(Clone)]
+pub struct Database {
+    client: Client,
+    name: String,
+}
+
+impl Database {
+    pub(crate) fn new(client: Client, name: String) -> Self {
+        Self { client, name }
+    }
+
+    /// Returns the name of the database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a handle to the collection for the given type.
+    pub fn collection<C>(&self) -> Collection
+    where
+        C: crate::Collection,
+    {
+        Collection {
+            client: self.client.clone(),
+            name: C::collection(),
+        }
+    }
+
+    /// Drops the database, deleting all of its collections.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn drop(&self) -> crate::Result<()> {
+        match self.client.execute(Request::DropDatabase)? {
+            Response::DropDatabase => Ok(()),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Returns a handle to a GridFS bucket in the database.
+    ///
+    /// When no options are given the default `fs` bucket is used.
+    pub fn gridfs_bucket(&self, options: Option<GridFsBucketOptions>) -> Bucket {
+        Bucket::new(self.client.clone(), options.unwrap_or_default())
+    }
+
+    /// Returns the names of the collections in the database, optionally matching a filter.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn list_collection_names(&self, filter: Option<Document>) -> crate::Result<Vec<String>> {
+        match self.client.execute(Request::ListCollectionNames(filter))? {
+            Response::ListCollectionNames(names) => Ok(names),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Runs a database command, returning the server's reply.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the command failed.
+    pub fn run_command(&self, command: Document) -> crate::Result<Document> {
+        match self.client.execute(Request::RunCommand(command))? {
+            Response::RunCommand(reply) => Ok(reply),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+}
+
+/// A blocking handle to a collection in the database used by a `Client`.
+///
+/// This is the blocking equivalent of the `mongodb::Collection` returned by the async `Client`,
+/// intended for administrative tasks. Documents are queried using the `Client` itself.
+#[derive(Clone)]
+pub struct Collection {
+    client: Client,
+    name: String,
+}
+
+impl Collection {
+    /// Returns the name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates the given index on the collection, returning its name.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn create_index(&self, index: IndexModel) -> crate::Result<String> {
+        self.create_indexes(vec![index])?
+            .pop()
+            .ok_or_else(|| crate::error::mongodb("failed to create index"))
+    }
+
+    /// Creates the given indexes on the collection, returning their names.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn create_indexes(&self, indexes: Vec<IndexModel>) -> crate::Result<Vec<String>> {
+        match self
+            .client
+            .execute(Request::CreateIndexes(self.name.clone(), indexes))?
+        {
+            Response::CreateIndexes(names) => Ok(names),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Drops the collection, deleting all of its documents and indexes.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn drop(&self) -> crate::Result<()> {
+        match self
+            .client
+            .execute(Request::DropCollection(self.name.clone()))?
+        {
+            Response::DropCollection => Ok(()),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Drops the named index from the collection.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the index does not exist.
+    pub fn drop_index(&self, name: &str) -> crate::Result<()> {
+        match self
+            .client
+            .execute(Request::DropIndex(self.name.clone(), name.to_owned()))?
+        {
+            Response::DropIndex => Ok(()),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Returns the names of the indexes on the collection.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn list_index_names(&self) -> crate::Result<Vec<String>> {
+        match self
+            .client
+            .execute(Request::ListIndexNames(self.name.clone()))?
+        {
+            Response::ListIndexNames(names) => Ok(names),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+}
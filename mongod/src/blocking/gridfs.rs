@@ -0,0 +1,277 @@
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use bson::Bson;
+use futures::io::{AsyncReadExt, AsyncWriteExt};
+use mongodb::gridfs::{GridFsDownloadStream, GridFsUploadStream};
+use mongodb::options::{GridFsBucketOptions, GridFsUploadOptions};
+
+use super::client::{recv, Client, Request, Response};
+
+enum UploadRequest {
+    Write(Vec<u8>),
+    Close,
+}
+type UploadMessage = (UploadRequest, std::sync::mpsc::Sender<crate::Result<()>>);
+type DownloadMessage = (usize, std::sync::mpsc::Sender<crate::Result<Vec<u8>>>);
+
+/// A blocking handle to a GridFS bucket in the database used by a `Client`.
+///
+/// GridFS stores files that exceed the BSON document size limit by splitting them into chunks,
+/// the files are streamed to and from the bucket using `std::io::Read` and `std::io::Write`. Each
+/// read and write is subject to the timeout of the `Client`.
+#[derive(Clone)]
+pub struct Bucket {
+    client: Client,
+    options: GridFsBucketOptions,
+}
+
+impl Bucket {
+    pub(crate) fn new(client: Client, options: GridFsBucketOptions) -> Self {
+        Self { client, options }
+    }
+
+    /// Deletes the file with the given `id`, along with its chunks.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the file does not exist.
+    pub fn delete(&self, id: Bson) -> crate::Result<()> {
+        match self
+            .client
+            .execute(Request::GridFsDelete(self.options.clone(), id))?
+        {
+            Response::GridFsDelete => Ok(()),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Downloads the file with the given `id` into the writer, returning the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if the writer fails.
+    pub fn download_to_writer<W: Write>(&self, id: Bson, mut writer: W) -> crate::Result<u64> {
+        let mut stream = self.open_download_stream(id)?;
+        io::copy(&mut stream, &mut writer).map_err(io_error)
+    }
+
+    /// Opens a stream to read the contents of the file with the given `id`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. the file does not exist.
+    pub fn open_download_stream(&self, id: Bson) -> crate::Result<DownloadStream> {
+        match self
+            .client
+            .execute(Request::GridFsDownload(self.options.clone(), id))?
+        {
+            Response::GridFsDownload(stream) => Ok(stream),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Opens a stream to write the contents of a new file to.
+    ///
+    /// The file is only stored once the stream has been closed, dropping the stream aborts the
+    /// upload.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the runtime thread is unavailable.
+    pub fn open_upload_stream(
+        &self,
+        filename: &str,
+        options: Option<GridFsUploadOptions>,
+    ) -> crate::Result<UploadStream> {
+        match self.client.execute(Request::GridFsUpload(
+            self.options.clone(),
+            filename.to_owned(),
+            options,
+        ))? {
+            Response::GridFsUpload(stream) => Ok(stream),
+            _ => Err(crate::error::runtime(
+                "incorrect response from blocking client",
+            )),
+        }
+    }
+
+    /// Uploads the contents of the reader as a new file, returning its `id`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, or if the reader fails.
+    pub fn upload_from_reader<R: Read>(
+        &self,
+        filename: &str,
+        mut reader: R,
+        options: Option<GridFsUploadOptions>,
+    ) -> crate::Result<Bson> {
+        let mut stream = self.open_upload_stream(filename, options)?;
+        io::copy(&mut reader, &mut stream).map_err(io_error)?;
+        let id = stream.id().clone();
+        stream.close()?;
+        Ok(id)
+    }
+}
+
+/// Unwraps the `Error` returned by a stream from the `io::Error` it was wrapped in.
+fn io_error(e: io::Error) -> crate::Error {
+    if !e.get_ref().is_some_and(|e| e.is::<crate::Error>()) {
+        return crate::error::io(e);
+    }
+    *e.into_inner()
+        .and_then(|e| e.downcast().ok())
+        .expect("wrapped error is an `Error`")
+}
+
+/// A blocking stream to read a file from a GridFS `Bucket`.
+///
+/// A chunk that timed out is returned by the next read instead.
+pub struct DownloadStream {
+    timeout: Option<Duration>,
+    tx: tokio::sync::mpsc::UnboundedSender<DownloadMessage>,
+}
+
+impl DownloadStream {
+    pub(crate) fn new(stream: GridFsDownloadStream) -> Self {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<DownloadMessage>();
+        let f = async move {
+            let mut stream = stream;
+            let mut pending: Option<crate::Result<Vec<u8>>> = None;
+            while let Some((len, tx)) = rx.recv().await {
+                let resp = match pending.take() {
+                    Some(Ok(mut chunk)) => {
+                        if chunk.len() > len {
+                            pending = Some(Ok(chunk.split_off(len)));
+                        }
+                        Ok(chunk)
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => {
+                        let mut buf = vec![0; len];
+                        stream
+                            .read(&mut buf)
+                            .await
+                            .map(|n| {
+                                buf.truncate(n);
+                                buf
+                            })
+                            .map_err(crate::error::mongodb)
+                    }
+                };
+                // NOTE: Should the request have timed out the chunk is kept for the next one
+                if let Err(e) = tx.send(resp) {
+                    pending = Some(e.0);
+                }
+            }
+        };
+        tokio::spawn(f);
+        Self { timeout: None, tx }
+    }
+
+    pub(crate) fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Read for DownloadStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.tx
+            .send((buf.len(), tx))
+            .map_err(|_| io::Error::other("failed to send request to blocking thread"))?;
+        let chunk = recv(&rx, self.timeout)
+            .map_err(io::Error::other)?
+            .map_err(io::Error::other)?;
+        buf[..chunk.len()].copy_from_slice(&chunk);
+        Ok(chunk.len())
+    }
+}
+
+/// A blocking stream to write a file to a GridFS `Bucket`.
+///
+/// A write that timed out is still completed, should it fail the error is returned by the next
+/// write instead.
+pub struct UploadStream {
+    id: Bson,
+    timeout: Option<Duration>,
+    tx: tokio::sync::mpsc::UnboundedSender<UploadMessage>,
+}
+
+impl UploadStream {
+    pub(crate) fn new(stream: GridFsUploadStream) -> Self {
+        let id = stream.id().clone();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<UploadMessage>();
+        let f = async move {
+            let mut stream = stream;
+            let mut pending = None;
+            while let Some((req, tx)) = rx.recv().await {
+                let resp = match (pending.take(), req) {
+                    (Some(e), _) => Err(e),
+                    (None, UploadRequest::Write(buf)) => {
+                        stream.write_all(&buf).await.map_err(crate::error::mongodb)
+                    }
+                    (None, UploadRequest::Close) => {
+                        stream.close().await.map_err(crate::error::mongodb)
+                    }
+                };
+                // NOTE: Should the request have timed out its error is kept for the next one
+                if let Err(std::sync::mpsc::SendError(Err(e))) = tx.send(resp) {
+                    pending = Some(e);
+                }
+            }
+        };
+        tokio::spawn(f);
+        Self {
+            id,
+            timeout: None,
+            tx,
+        }
+    }
+
+    pub(crate) fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the `id` of the file being uploaded.
+    pub fn id(&self) -> &Bson {
+        &self.id
+    }
+
+    /// Closes the stream, storing the file in the bucket.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub fn close(self) -> crate::Result<()> {
+        self.send(UploadRequest::Close)
+    }
+
+    fn send(&self, req: UploadRequest) -> crate::Result<()> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.tx
+            .send((req, tx))
+            .map_err(|_| crate::error::runtime("failed to send request to blocking thread"))?;
+        recv(&rx, self.timeout)?
+    }
+}
+
+impl Write for UploadStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.send(UploadRequest::Write(buf.to_vec()))
+            .map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@
 //! example to fetch users from a collection.
 //!
 //! ```no_run
+//! # mod wrapper {
 //! # use mongod_derive::{Bson, Mongo};
 //! # #[derive(Debug, Bson, Mongo)]
 //! # #[mongo(collection="users", field, filter, update)]
@@ -23,6 +24,7 @@
 //! # }
 //! use mongod::Collection;
 //!
+//! # fn doc() {
 //! let client = mongod::blocking::Client::new();
 //!
 //! let mut cursor = client.find::<User, _>(None).unwrap();
@@ -31,11 +33,89 @@
 //!         println!("{:?}", user);
 //!     }
 //! }
+//! # }
+//! # }
+//! ```
+//!
+//! # Administration
+//!
+//! Handles to the client's database and collections can be used for administrative tasks, such as
+//! dropping them, without needing to create a runtime for the async `Client`.
+//!
+//! ```no_run
+//! # mod wrapper {
+//! # use mongod_derive::{Bson, Mongo};
+//! # #[derive(Debug, Bson, Mongo)]
+//! # #[mongo(collection="users", field, filter, update)]
+//! # pub struct User {
+//! #     name: String,
+//! # }
+//! # fn doc() -> Result<(), mongod::Error> {
+//! let client = mongod::blocking::Client::new();
+//!
+//! let names = client.database().list_collection_names(None)?;
+//! if names.iter().any(|name| name == "users") {
+//!     client.collection::<User>().drop()?;
+//! }
+//! # Ok(())
+//! # }
+//! # }
+//! ```
+//!
+//! # GridFS
+//!
+//! Files can be stored in and fetched from a GridFS bucket using `std::io::Read` and
+//! `std::io::Write`, so that they can be streamed from and to disk without being held in memory.
+//!
+//! ```no_run
+//! # fn doc() -> Result<(), mongod::Error> {
+//! let client = mongod::blocking::Client::new();
+//! let bucket = client.database().gridfs_bucket(None);
+//!
+//! let file = std::fs::File::open("./report.pdf").unwrap();
+//! let id = bucket.upload_from_reader("report.pdf", file, None)?;
+//!
+//! let mut contents = vec![];
+//! bucket.download_to_writer(id, &mut contents)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Watching changes
+//!
+//! The changes made to a collection can be iterated over as they happen, which allows tools
+//! without an async runtime to react to them. Change streams require a replica set or sharded
+//! cluster.
+//!
+//! ```no_run
+//! # mod wrapper {
+//! # use mongod_derive::{Bson, Mongo};
+//! # #[derive(Debug, Bson, Mongo)]
+//! # #[mongo(collection="users", field, filter, update)]
+//! # pub struct User {
+//! #     name: String,
+//! # }
+//! # fn doc() -> Result<(), mongod::Error> {
+//! let client = mongod::blocking::Client::new();
+//!
+//! for res in client.watch::<User>()? {
+//!     let event = res?;
+//!     println!("{:?}: {:?}", event.document_key, event.change);
+//! }
+//! # Ok(())
+//! # }
+//! # }
 //! ```
 
+mod change_stream;
 mod client;
 mod cursor;
+mod database;
+mod gridfs;
 
+pub use self::change_stream::ChangeStream;
 pub use self::client::{Client, ClientBuilder};
 pub(crate) use self::client::{Request, Response};
-pub use self::cursor::{Cursor, TypedCursor};
+pub use self::cursor::{Chunks, Cursor, DeserializeCursor, TypedCursor, ViewCursor};
+pub use self::database::{Collection, Database};
+pub use self::gridfs::{Bucket, DownloadStream, UploadStream};
@@ -0,0 +1,231 @@
+//! Typed events for watching the changes made to a collection.
+//!
+//! A change stream is opened using the [`Watch`](../query/struct.Watch.html) querier, which yields
+//! `ChangeEvent`s for both the async and blocking clients. The raw events of the wrapped stream
+//! can also be converted using `ChangeEvent::try_from`.
+//!
+//! To resume watching where it left off after a restart, the resume token of the last handled
+//! event can be persisted using a `TokenStore`, from which the `Watch` querier is then resumed.
+
+use std::convert::TryFrom;
+
+use bson::{Document, Timestamp};
+use mongodb::change_stream::event::ChangeStreamEvent;
+
+use crate::collection::Collection;
+
+pub use mongodb::change_stream::event::{
+    ChangeNamespace, OperationType, ResumeToken, UpdateDescription,
+};
+
+/// A change made to a `Collection`.
+#[derive(Debug)]
+pub struct ChangeEvent<C>
+where
+    C: Collection,
+{
+    /// The token that can be used to resume watching after this event.
+    pub resume_token: ResumeToken,
+    /// The change that occurred.
+    pub change: Change<C>,
+    /// The `_id`, and shard key for sharded collections, of the changed document.
+    pub document_key: Option<Document>,
+    /// The time at which the change occurred.
+    pub cluster_time: Option<Timestamp>,
+    /// The document before the change, when requested and the pre-image is available.
+    pub full_document_before_change: Option<C>,
+}
+
+/// The typed variants of a `ChangeEvent`.
+#[derive(Debug)]
+pub enum Change<C>
+where
+    C: Collection,
+{
+    /// A document was inserted.
+    Insert(C),
+    /// A document was updated.
+    Update {
+        /// The fields that were updated or removed.
+        description: UpdateDescription,
+        /// The document after the update, only present when watching with
+        /// `FullDocumentType::UpdateLookup`.
+        document: Option<C>,
+    },
+    /// A document was replaced.
+    Replace(C),
+    /// A document was deleted.
+    Delete,
+    /// The collection was dropped.
+    Drop,
+    /// The collection was renamed.
+    Rename {
+        /// The new namespace of the collection.
+        to: ChangeNamespace,
+    },
+    /// The database was dropped.
+    DropDatabase,
+    /// The change stream was invalidated, i.e. because the collection was dropped.
+    Invalidate,
+    /// Any other operation.
+    Other(OperationType),
+}
+
+impl<C> Change<C>
+where
+    C: Collection,
+{
+    /// Returns the type of operation that caused the change.
+    pub fn operation_type(&self) -> OperationType {
+        match self {
+            Change::Insert(_) => OperationType::Insert,
+            Change::Update { .. } => OperationType::Update,
+            Change::Replace(_) => OperationType::Replace,
+            Change::Delete => OperationType::Delete,
+            Change::Drop => OperationType::Drop,
+            Change::Rename { .. } => OperationType::Rename,
+            Change::DropDatabase => OperationType::DropDatabase,
+            Change::Invalidate => OperationType::Invalidate,
+            Change::Other(operation_type) => operation_type.clone(),
+        }
+    }
+}
+
+impl<C> TryFrom<ChangeStreamEvent<Document>> for ChangeEvent<C>
+where
+    C: Collection,
+{
+    type Error = crate::Error;
+    fn try_from(event: ChangeStreamEvent<Document>) -> crate::Result<Self> {
+        let full_document = event.full_document.map(C::from_document).transpose()?;
+        let change = match event.operation_type {
+            OperationType::Insert => {
+                Change::Insert(full_document.ok_or_else(missing("fullDocument"))?)
+            }
+            OperationType::Update => Change::Update {
+                description: event
+                    .update_description
+                    .ok_or_else(missing("updateDescription"))?,
+                document: full_document,
+            },
+            OperationType::Replace => {
+                Change::Replace(full_document.ok_or_else(missing("fullDocument"))?)
+            }
+            OperationType::Delete => Change::Delete,
+            OperationType::Drop => Change::Drop,
+            OperationType::Rename => Change::Rename {
+                to: event.to.ok_or_else(missing("to"))?,
+            },
+            OperationType::DropDatabase => Change::DropDatabase,
+            OperationType::Invalidate => Change::Invalidate,
+            operation_type => Change::Other(operation_type),
+        };
+        Ok(Self {
+            resume_token: event.id,
+            change,
+            document_key: event.document_key,
+            cluster_time: event.cluster_time,
+            full_document_before_change: event
+                .full_document_before_change
+                .map(C::from_document)
+                .transpose()?,
+        })
+    }
+}
+
+/// A store for the resume token of a change stream.
+///
+/// Persisting the token allows a change stream to be resumed where it left off, such as after a
+/// restart, using [`Watch::resume_from`](../query/struct.Watch.html#method.resume_from).
+///
+/// # Blocking
+///
+/// The store is called synchronously, in the async client from within `Stream::poll_next`, so
+/// `save` must be cheap and must not block. A store backed by slow I/O should hand the token off,
+/// i.e. to a channel drained by a task of its own, instead of writing it out itself.
+///
+/// # Example
+///
+/// Keep the token in memory, a real store would persist it to disk or a database.
+///
+/// ```
+/// use mongod::change_stream::{ResumeToken, TokenStore};
+///
+/// struct MemoryStore(Option<ResumeToken>);
+///
+/// impl TokenStore for MemoryStore {
+///     fn load(&self) -> Result<Option<ResumeToken>, mongod::Error> {
+///         Ok(self.0.clone())
+///     }
+///
+///     fn save(&mut self, token: &ResumeToken) -> Result<(), mongod::Error> {
+///         self.0 = Some(token.clone());
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait TokenStore {
+    /// Returns the stored token, or `None` should there not be one.
+    fn load(&self) -> crate::Result<Option<ResumeToken>>;
+
+    /// Stores the token, replacing the one stored previously.
+    ///
+    /// This is called before each event is requested from the stream, so it must return quickly.
+    fn save(&mut self, token: &ResumeToken) -> crate::Result<()>;
+}
+
+fn missing(field: &'static str) -> impl FnOnce() -> crate::Error {
+    move || crate::Error::invalid_document(format!("change event is missing '{}'", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::User;
+
+    #[test]
+    fn typed_event() {
+        let event: ChangeStreamEvent<Document> = bson::from_document(doc! {
+            "_id": { "_data": "token" },
+            "operationType": "insert",
+            "documentKey": { "_id": 1 },
+            "fullDocument": { "_id": 1, "name": "foo" },
+        })
+        .unwrap();
+        let event = ChangeEvent::<User>::try_from(event).unwrap();
+        assert_eq!(event.change.operation_type(), OperationType::Insert);
+        assert_eq!(event.document_key, Some(doc! { "_id": 1 }));
+        assert!(matches!(event.change, Change::Insert(user) if user.name == "foo"));
+        assert!(event.full_document_before_change.is_none());
+
+        let event: ChangeStreamEvent<Document> = bson::from_document(doc! {
+            "_id": { "_data": "token" },
+            "operationType": "update",
+            "documentKey": { "_id": 1 },
+            "updateDescription": { "updatedFields": { "name": "bar" }, "removedFields": [] },
+        })
+        .unwrap();
+        let event = ChangeEvent::<User>::try_from(event).unwrap();
+        assert!(matches!(
+            event.change,
+            Change::Update { description, document: None }
+                if description.updated_fields == doc! { "name": "bar" }
+        ));
+
+        let event: ChangeStreamEvent<Document> = bson::from_document(doc! {
+            "_id": { "_data": "token" },
+            "operationType": "insert",
+        })
+        .unwrap();
+        assert!(ChangeEvent::<User>::try_from(event).is_err());
+
+        let event: ChangeStreamEvent<Document> = bson::from_document(doc! {
+            "_id": { "_data": "token" },
+            "operationType": "insert",
+            "fullDocument": { "_id": 1 },
+        })
+        .unwrap();
+        assert!(ChangeEvent::<User>::try_from(event).is_err());
+    }
+}
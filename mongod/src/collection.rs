@@ -1,4 +1,6 @@
-use bson::Document;
+use bson::{Bson, Document};
+use mongodb::options::CreateCollectionOptions;
+use mongodb::IndexModel;
 
 use crate::error::Error;
 
@@ -55,12 +57,49 @@ pub trait Collection {
     /// The name of the collection to store the documents in.
     const COLLECTION: &'static str;
 
+    /// The name of the collection to store the documents in, as used by the queriers.
+    ///
+    /// By default this is [`COLLECTION`](`Collection::COLLECTION`), it can be overridden when the
+    /// name is only known at runtime, i.e. per-environment or date-partitioned collections.
+    fn collection() -> String {
+        Self::COLLECTION.to_owned()
+    }
+
     /// Convert from a BSON `Document` into the `Collection`s type.
     fn from_document(document: Document) -> Result<Self, Error>
     where
         Self: Sized;
     /// Convert the `Collection`s type into a BSON `Document`.
     fn into_document(self) -> Result<Document, Error>;
+
+    /// The document's `_id`, if it holds one.
+    ///
+    /// This is used by [`Client::save`](`crate::Client::save`) to decide between inserting and
+    /// replacing the document. By default documents do not hold their `_id`.
+    fn id(&self) -> Option<Bson> {
+        None
+    }
+
+    /// Sets the document's `_id`, which is called once the document has been inserted.
+    ///
+    /// By default this does nothing.
+    fn set_id(&mut self, _id: Bson) {}
+
+    /// The indexes that should exist on the collection, which are created along with it by
+    /// [`Client::create_collection`](`crate::Client::create_collection`).
+    ///
+    /// By default there are none.
+    fn indexes() -> Vec<IndexModel> {
+        Vec::new()
+    }
+
+    /// The options used to create the collection using
+    /// [`Client::create_collection`](`crate::Client::create_collection`).
+    ///
+    /// By default there are none.
+    fn create_options() -> Option<CreateCollectionOptions> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +140,26 @@ mod tests {
     #[test]
     fn collection() {
         assert_eq!(User::COLLECTION, "users");
+        assert_eq!(User::collection(), "users");
+    }
+
+    #[test]
+    fn collection_id() {
+        let mut user = User {
+            name: "foo".to_owned(),
+        };
+        user.set_id(bson::oid::ObjectId::new().into());
+        assert!(user.id().is_none());
+    }
+
+    #[test]
+    fn collection_indexes() {
+        assert!(User::indexes().is_empty());
+    }
+
+    #[test]
+    fn collection_create_options() {
+        assert!(User::create_options().is_none());
     }
 
     #[test]
@@ -55,6 +55,13 @@ impl Error {
     pub fn invalid_document<E: Into<Source>>(error: E) -> Error {
         Error::new(Kind::InvalidDocument).with(error)
     }
+
+    /// Creates a custom `Kind::InvalidQueryString` error.
+    ///
+    /// This is useful when manually parsing filters from query strings.
+    pub fn invalid_query_string<E: Into<Source>>(error: E) -> Error {
+        Error::new(Kind::InvalidQueryString).with(error)
+    }
 }
 
 impl fmt::Debug for Error {
@@ -75,7 +82,10 @@ impl fmt::Display for Error {
             Kind::Builder => "builder error",
             Kind::Mongodb => "mongodb error",
             Kind::InvalidDocument => "invalid document",
+            Kind::InvalidQueryString => "invalid query string",
+            Kind::Io => "io error",
             Kind::Runtime => "runtime error",
+            Kind::Timeout => "timeout error",
         };
         if let Some(ref source) = self.inner.source {
             write!(f, "{}: {}", desc, source)
@@ -114,8 +124,14 @@ pub enum Kind {
     Mongodb,
     /// Error that occurred when creating a runtime
     Runtime,
+    /// Error that occurred when an operation exceeded its timeout
+    Timeout,
     /// Error that occurred when converting to or from a BSON `Document`
     InvalidDocument,
+    /// Error that occurred when parsing a query string
+    InvalidQueryString,
+    /// Error that occurred when reading or writing data, i.e. GridFS files
+    Io,
 }
 
 // Helpers
@@ -134,6 +150,15 @@ pub(crate) fn mongodb<E: Into<Source>>(e: E) -> Error {
     Error::new(Kind::Mongodb).with(e)
 }
 
+pub(crate) fn timeout<E: Into<Source>>(e: E) -> Error {
+    Error::new(Kind::Timeout).with(e)
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) fn io<E: Into<Source>>(e: E) -> Error {
+    Error::new(Kind::Io).with(e)
+}
+
 #[cfg(feature = "blocking")]
 pub(crate) fn runtime<E: Into<Source>>(e: E) -> Error {
     Error::new(Kind::Runtime).with(e)
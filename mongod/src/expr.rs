@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use bson::Bson;
+
+use crate::field::Field;
+
+/// A typed aggregation expression, for use in `$expr` filters.
+///
+/// Expressions reference the fields of a `Collection` through its derived `Field`, allowing
+/// documents to be filtered by comparing their fields against one another. They are included in
+/// a filter by converting them into a [`FilterExpr`](./enum.FilterExpr.html).
+///
+/// # Examples
+///
+/// Finding users that have logged in since they were last updated.
+///
+/// ```
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Expr, Filter, FilterExpr};
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", field, filter)]
+/// pub struct User {
+///     name: String,
+///     last_login: i64,
+///     last_update: i64,
+/// }
+///
+/// # pub fn main() {
+/// let expr = Expr::field(user::Field::LastLogin).gt(Expr::field(user::Field::LastUpdate));
+/// let filter: FilterExpr<user::Filter> = expr.into();
+///
+/// assert_eq!(
+///     filter.into_document().unwrap(),
+///     mongod::bson::doc! { "$expr": { "$gt": ["$last_login", "$last_update"] } },
+/// );
+/// # }
+/// # }
+/// # wrapper::main();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expr<F: Field + Into<String>> {
+    bson: Bson,
+    _field: PhantomData<F>,
+}
+
+#[allow(clippy::should_implement_trait)]
+impl<F: Field + Into<String>> Expr<F> {
+    fn new(bson: Bson) -> Self {
+        Expr {
+            bson,
+            _field: PhantomData,
+        }
+    }
+
+    fn op(op: &str, args: Vec<Self>) -> Self {
+        Self::new(bson!({ op: args.into_iter().map(Expr::into_bson).collect::<Vec<_>>() }))
+    }
+
+    /// An expression that references the value of a field.
+    pub fn field(field: F) -> Self {
+        Self::new(Bson::String(format!("${}", field.into())))
+    }
+
+    /// An expression that is a literal value.
+    pub fn value<T: Into<Bson>>(value: T) -> Self {
+        Self::new(bson!({ "$literal": value.into() }))
+    }
+
+    /// Evaluates to `true` when this expression equals the other.
+    pub fn eq(self, other: Self) -> Self {
+        Self::op("$eq", vec![self, other])
+    }
+
+    /// Evaluates to `true` when this expression is greater than the other.
+    pub fn gt(self, other: Self) -> Self {
+        Self::op("$gt", vec![self, other])
+    }
+
+    /// Evaluates to `true` when this expression is greater than or equal to the other.
+    pub fn gte(self, other: Self) -> Self {
+        Self::op("$gte", vec![self, other])
+    }
+
+    /// Evaluates to `true` when this expression is less than the other.
+    pub fn lt(self, other: Self) -> Self {
+        Self::op("$lt", vec![self, other])
+    }
+
+    /// Evaluates to `true` when this expression is less than or equal to the other.
+    pub fn lte(self, other: Self) -> Self {
+        Self::op("$lte", vec![self, other])
+    }
+
+    /// Evaluates to `true` when this expression does not equal the other.
+    pub fn ne(self, other: Self) -> Self {
+        Self::op("$ne", vec![self, other])
+    }
+
+    /// Evaluates to `true` when both this expression and the other are `true`.
+    pub fn and(self, other: Self) -> Self {
+        Self::op("$and", vec![self, other])
+    }
+
+    /// Evaluates to `true` when either this expression or the other are `true`.
+    pub fn or(self, other: Self) -> Self {
+        Self::op("$or", vec![self, other])
+    }
+
+    /// Evaluates to the boolean opposite of this expression.
+    pub fn not(self) -> Self {
+        Self::op("$not", vec![self])
+    }
+
+    /// Evaluates to the sum of this expression and the other.
+    pub fn add(self, other: Self) -> Self {
+        Self::op("$add", vec![self, other])
+    }
+
+    /// Evaluates to this expression minus the other.
+    pub fn sub(self, other: Self) -> Self {
+        Self::op("$subtract", vec![self, other])
+    }
+
+    /// Evaluates to the product of this expression and the other.
+    pub fn mul(self, other: Self) -> Self {
+        Self::op("$multiply", vec![self, other])
+    }
+
+    /// Evaluates to this expression divided by the other.
+    pub fn div(self, other: Self) -> Self {
+        Self::op("$divide", vec![self, other])
+    }
+
+    /// Converts the `Expr` into BSON.
+    pub fn into_bson(self) -> Bson {
+        self.bson
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub enum Field {
+        Price,
+        Quantity,
+    }
+
+    impl crate::field::Field for Field {}
+
+    impl From<Field> for String {
+        fn from(field: Field) -> String {
+            match field {
+                Field::Price => "price".to_owned(),
+                Field::Quantity => "quantity".to_owned(),
+            }
+        }
+    }
+
+    #[test]
+    fn expr_into_bson() {
+        let expr = Expr::field(Field::Price)
+            .mul(Expr::field(Field::Quantity))
+            .gte(Expr::value(100))
+            .and(Expr::field(Field::Quantity).ne(Expr::value("$0")).not());
+        assert_eq!(
+            expr.into_bson(),
+            bson!({
+                "$and": [
+                    { "$gte": [{ "$multiply": ["$price", "$quantity"] }, { "$literal": 100 }] },
+                    { "$not": [{ "$ne": ["$quantity", { "$literal": "$0" }] }] },
+                ]
+            })
+        );
+    }
+}
@@ -1,11 +1,68 @@
 //! Extensions for the `bson` crate so that `mongo-derive` can be implemented efficiently.
+//!
+//! # Dates
+//!
+//! Date and time types are stored as a `Bson::DateTime`, which is a UTC instant with millisecond
+//! precision:
+//!
+//! - `chrono::NaiveDateTime` is treated as UTC
+//! - `chrono::NaiveDate` & `time::Date` are stored at midnight UTC
+//! - `chrono::NaiveTime` is stored on the unix epoch, `1970-01-01`
+//! - `chrono::DateTime<FixedOffset>` is stored as UTC, so is read back with a zero offset
+//!
+//! # Unsigned Integers
+//!
+//! `u32` & `u64` are checked rather than cast to their signed equivalent. A `u32` too large for a
+//! `Bson::Int32` is stored as a `Bson::Int64`, while a `u64` too large for a `Bson::Int64` is a
+//! range error, so `u64` only converts into `Bson` through `TryFrom`. Negative or oversized values
+//! are rejected when read back.
+//!
+//! The lossy behaviours have to be opted into, either [`set_unsigned_wrapping`] to wrap, i.e.
+//! `u64::MAX` stored as `-1`, or the [`as_decimal128`] module with `#[bson(with = "...")]` to store
+//! a `u64` as a `Bson::Decimal128`.
+//!
+//! # Numbers
+//!
+//! Each numeric type only accepts its own `Bson` variant by default, i.e. an `i32` must be read
+//! from a `Bson::Int32`. Collections that mix numeric widths can use [`set_numeric_coercion`] so
+//! that `Bson::Int32`, `Bson::Int64` & `Bson::Double` are accepted interchangeably when the
+//! conversion is lossless, or only the fields that need it can use the [`coerce_numbers`] module
+//! with `#[bson(with = "...")]`.
+//!
+//! # Bytes
+//!
+//! `Vec<u8>`, `&[u8]`, `[u8; N]`, `bytes::Bytes` & `bytes::BytesMut` are stored as a generic
+//! `Bson::Binary`, rather than an array of integers. For this to work `u8` only converts into
+//! `Bson` through `TryFrom`. Arrays of integers are still read back into a `Vec<u8>`, and the
+//! [`as_array`] module can be used with `#[bson(with = "...")]` to keep writing them. Only bytes
+//! can be read from a `Bson::Binary`, i.e. reading one into a `Vec<i32>` fails.
+//!
+//! The exception is `[u8; 12]`, which is stored as a `Bson::ObjectId` as it is by the `bson`
+//! crate, and can be read back from one.
+//!
+//! # Smart Pointers
+//!
+//! `Rc<T>` & `Arc<T>` convert into `Bson` as their `T`, which is only cloned when there are other
+//! references to it. The remaining conversions, i.e. `Box<T>` into `Bson` or `Bson` into any of
+//! them, conflict with the standard library's blanket implementations, so instead `mongo-derive`
+//! unwraps smart pointers itself.
 
 use serde::de::Error;
 use serde::ser::Error as SerError;
-use std::collections::{HashMap, HashSet};
-use std::convert::{TryFrom, TryInto};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::convert::{Infallible, TryFrom, TryInto};
 use std::hash::Hash;
 use std::iter::FromIterator;
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
+};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+
+use bson::uuid::UuidRepresentation;
 
 // The BSON crate implments zero methods to go from BSON to another type without using serde, lets
 // rectify that here...
@@ -96,17 +153,246 @@ pub mod ser {
     }
 }
 
+static UUID_REPRESENTATION: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the representation used when converting a `uuid::Uuid` to and from `Bson`.
+///
+/// The representation is process wide, defaulting to `UuidRepresentation::Standard`, so it should
+/// be set once before any conversions take place. A `uuidRepresentation` given in the uri of a
+/// `Client` is validate only, it never changes the representation, instead building the client
+/// fails should the two not match.
+///
+/// # Example
+///
+/// ```
+/// use mongod::ext::bson::{set_uuid_representation, uuid_representation};
+/// use mongod::options::UuidRepresentation;
+///
+/// set_uuid_representation(UuidRepresentation::JavaLegacy);
+/// assert_eq!(uuid_representation(), UuidRepresentation::JavaLegacy);
+/// ```
+pub fn set_uuid_representation(representation: UuidRepresentation) {
+    let value = match representation {
+        UuidRepresentation::CSharpLegacy => 1,
+        UuidRepresentation::JavaLegacy => 2,
+        UuidRepresentation::PythonLegacy => 3,
+        _ => 0,
+    };
+    UUID_REPRESENTATION.store(value, Ordering::Relaxed);
+}
+
+/// Returns the representation used when converting a `uuid::Uuid` to and from `Bson`.
+pub fn uuid_representation() -> UuidRepresentation {
+    match UUID_REPRESENTATION.load(Ordering::Relaxed) {
+        1 => UuidRepresentation::CSharpLegacy,
+        2 => UuidRepresentation::JavaLegacy,
+        3 => UuidRepresentation::PythonLegacy,
+        _ => UuidRepresentation::Standard,
+    }
+}
+
+static UNSIGNED_WRAPPING: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether `u32`, `u64` & `usize` wrap when converted to and from `Bson`, rather than being
+/// checked.
+///
+/// Wrapping casts to and from the signed equivalent, which is how these were converted before
+/// being checked, so it can be used with collections that already hold wrapped values. The
+/// setting is process wide and defaults to `false`.
+pub fn set_unsigned_wrapping(wrapping: bool) {
+    UNSIGNED_WRAPPING.store(wrapping, Ordering::Relaxed);
+}
+
+/// Returns whether `u32`, `u64` & `usize` wrap when converted to and from `Bson`.
+pub fn unsigned_wrapping() -> bool {
+    UNSIGNED_WRAPPING.load(Ordering::Relaxed)
+}
+
+static NUMERIC_COERCION: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether numeric types accept any of `Bson::Int32`, `Bson::Int64` & `Bson::Double` when
+/// converted from `Bson`, as long as the value converts losslessly.
+///
+/// The setting is process wide and defaults to `false`.
+pub fn set_numeric_coercion(lenient: bool) {
+    NUMERIC_COERCION.store(lenient, Ordering::Relaxed);
+}
+
+/// Returns whether numeric types accept any numeric `Bson` variant when converted from `Bson`.
+pub fn numeric_coercion() -> bool {
+    NUMERIC_COERCION.load(Ordering::Relaxed)
+}
+
+// The integral value of a `Bson::Double`, if it fits into an `i64`.
+fn double_to_i64(f: f64) -> Option<i64> {
+    // NOTE: `i64::MAX as f64` rounds up to 2^63 which does not fit
+    if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+        Some(f as i64)
+    } else {
+        None
+    }
+}
+
+// Massages a numeric `bson` into a `Bson::Int32` when numeric coercion is enabled, see
+// `to_int32`.
+fn coerce_int32(bson: bson::Bson) -> bson::Bson {
+    if !numeric_coercion() {
+        return bson;
+    }
+    to_int32(bson)
+}
+
+// Massages a numeric `bson` into a `Bson::Int64` when numeric coercion is enabled, see `to_int64`.
+fn coerce_int64(bson: bson::Bson) -> bson::Bson {
+    if !numeric_coercion() {
+        return bson;
+    }
+    to_int64(bson)
+}
+
+// Massages a numeric `bson` into a `Bson::Double` when numeric coercion is enabled, see
+// `to_double`.
+fn coerce_double(bson: bson::Bson, mantissa: u32) -> bson::Bson {
+    if !numeric_coercion() {
+        return bson;
+    }
+    to_double(bson, mantissa)
+}
+
+// Massages a numeric `bson` into a `Bson::Int32` when it fits, otherwise it is returned as is.
+fn to_int32(bson: bson::Bson) -> bson::Bson {
+    let value = match bson {
+        bson::Bson::Int64(i) => Some(i),
+        bson::Bson::Double(f) => double_to_i64(f),
+        _ => None,
+    };
+    match value.and_then(|i| i32::try_from(i).ok()) {
+        Some(i) => bson::Bson::Int32(i),
+        None => bson,
+    }
+}
+
+// Massages a numeric `bson` into a `Bson::Int64`, see `to_int32`.
+fn to_int64(bson: bson::Bson) -> bson::Bson {
+    let value = match bson {
+        bson::Bson::Int32(i) => Some(i as i64),
+        bson::Bson::Double(f) => double_to_i64(f),
+        _ => None,
+    };
+    match value {
+        Some(i) => bson::Bson::Int64(i),
+        None => bson,
+    }
+}
+
+// Massages a numeric `bson` into a `Bson::Double` when its integer fits within the `mantissa`
+// bits of the float, see `to_int32`.
+fn to_double(bson: bson::Bson, mantissa: u32) -> bson::Bson {
+    let value = match bson {
+        bson::Bson::Int32(i) => i as i64,
+        bson::Bson::Int64(i) => i,
+        _ => return bson,
+    };
+    if value.unsigned_abs() <= 1 << mantissa {
+        bson::Bson::Double(value as f64)
+    } else {
+        bson
+    }
+}
+
 /// Wraps `bson::Bson` so that additional rust conversions can be applied.
+#[derive(Debug)]
 pub struct Bson(pub bson::Bson);
 
 /// Wraps a type that implements `serde::de::Deserialize` so it can bypass blanket implementations
 // FIXME: https://github.com/rust-lang/rust/issues/31844
+#[derive(Debug)]
 pub struct De<T: serde::de::DeserializeOwned>(pub T);
 
 /// Wraps a type that implements `serde::ser::Serialize` so it can bypass blanket implementations
 // FIXME: https://github.com/rust-lang/rust/issues/31844
+#[derive(Debug)]
 pub struct Ser<T: serde::ser::Serialize>(pub T);
 
+/// Wraps a value along with the function used to convert it into BSON, so that fields using
+/// `#[bson(with = "...")]` can still be used in derived filters.
+pub struct With<T> {
+    /// The wrapped value.
+    pub value: T,
+    /// The function used to convert the value into BSON.
+    pub to_bson: fn(T) -> Result<bson::Bson, ser::Error>,
+}
+
+impl<T> With<T> {
+    /// Wraps the `value` with its conversion function.
+    pub fn new(value: T, to_bson: fn(T) -> Result<bson::Bson, ser::Error>) -> Self {
+        With { value, to_bson }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for With<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("With").field(&self.value).finish()
+    }
+}
+
+/// Converts a custom type into `Bson`.
+///
+/// Implementing this, along with [`FromBson`], on a downstream type and deriving `Bson` with
+/// `#[bson(scalar)]` plugs it into the conversions used by `mongo-derive`, so it can be used as a
+/// field without `#[bson(serde)]` or `#[bson(with = "...")]`.
+///
+/// # Examples
+///
+/// ```
+/// # use mongod_derive::Bson;
+/// use std::convert::TryFrom;
+///
+/// use mongod::ext::bson::de::{self, ErrorExt};
+/// use mongod::ext::bson::{ser, FromBson, ToBson};
+///
+/// #[derive(Bson, Debug, PartialEq)]
+/// #[bson(scalar)]
+/// struct Cents(i64);
+///
+/// // Stored as a decimal string, i.e. `"1.50"`
+/// impl ToBson for Cents {
+///     fn to_bson(self) -> Result<mongod::bson::Bson, ser::Error> {
+///         Ok(format!("{}.{:02}", self.0 / 100, self.0 % 100).into())
+///     }
+/// }
+///
+/// impl FromBson for Cents {
+///     fn from_bson(bson: mongod::bson::Bson) -> Result<Self, de::Error> {
+///         let s = String::try_from(mongod::ext::bson::Bson(bson))?;
+///         let (units, cents) = s
+///             .split_once('.')
+///             .and_then(|(u, c)| Some((u.parse::<i64>().ok()?, c.parse::<i64>().ok()?)))
+///             .ok_or_else(|| mongod::bson::de::Error::custom(format!("invalid amount `{}`", s)))?;
+///         Ok(Cents(units * 100 + cents))
+///     }
+/// }
+///
+/// #[derive(Bson)]
+/// struct Order {
+///     total: Cents,
+/// }
+///
+/// let bson = mongod::bson::Bson::try_from(Order { total: Cents(150) }).unwrap();
+/// assert_eq!(bson, mongod::bson::bson!({ "total": "1.50" }));
+/// assert_eq!(Order::try_from(bson).unwrap().total, Cents(150));
+/// ```
+pub trait ToBson {
+    /// Converts the value into `Bson`.
+    fn to_bson(self) -> Result<bson::Bson, ser::Error>;
+}
+
+/// Converts `Bson` into a custom type, see [`ToBson`].
+pub trait FromBson: Sized {
+    /// Converts the `Bson` into the value.
+    fn from_bson(bson: bson::Bson) -> Result<Self, de::Error>;
+}
+
 // NOTE: Due to https://github.com/rust-lang/rust/issues/29635 we cant be generic and implement the
 // missing, so we have to wrap them all... yay...
 macro_rules! wrap_bson_from {
@@ -121,6 +407,7 @@ macro_rules! wrap_bson_from {
 wrap_bson_from!(bson::Binary);
 wrap_bson_from!(bson::Bson);
 wrap_bson_from!(bson::DbPointer);
+wrap_bson_from!(bson::Decimal128);
 wrap_bson_from!(bson::Document);
 wrap_bson_from!(bson::JavaScriptCodeWithScope);
 wrap_bson_from!(bson::oid::ObjectId);
@@ -133,10 +420,83 @@ wrap_bson_from!(f64);
 wrap_bson_from!(i32);
 wrap_bson_from!(i64);
 wrap_bson_from!(String);
-wrap_bson_from!([u8; 12]);
 #[cfg(feature = "chrono")]
 wrap_bson_from!(chrono::DateTime<chrono::Utc>);
 
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::FixedOffset>> for Bson {
+    fn from(dt: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Bson(bson::Bson::DateTime(bson::DateTime::from_chrono(dt)))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for Bson {
+    fn from(dt: chrono::NaiveDateTime) -> Self {
+        chrono::TimeZone::from_utc_datetime(&chrono::Utc, &dt).into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Bson {
+    fn from(date: chrono::NaiveDate) -> Self {
+        date.and_time(chrono::NaiveTime::default()).into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for Bson {
+    fn from(time: chrono::NaiveTime) -> Self {
+        chrono::NaiveDate::default().and_time(time).into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Bson {
+    fn from(dt: time::OffsetDateTime) -> Self {
+        Bson(bson::Bson::DateTime(bson::DateTime::from_time_0_3(dt)))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Date> for Bson {
+    fn from(date: time::Date) -> Self {
+        date.midnight().assume_utc().into()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Bson {
+    fn from(uuid: uuid::Uuid) -> Self {
+        Bson(bson::Bson::Binary(
+            bson::Binary::from_uuid_with_representation(uuid.into(), uuid_representation()),
+        ))
+    }
+}
+
+#[cfg(feature = "url")]
+impl From<url::Url> for Bson {
+    fn from(url: url::Url) -> Self {
+        Bson(bson::Bson::String(url.into()))
+    }
+}
+
+// NOTE: Decimal128 is encoded using the binary integer decimal format, which is limited to what
+// `rust_decimal` can represent: a 96 bit coefficient with a scale of up to 28.
+const DECIMAL128_EXPONENT_BIAS: i32 = 6176;
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Bson {
+    fn from(decimal: rust_decimal::Decimal) -> Self {
+        let mantissa = decimal.mantissa();
+        let exponent = (DECIMAL128_EXPONENT_BIAS - decimal.scale() as i32) as u128;
+        let bits = (((mantissa < 0) as u128) << 127) | (exponent << 113) | mantissa.unsigned_abs();
+        Bson(bson::Bson::Decimal128(bson::Decimal128::from_bytes(
+            bits.to_le_bytes(),
+        )))
+    }
+}
+
 macro_rules! wrap_try_from_bson {
     ($source:ty) => {
         impl TryFrom<Bson> for $source {
@@ -150,6 +510,7 @@ macro_rules! wrap_try_from_bson {
 wrap_try_from_bson!(bson::Binary);
 wrap_try_from_bson!(bson::Bson);
 wrap_try_from_bson!(bson::DbPointer);
+wrap_try_from_bson!(bson::Decimal128);
 wrap_try_from_bson!(bson::Document);
 wrap_try_from_bson!(bson::JavaScriptCodeWithScope);
 wrap_try_from_bson!(bson::oid::ObjectId);
@@ -162,6 +523,12 @@ impl From<char> for Bson {
     }
 }
 
+impl From<Cow<'_, str>> for Bson {
+    fn from(s: Cow<'_, str>) -> Self {
+        Bson(bson::Bson::String(s.into_owned()))
+    }
+}
+
 impl From<i8> for Bson {
     fn from(value: i8) -> Self {
         Bson(bson::Bson::Int32(value as i32))
@@ -174,9 +541,13 @@ impl From<i16> for Bson {
     }
 }
 
-impl From<u8> for Bson {
-    fn from(value: u8) -> Self {
-        Bson(bson::Bson::Int32(value as i32))
+// NOTE: This is not `From` so that the generic conversions of `Vec<T>` and the like do not
+// overlap with those of `Vec<u8>` & co, which are stored as binary.
+#[allow(clippy::infallible_try_from)]
+impl TryFrom<u8> for Bson {
+    type Error = Infallible;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Bson(bson::Bson::Int32(value as i32)))
     }
 }
 
@@ -188,15 +559,142 @@ impl From<u16> for Bson {
 
 impl From<u32> for Bson {
     fn from(value: u32) -> Self {
-        Bson(bson::Bson::Int32(value as i32))
+        match i32::try_from(value) {
+            Ok(i) => Bson(bson::Bson::Int32(i)),
+            Err(_) if unsigned_wrapping() => Bson(bson::Bson::Int32(value as i32)),
+            Err(_) => Bson(bson::Bson::Int64(value as i64)),
+        }
+    }
+}
+
+impl TryFrom<u64> for Bson {
+    type Error = ser::Error;
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match i64::try_from(value) {
+            Ok(i) => Ok(Bson(bson::Bson::Int64(i))),
+            Err(_) if unsigned_wrapping() => Ok(Bson(bson::Bson::Int64(value as i64))),
+            Err(_) => Err(bson::ser::Error::custom(format!(
+                "invalid value, could not coerce `{}` into a `Bson::Int64`",
+                value
+            ))
+            .into()),
+        }
     }
 }
 
-impl From<u64> for Bson {
-    fn from(value: u64) -> Self {
-        Bson(bson::Bson::Int64(value as i64))
+// The integral value of a `Decimal128`, if it fits into a `u64`.
+fn decimal128_to_u64(decimal: bson::Decimal128) -> Option<u64> {
+    let bits = u128::from_le_bytes(decimal.bytes());
+    // NOTE: Infinity, NaN and coefficients larger than 113 bits use the combination field
+    if bits >> 127 == 1 || (bits >> 125) & 0b11 == 0b11 {
+        return None;
+    }
+    let mut exponent = ((bits >> 113) & 0x3fff) as i32 - DECIMAL128_EXPONENT_BIAS;
+    let mut coefficient = bits & ((1 << 113) - 1);
+    while exponent > 0 {
+        coefficient = coefficient.checked_mul(10)?;
+        exponent -= 1;
+    }
+    while exponent < 0 {
+        if !coefficient.is_multiple_of(10) {
+            return None;
+        }
+        coefficient /= 10;
+        exponent += 1;
     }
+    u64::try_from(coefficient).ok()
+}
+
+macro_rules! non_zero {
+    ($ty:ty, $int:ty) => {
+        impl From<$ty> for Bson {
+            fn from(value: $ty) -> Self {
+                match Bson::try_from(value.get()) {
+                    Ok(bson) => bson,
+                    Err(e) => match e {},
+                }
+            }
+        }
+
+        non_zero!(@try_from_bson $ty, $int);
+    };
+    (try $ty:ty, $int:ty) => {
+        impl TryFrom<$ty> for Bson {
+            type Error = ser::Error;
+            fn try_from(value: $ty) -> Result<Self, Self::Error> {
+                Bson::try_from(value.get())
+            }
+        }
+
+        non_zero!(@try_from_bson $ty, $int);
+    };
+    (@try_from_bson $ty:ty, $int:ty) => {
+        impl TryFrom<Bson> for $ty {
+            type Error = de::Error;
+            fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+                let value = <$int>::try_from(bson)?;
+                <$ty>::new(value).ok_or_else(|| {
+                    bson::de::Error::custom(format!(
+                        "invalid value, could not coerce `{}` into a {}",
+                        value,
+                        stringify!($ty)
+                    ))
+                    .into()
+                })
+            }
+        }
+    };
+}
+non_zero!(NonZeroI8, i8);
+non_zero!(NonZeroI16, i16);
+non_zero!(NonZeroI32, i32);
+non_zero!(NonZeroI64, i64);
+non_zero!(NonZeroU8, u8);
+non_zero!(NonZeroU16, u16);
+non_zero!(NonZeroU32, u32);
+non_zero!(try NonZeroU64, u64);
+
+#[cfg(feature = "ordered-float")]
+macro_rules! ordered_float {
+    ($float:ty) => {
+        impl From<ordered_float::OrderedFloat<$float>> for Bson {
+            fn from(value: ordered_float::OrderedFloat<$float>) -> Self {
+                value.into_inner().into()
+            }
+        }
+
+        impl From<ordered_float::NotNan<$float>> for Bson {
+            fn from(value: ordered_float::NotNan<$float>) -> Self {
+                value.into_inner().into()
+            }
+        }
+
+        impl TryFrom<Bson> for ordered_float::OrderedFloat<$float> {
+            type Error = bson::de::Error;
+            fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+                <$float>::try_from(bson).map(ordered_float::OrderedFloat)
+            }
+        }
+
+        impl TryFrom<Bson> for ordered_float::NotNan<$float> {
+            type Error = bson::de::Error;
+            fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+                let value = <$float>::try_from(bson)?;
+                ordered_float::NotNan::new(value).map_err(|_| {
+                    bson::de::Error::custom(format!(
+                        "invalid value, could not coerce `{}` into a NotNan<{}>",
+                        value,
+                        stringify!($float)
+                    ))
+                })
+            }
+        }
+    };
 }
+#[cfg(feature = "ordered-float")]
+ordered_float!(f32);
+#[cfg(feature = "ordered-float")]
+ordered_float!(f64);
 
 impl<T> TryFrom<Bson> for De<T>
 where
@@ -222,6 +720,34 @@ where
     }
 }
 
+impl<T> TryFrom<With<T>> for Bson {
+    type Error = ser::Error;
+    fn try_from(value: With<T>) -> Result<Self, Self::Error> {
+        Ok(Bson((value.to_bson)(value.value)?))
+    }
+}
+
+impl TryFrom<&Path> for Bson {
+    type Error = ser::Error;
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        match path.to_str() {
+            Some(s) => Ok(Bson(bson::Bson::String(s.to_owned()))),
+            None => Err(bson::ser::Error::custom(format!(
+                "invalid value, could not coerce path `{}` into a string",
+                path.display()
+            ))
+            .into()),
+        }
+    }
+}
+
+impl TryFrom<PathBuf> for Bson {
+    type Error = ser::Error;
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        Bson::try_from(path.as_path())
+    }
+}
+
 impl<K, V> TryFrom<HashMap<K, V>> for Bson
 where
     K: Into<String>,
@@ -256,6 +782,76 @@ where
     }
 }
 
+impl<K, V> TryFrom<BTreeMap<K, V>> for Bson
+where
+    K: Into<String>,
+    V: TryInto<Bson>,
+    V::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(m: BTreeMap<K, V>) -> Result<Self, Self::Error> {
+        let mut doc = bson::Document::new();
+        for (k, v) in m {
+            doc.insert(k.into(), v.try_into().map_err(|e| e.into())?.0);
+        }
+        Ok(Bson(bson::Bson::Document(doc)))
+    }
+}
+
+impl<T> TryFrom<BTreeSet<T>> for Bson
+where
+    T: Ord + TryInto<Bson>,
+    T::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(s: BTreeSet<T>) -> Result<Self, Self::Error> {
+        let int = s
+            .into_iter()
+            .map(|t| t.try_into())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.into())?;
+        Ok(Bson(bson::Bson::Array(
+            int.into_iter().map(|x| x.0).collect(),
+        )))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V> TryFrom<indexmap::IndexMap<K, V>> for Bson
+where
+    K: Into<String>,
+    V: TryInto<Bson>,
+    V::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(m: indexmap::IndexMap<K, V>) -> Result<Self, Self::Error> {
+        let mut doc = bson::Document::new();
+        for (k, v) in m {
+            doc.insert(k.into(), v.try_into().map_err(|e| e.into())?.0);
+        }
+        Ok(Bson(bson::Bson::Document(doc)))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<T> TryFrom<indexmap::IndexSet<T>> for Bson
+where
+    T: Eq + Hash + TryInto<Bson>,
+    T::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(s: indexmap::IndexSet<T>) -> Result<Self, Self::Error> {
+        let int = s
+            .into_iter()
+            .map(|t| t.try_into())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.into())?;
+        Ok(Bson(bson::Bson::Array(
+            int.into_iter().map(|x| x.0).collect(),
+        )))
+    }
+}
+
 impl<T> TryFrom<Option<T>> for Bson
 where
     T: TryInto<Bson>,
@@ -270,6 +866,35 @@ where
     }
 }
 
+// NOTE: Shared values are only cloned when there are other references to them
+impl<T> TryFrom<Rc<T>> for Bson
+where
+    T: Clone + TryInto<Bson>,
+    T::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(value: Rc<T>) -> Result<Self, Self::Error> {
+        Rc::try_unwrap(value)
+            .unwrap_or_else(|v| (*v).clone())
+            .try_into()
+            .map_err(|e| e.into())
+    }
+}
+
+impl<T> TryFrom<Arc<T>> for Bson
+where
+    T: Clone + TryInto<Bson>,
+    T::Error: Into<ser::Error>,
+{
+    type Error = ser::Error;
+    fn try_from(value: Arc<T>) -> Result<Self, Self::Error> {
+        Arc::try_unwrap(value)
+            .unwrap_or_else(|v| (*v).clone())
+            .try_into()
+            .map_err(|e| e.into())
+    }
+}
+
 impl<T> From<Vec<T>> for Bson
 where
     T: Into<Bson>,
@@ -281,64 +906,156 @@ where
     }
 }
 
-impl<T> From<&T> for Bson
+impl<T> From<VecDeque<T>> for Bson
 where
-    T: Clone + Into<Bson>,
+    T: Into<Bson>,
 {
-    fn from(t: &T) -> Self {
-        t.clone().into()
+    fn from(v: VecDeque<T>) -> Self {
+        v.into_iter().collect()
     }
 }
 
-impl<T> From<&[T]> for Bson
+impl<T> From<LinkedList<T>> for Bson
 where
-    T: Clone + Into<Bson>,
+    T: Into<Bson>,
 {
-    fn from(v: &[T]) -> Self {
-        Bson(bson::Bson::Array(
-            v.iter().cloned().map(|x| x.into().0).collect(),
-        ))
+    fn from(l: LinkedList<T>) -> Self {
+        l.into_iter().collect()
     }
 }
 
-impl<T> FromIterator<T> for Bson
+impl<T, const N: usize> From<[T; N]> for Bson
 where
     T: Into<Bson>,
 {
-    fn from_iter<I>(iter: I) -> Self
-    where
-        I: IntoIterator<Item = T>,
-    {
-        Bson(bson::Bson::Array(
-            iter.into_iter().map(Into::into).map(|x| x.0).collect(),
-        ))
+    fn from(a: [T; N]) -> Self {
+        Vec::from(a).into()
     }
 }
 
-impl TryFrom<Bson> for Box<bson::Bson> {
-    type Error = std::convert::Infallible;
-    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        Ok(Box::new(bson.0))
+#[cfg(feature = "smallvec")]
+impl<A> From<smallvec::SmallVec<A>> for Bson
+where
+    A: smallvec::Array,
+    A::Item: Into<Bson>,
+{
+    fn from(v: smallvec::SmallVec<A>) -> Self {
+        v.into_iter().collect()
     }
 }
 
-impl From<Bson> for Option<bson::Bson> {
-    fn from(bson: Bson) -> Self {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Null => None,
-            _ => Some(inner),
-        }
+#[cfg(feature = "arrayvec")]
+impl<T, const CAP: usize> From<arrayvec::ArrayVec<T, CAP>> for Bson
+where
+    T: Into<Bson>,
+{
+    fn from(v: arrayvec::ArrayVec<T, CAP>) -> Self {
+        v.into_iter().collect()
     }
 }
 
-impl TryFrom<Bson> for bool {
-    type Error = bson::de::Error;
-    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Boolean(b) => Ok(b),
-            _ => Err(bson::de::Error::custom(format!(
+// Whether `T` is `u8`, so that byte arrays can be stored differently to other arrays. This cannot
+// be done with a dedicated impl, as it would overlap the generic one.
+fn is_u8<T>() -> bool {
+    std::any::type_name::<T>() == std::any::type_name::<u8>()
+}
+
+impl From<Vec<u8>> for Bson {
+    fn from(bytes: Vec<u8>) -> Self {
+        Bson(bson::Bson::Binary(bson::Binary {
+            subtype: bson::spec::BinarySubtype::Generic,
+            bytes,
+        }))
+    }
+}
+
+impl From<&[u8]> for Bson {
+    fn from(bytes: &[u8]) -> Self {
+        bytes.to_vec().into()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Bson {
+    fn from(bytes: [u8; N]) -> Self {
+        // NOTE: This only succeeds for a `[u8; 12]`, which is kept as an `ObjectId`
+        match <[u8; 12]>::try_from(&bytes[..]) {
+            Ok(oid) => Bson(bson::Bson::ObjectId(bson::oid::ObjectId::from_bytes(oid))),
+            Err(_) => bytes.to_vec().into(),
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Bson {
+    fn from(bytes: bytes::Bytes) -> Self {
+        Vec::from(bytes).into()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::BytesMut> for Bson {
+    fn from(bytes: bytes::BytesMut) -> Self {
+        Vec::from(bytes).into()
+    }
+}
+
+impl<T> From<&T> for Bson
+where
+    T: Clone + Into<Bson>,
+{
+    fn from(t: &T) -> Self {
+        t.clone().into()
+    }
+}
+
+impl<T> From<&[T]> for Bson
+where
+    T: Clone + Into<Bson>,
+{
+    fn from(v: &[T]) -> Self {
+        Bson(bson::Bson::Array(
+            v.iter().cloned().map(|x| x.into().0).collect(),
+        ))
+    }
+}
+
+impl<T> FromIterator<T> for Bson
+where
+    T: Into<Bson>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Bson(bson::Bson::Array(
+            iter.into_iter().map(Into::into).map(|x| x.0).collect(),
+        ))
+    }
+}
+
+impl From<Bson> for Box<bson::Bson> {
+    fn from(bson: Bson) -> Self {
+        Box::new(bson.0)
+    }
+}
+
+impl From<Bson> for Option<bson::Bson> {
+    fn from(bson: Bson) -> Self {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Null => None,
+            _ => Some(inner),
+        }
+    }
+}
+
+impl TryFrom<Bson> for bool {
+    type Error = bson::de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Boolean(b) => Ok(b),
+            _ => Err(bson::de::Error::custom(format!(
                 "invalid variant, expected `Bson::Bool(...)` but found `{}`",
                 inner
             ))),
@@ -371,7 +1088,7 @@ impl TryFrom<Bson> for char {
 impl TryFrom<Bson> for f32 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_double(bson.0, 24);
         match inner {
             bson::Bson::Double(f) => {
                 if f < (Self::MIN as f64) || f > (Self::MAX as f64) {
@@ -393,7 +1110,7 @@ impl TryFrom<Bson> for f32 {
 impl TryFrom<Bson> for f64 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_double(bson.0, 53);
         match inner {
             bson::Bson::Double(f) => Ok(f),
             _ => Err(bson::de::Error::custom(format!(
@@ -407,7 +1124,7 @@ impl TryFrom<Bson> for f64 {
 impl TryFrom<Bson> for i8 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int32(bson.0);
         match inner {
             bson::Bson::Int32(i) => {
                 if i < (Self::MIN as i32) || i > (Self::MAX as i32) {
@@ -429,7 +1146,7 @@ impl TryFrom<Bson> for i8 {
 impl TryFrom<Bson> for i16 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int32(bson.0);
         match inner {
             bson::Bson::Int32(i) => {
                 if i < (Self::MIN as i32) || i > (Self::MAX as i32) {
@@ -451,7 +1168,7 @@ impl TryFrom<Bson> for i16 {
 impl TryFrom<Bson> for i32 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int32(bson.0);
         match inner {
             bson::Bson::Int32(i) => Ok(i),
             _ => Err(bson::de::Error::custom(format!(
@@ -465,7 +1182,7 @@ impl TryFrom<Bson> for i32 {
 impl TryFrom<Bson> for i64 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int64(bson.0);
         match inner {
             bson::Bson::Int64(i) => Ok(i),
             _ => Err(bson::de::Error::custom(format!(
@@ -479,7 +1196,7 @@ impl TryFrom<Bson> for i64 {
 impl TryFrom<Bson> for isize {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int64(bson.0);
         match inner {
             bson::Bson::Int64(i) => {
                 if i < (Self::MIN as i64) || i > (Self::MAX as i64) {
@@ -501,7 +1218,7 @@ impl TryFrom<Bson> for isize {
 impl TryFrom<Bson> for u8 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int32(bson.0);
         match inner {
             bson::Bson::Int32(i) => {
                 if i < (Self::MIN as i32) || i > (Self::MAX as i32) {
@@ -523,7 +1240,7 @@ impl TryFrom<Bson> for u8 {
 impl TryFrom<Bson> for u16 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int32(bson.0);
         match inner {
             bson::Bson::Int32(i) => {
                 if i < (Self::MIN as i32) || i > (Self::MAX as i32) {
@@ -545,23 +1262,45 @@ impl TryFrom<Bson> for u16 {
 impl TryFrom<Bson> for u32 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Int32(i) => Ok(i as u32),
-            _ => Err(bson::de::Error::custom(format!(
-                "invalid variant, expected `Bson::Int32(...)` but found `{}`",
-                inner
-            ))),
-        }
+        let inner = coerce_int32(bson.0);
+        let value = match inner {
+            bson::Bson::Int32(i) if unsigned_wrapping() => return Ok(i as u32),
+            bson::Bson::Int32(i) => i as i64,
+            bson::Bson::Int64(i) => i,
+            _ => {
+                return Err(bson::de::Error::custom(format!(
+                    "invalid variant, expected `Bson::Int32(...)` but found `{}`",
+                    inner
+                )))
+            }
+        };
+        Self::try_from(value).map_err(|_| {
+            bson::de::Error::custom(format!(
+                "invalid value, could not coerce `{}` into an u32",
+                value
+            ))
+        })
     }
 }
 
 impl TryFrom<Bson> for u64 {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int64(bson.0);
         match inner {
-            bson::Bson::Int64(i) => Ok(i as u64),
+            bson::Bson::Int64(i) if unsigned_wrapping() => Ok(i as u64),
+            bson::Bson::Int64(i) => Self::try_from(i).map_err(|_| {
+                bson::de::Error::custom(format!(
+                    "invalid value, could not coerce `{}` into an u64",
+                    i
+                ))
+            }),
+            bson::Bson::Decimal128(d) => decimal128_to_u64(d).ok_or_else(|| {
+                bson::de::Error::custom(format!(
+                    "invalid value, could not coerce `{}` into an u64",
+                    d
+                ))
+            }),
             _ => Err(bson::de::Error::custom(format!(
                 "invalid variant, expected `Bson::Int64(...)` but found `{}`",
                 inner
@@ -573,9 +1312,15 @@ impl TryFrom<Bson> for u64 {
 impl TryFrom<Bson> for usize {
     type Error = bson::de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
+        let inner = coerce_int64(bson.0);
         match inner {
-            bson::Bson::Int64(i) => Ok(i as usize),
+            bson::Bson::Int64(i) if unsigned_wrapping() => Ok(i as usize),
+            bson::Bson::Int64(i) => Self::try_from(i).map_err(|_| {
+                bson::de::Error::custom(format!(
+                    "invalid value, could not coerce `{}` into an usize",
+                    i
+                ))
+            }),
             _ => Err(bson::de::Error::custom(format!(
                 "invalid variant, expected `Bson::Int64(...)` but found `{}`",
                 inner
@@ -599,111 +1344,611 @@ impl TryFrom<Bson> for String {
     }
 }
 
-impl<K, V> TryFrom<Bson> for HashMap<K, V>
+impl TryFrom<Bson> for Cow<'static, str> {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        String::try_from(bson).map(Cow::Owned)
+    }
+}
+
+impl TryFrom<Bson> for PathBuf {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        String::try_from(bson).map(PathBuf::from)
+    }
+}
+
+impl<K, V> TryFrom<Bson> for HashMap<K, V>
+where
+    K: Eq + Hash + TryFrom<String>,
+    K::Error: Into<de::Error>,
+    V: TryFrom<Bson>,
+    V::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Document(d) => {
+                let mut m = HashMap::with_capacity(d.len());
+                for (k, v) in d {
+                    m.insert(
+                        K::try_from(k).map_err(|e| e.into())?,
+                        V::try_from(Bson(v)).map_err(|e| e.into())?,
+                    );
+                }
+                Ok(m)
+            }
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Document(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+impl<T> TryFrom<Bson> for HashSet<T>
+where
+    T: Eq + Hash + TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Array(a) => a
+                .into_iter()
+                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
+                .collect(),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Array(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+impl<K, V> TryFrom<Bson> for BTreeMap<K, V>
+where
+    K: Ord + TryFrom<String>,
+    K::Error: Into<de::Error>,
+    V: TryFrom<Bson>,
+    V::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Document(d) => {
+                let mut m = BTreeMap::new();
+                for (k, v) in d {
+                    m.insert(
+                        K::try_from(k).map_err(|e| e.into())?,
+                        V::try_from(Bson(v)).map_err(|e| e.into())?,
+                    );
+                }
+                Ok(m)
+            }
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Document(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+impl<T> TryFrom<Bson> for BTreeSet<T>
+where
+    T: Ord + TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Array(a) => a
+                .into_iter()
+                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
+                .collect(),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Array(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V> TryFrom<Bson> for indexmap::IndexMap<K, V>
+where
+    K: Eq + Hash + TryFrom<String>,
+    K::Error: Into<de::Error>,
+    V: TryFrom<Bson>,
+    V::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Document(d) => {
+                let mut m = indexmap::IndexMap::with_capacity(d.len());
+                for (k, v) in d {
+                    m.insert(
+                        K::try_from(k).map_err(|e| e.into())?,
+                        V::try_from(Bson(v)).map_err(|e| e.into())?,
+                    );
+                }
+                Ok(m)
+            }
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Document(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<T> TryFrom<Bson> for indexmap::IndexSet<T>
+where
+    T: Eq + Hash + TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Array(a) => a
+                .into_iter()
+                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
+                .collect(),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Array(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+// FIXME: Blanket impls mess us up here we are not allowed to impl the below with current rust,
+// fortunately we can work around this in the derive.
+// https://github.com/rust-lang/rust/issues/31844
+//impl<T> TryFrom<Bson> for Option<T>
+//where
+//    T: TryFrom<Bson>,
+//    T::Error: Into<de::Error>,
+//{
+//    type Error = de::Error;
+//    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+//        let inner = bson.0;
+//        Ok(match inner {
+//            bson::Bson::Null => None,
+//            _ => Some(Bson(inner).try_into()?),
+//        })
+//    }
+//}
+
+impl<T> TryFrom<Bson> for Vec<T>
+where
+    T: TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Array(a) => a
+                .into_iter()
+                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
+                .collect(),
+            // NOTE: This is how `Vec<u8>` is stored, each byte is massaged through an `Int32` so
+            // that it can be read back without its own impl
+            bson::Bson::Binary(b) if is_u8::<T>() => b
+                .bytes
+                .into_iter()
+                .map(|x| T::try_from(Bson(bson::Bson::Int32(x as i32))).map_err(|e| e.into()))
+                .collect(),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Array(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Bson> for chrono::DateTime<chrono::Utc> {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::DateTime(dt) => Ok(dt.into()),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::DateTime(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Bson> for chrono::DateTime<chrono::FixedOffset> {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        chrono::DateTime::<chrono::Utc>::try_from(bson).map(Into::into)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Bson> for chrono::NaiveDateTime {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        chrono::DateTime::<chrono::Utc>::try_from(bson).map(|dt| dt.naive_utc())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Bson> for chrono::NaiveDate {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        chrono::NaiveDateTime::try_from(bson).map(|dt| dt.date())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Bson> for chrono::NaiveTime {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        chrono::NaiveDateTime::try_from(bson).map(|dt| dt.time())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Bson> for time::OffsetDateTime {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::DateTime(dt) => Ok(dt.to_time_0_3()),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::DateTime(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Bson> for time::Date {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        time::OffsetDateTime::try_from(bson).map(|dt| dt.date())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TryFrom<Bson> for uuid::Uuid {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let inner = bson.0;
+        match inner {
+            bson::Bson::Binary(b) => b
+                .to_uuid_with_representation(uuid_representation())
+                .map(Into::into)
+                .map_err(|e| bson::de::Error::custom(e).into()),
+            _ => Err(bson::de::Error::custom(format!(
+                "invalid variant, expected `Bson::Binary(...)` but found `{}`",
+                inner
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<Bson> for url::Url {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        let s = String::try_from(bson)?;
+        url::Url::parse(&s).map_err(|e| {
+            bson::de::Error::custom(format!(
+                "invalid value, could not parse `{}` into a url: {}",
+                s, e
+            ))
+            .into()
+        })
+    }
+}
+
+impl<T> TryFrom<Bson> for VecDeque<T>
+where
+    T: TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        Vec::try_from(bson).map(Into::into)
+    }
+}
+
+impl<T> TryFrom<Bson> for LinkedList<T>
+where
+    T: TryFrom<Bson>,
+    T::Error: Into<de::Error>,
+{
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        Vec::try_from(bson).map(|v| v.into_iter().collect())
+    }
+}
+
+impl<T, const N: usize> TryFrom<Bson> for [T; N]
 where
-    K: Eq + Hash + TryFrom<String>,
-    K::Error: Into<de::Error>,
-    V: TryFrom<Bson>,
-    V::Error: Into<de::Error>,
+    T: TryFrom<Bson>,
+    T::Error: Into<de::Error>,
 {
     type Error = de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Document(d) => {
-                let mut m = HashMap::with_capacity(d.len());
-                for (k, v) in d {
-                    m.insert(
-                        K::try_from(k).map_err(|e| e.into())?,
-                        V::try_from(Bson(v)).map_err(|e| e.into())?,
-                    );
-                }
-                Ok(m)
-            }
-            _ => Err(bson::de::Error::custom(format!(
-                "invalid variant, expected `Bson::Document(...)` but found `{}`",
-                inner
-            ))
-            .into()),
-        }
+        // NOTE: A `[u8; 12]` is stored as an `ObjectId`, so its bytes are read back like an array
+        let bson = match bson.0 {
+            bson::Bson::ObjectId(oid) if N == 12 && is_u8::<T>() => Bson(bson::Bson::Array(
+                oid.bytes()
+                    .iter()
+                    .map(|b| bson::Bson::Int32(*b as i32))
+                    .collect(),
+            )),
+            inner => Bson(inner),
+        };
+        let v: Vec<T> = Vec::try_from(bson)?;
+        let len = v.len();
+        Self::try_from(v).map_err(|_| {
+            bson::de::Error::invalid_length(len, &format!("an array of length {}", N).as_str())
+                .into()
+        })
     }
 }
 
-impl<T> TryFrom<Bson> for HashSet<T>
+#[cfg(feature = "smallvec")]
+impl<A> TryFrom<Bson> for smallvec::SmallVec<A>
 where
-    T: Eq + Hash + TryFrom<Bson>,
-    T::Error: Into<de::Error>,
+    A: smallvec::Array,
+    A::Item: TryFrom<Bson>,
+    <A::Item as TryFrom<Bson>>::Error: Into<de::Error>,
 {
     type Error = de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Array(a) => a
-                .into_iter()
-                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
-                .collect(),
-            _ => Err(bson::de::Error::custom(format!(
-                "invalid variant, expected `Bson::Array(...)` but found `{}`",
-                inner
-            ))
-            .into()),
-        }
+        Vec::try_from(bson).map(Self::from_vec)
     }
 }
 
-// FIXME: Blanket impls mess us up here we are not allowed to impl the below with current rust,
-// fortunately we can work around this in the derive.
-// https://github.com/rust-lang/rust/issues/31844
-//impl<T> TryFrom<Bson> for Option<T>
-//where
-//    T: TryFrom<Bson>,
-//    T::Error: Into<de::Error>,
-//{
-//    type Error = de::Error;
-//    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-//        let inner = bson.0;
-//        Ok(match inner {
-//            bson::Bson::Null => None,
-//            _ => Some(Bson(inner).try_into()?),
-//        })
-//    }
-//}
-
-impl<T> TryFrom<Bson> for Vec<T>
+#[cfg(feature = "arrayvec")]
+impl<T, const CAP: usize> TryFrom<Bson> for arrayvec::ArrayVec<T, CAP>
 where
     T: TryFrom<Bson>,
     T::Error: Into<de::Error>,
 {
     type Error = de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
-        let inner = bson.0;
-        match inner {
-            bson::Bson::Array(a) => a
-                .into_iter()
-                .map(|x| T::try_from(Bson(x)).map_err(|e| e.into()))
-                .collect(),
-            _ => Err(bson::de::Error::custom(format!(
-                "invalid variant, expected `Bson::Array(...)` but found `{}`",
-                inner
-            ))
-            .into()),
+        let v: Vec<T> = Vec::try_from(bson)?;
+        if v.len() > CAP {
+            return Err(bson::de::Error::invalid_length(
+                v.len(),
+                &format!("an array of at most length {}", CAP).as_str(),
+            )
+            .into());
         }
+        Ok(v.into_iter().collect())
     }
 }
 
-#[cfg(feature = "chrono")]
-impl TryFrom<Bson> for chrono::DateTime<chrono::Utc> {
+#[cfg(feature = "bytes")]
+impl TryFrom<Bson> for bytes::Bytes {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        Vec::<u8>::try_from(bson).map(Into::into)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TryFrom<Bson> for bytes::BytesMut {
+    type Error = de::Error;
+    fn try_from(bson: Bson) -> Result<Self, Self::Error> {
+        bytes::Bytes::try_from(bson).map(Into::into)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<Bson> for rust_decimal::Decimal {
     type Error = de::Error;
     fn try_from(bson: Bson) -> Result<Self, Self::Error> {
         let inner = bson.0;
-        match inner {
-            bson::Bson::DateTime(dt) => Ok(dt.into()),
-            _ => Err(bson::de::Error::custom(format!(
-                "invalid variant, expected `Bson::DateTime(...)` but found `{}`",
-                inner
+        let decimal = match inner {
+            bson::Bson::Decimal128(d) => d,
+            _ => {
+                return Err(bson::de::Error::custom(format!(
+                    "invalid variant, expected `Bson::Decimal128(...)` but found `{}`",
+                    inner
+                ))
+                .into())
+            }
+        };
+        let bits = u128::from_le_bytes(decimal.bytes());
+        // NOTE: Infinity, NaN and coefficients larger than 113 bits use the combination field
+        if (bits >> 125) & 0b11 == 0b11 {
+            return Err(bson::de::Error::custom(format!(
+                "decimal `{}` cannot be represented by `rust_decimal::Decimal`",
+                decimal
             ))
-            .into()),
+            .into());
+        }
+        let negative = bits >> 127 == 1;
+        let mut exponent = ((bits >> 113) & 0x3fff) as i32 - DECIMAL128_EXPONENT_BIAS;
+        let mut coefficient = bits & ((1 << 113) - 1);
+        while exponent > 0 {
+            coefficient = match coefficient.checked_mul(10) {
+                Some(c) => c,
+                None => break,
+            };
+            exponent -= 1;
+        }
+        while exponent < -28 && coefficient % 10 == 0 {
+            coefficient /= 10;
+            exponent += 1;
         }
+        let mantissa = i128::try_from(coefficient).ok().filter(|_| exponent <= 0);
+        mantissa
+            .and_then(|m| {
+                let m = if negative { -m } else { m };
+                rust_decimal::Decimal::try_from_i128_with_scale(m, (-exponent) as u32).ok()
+            })
+            .ok_or_else(|| {
+                bson::de::Error::custom(format!(
+                    "decimal `{}` cannot be represented by `rust_decimal::Decimal`",
+                    decimal
+                ))
+                .into()
+            })
+    }
+}
+
+pub mod as_array {
+    //! Converts a `Vec<u8>` to and from an array of integers rather than a `Bson::Binary`, for
+    //! use with `#[bson(with = "mongod::ext::bson::as_array")]`.
+    use std::convert::TryFrom;
+
+    use super::{de, ser, Bson};
+
+    /// Converts the bytes into an array of `Bson::Int32`.
+    pub fn to_bson(value: Vec<u8>) -> Result<bson::Bson, ser::Error> {
+        Ok(bson::Bson::Array(
+            value
+                .into_iter()
+                .map(|x| bson::Bson::Int32(x as i32))
+                .collect(),
+        ))
+    }
+
+    /// Converts an array of `Bson::Int32` into bytes.
+    pub fn from_bson(bson: bson::Bson) -> Result<Vec<u8>, de::Error> {
+        Vec::try_from(Bson(bson))
+    }
+}
+
+pub mod as_decimal128 {
+    //! Converts a `u64` to and from a `Bson::Decimal128` rather than a `Bson::Int64`, for use
+    //! with `#[bson(with = "mongod::ext::bson::as_decimal128")]`, so that values too large for a
+    //! `Bson::Int64` can be stored.
+    //!
+    //! ```
+    //! # use mongod_derive::Bson;
+    //! use std::convert::TryFrom;
+    //!
+    //! #[derive(Bson)]
+    //! struct Counter {
+    //!     #[bson(with = "mongod::ext::bson::as_decimal128")]
+    //!     total: u64,
+    //! }
+    //!
+    //! let bson = mongod::bson::Bson::try_from(Counter { total: u64::MAX }).unwrap();
+    //! assert_eq!(
+    //!     bson.as_document().unwrap().get("total").unwrap().to_string(),
+    //!     u64::MAX.to_string()
+    //! );
+    //! ```
+    use std::convert::TryFrom;
+
+    use super::{de, ser, Bson, DECIMAL128_EXPONENT_BIAS};
+
+    /// Converts the integer into a `Bson::Decimal128`.
+    pub fn to_bson(value: u64) -> Result<bson::Bson, ser::Error> {
+        let bits = ((DECIMAL128_EXPONENT_BIAS as u128) << 113) | value as u128;
+        Ok(bson::Bson::Decimal128(bson::Decimal128::from_bytes(
+            bits.to_le_bytes(),
+        )))
+    }
+
+    /// Converts a `Bson::Decimal128`, or a `Bson::Int64`, into an integer.
+    pub fn from_bson(bson: bson::Bson) -> Result<u64, de::Error> {
+        Ok(u64::try_from(Bson(bson))?)
+    }
+}
+
+pub mod coerce_numbers {
+    //! Converts a number from any of `Bson::Int32`, `Bson::Int64` & `Bson::Double`, as long as
+    //! the value converts losslessly, for use with
+    //! `#[bson(with = "mongod::ext::bson::coerce_numbers")]`. Unlike [`set_numeric_coercion`]
+    //! this only applies to the fields that use it.
+    //!
+    //! [`set_numeric_coercion`]: super::set_numeric_coercion
+    //!
+    //! ```
+    //! # use mongod_derive::Bson;
+    //! use std::convert::TryFrom;
+    //!
+    //! #[derive(Bson)]
+    //! struct Reading {
+    //!     #[bson(with = "mongod::ext::bson::coerce_numbers")]
+    //!     value: i32,
+    //! }
+    //!
+    //! let bson = mongod::bson::bson!({ "value": 1.0 });
+    //! let reading = Reading::try_from(bson).unwrap();
+    //! assert_eq!(reading.value, 1);
+    //! ```
+    use std::convert::TryFrom;
+
+    use super::{de, ser, to_double, to_int32, to_int64, Bson};
+
+    /// The numbers that can be coerced.
+    pub trait Number: TryFrom<Bson, Error = bson::de::Error> {
+        #[doc(hidden)]
+        fn coerce(bson: bson::Bson) -> bson::Bson;
+    }
+
+    macro_rules! number {
+        ($ty:ty, $coerce:expr) => {
+            impl Number for $ty {
+                fn coerce(bson: bson::Bson) -> bson::Bson {
+                    $coerce(bson)
+                }
+            }
+        };
+    }
+    number!(f32, |b| to_double(b, 24));
+    number!(f64, |b| to_double(b, 53));
+    number!(i8, to_int32);
+    number!(i16, to_int32);
+    number!(i32, to_int32);
+    number!(i64, to_int64);
+    number!(isize, to_int64);
+    number!(u8, to_int32);
+    number!(u16, to_int32);
+    number!(u32, to_int32);
+    number!(u64, to_int64);
+    number!(usize, to_int64);
+
+    /// Converts the number into `Bson` as it would be without this module.
+    pub fn to_bson<T>(value: T) -> Result<bson::Bson, ser::Error>
+    where
+        T: Number,
+        Bson: TryFrom<T>,
+        <Bson as TryFrom<T>>::Error: Into<ser::Error>,
+    {
+        Ok(Bson::try_from(value).map_err(Into::into)?.0)
+    }
+
+    /// Converts any numeric `Bson` into the number, should it convert losslessly.
+    pub fn from_bson<T: Number>(bson: bson::Bson) -> Result<T, de::Error> {
+        Ok(T::try_from(Bson(T::coerce(bson)))?)
     }
 }
 
@@ -714,6 +1959,9 @@ mod test {
     #[cfg(feature = "chrono")]
     use chrono::Timelike;
 
+    // NOTE: The process wide settings, i.e. `set_unsigned_wrapping`, are tested in
+    // `tests/ext_bson.rs`, as they would otherwise race with the tests here
+
     #[test]
     fn bool_to_bson() {
         let v: bool = true;
@@ -773,7 +2021,7 @@ mod test {
     #[test]
     fn u8_to_bson() {
         let v: u8 = 0;
-        let b = Bson::from(v).0;
+        let b = Bson::try_from(v).unwrap().0;
         assert_eq!(b, bson::Bson::Int32(0));
     }
 
@@ -794,8 +2042,39 @@ mod test {
     #[test]
     fn u64_to_bson() {
         let v: u64 = 0;
-        let b = Bson::from(v).0;
+        let b = Bson::try_from(v).unwrap().0;
         assert_eq!(b, bson::Bson::Int64(0));
+        assert!(Bson::try_from(i64::MAX as u64 + 1).is_err());
+    }
+
+    #[test]
+    fn non_zero_to_bson() {
+        let b = Bson::from(NonZeroU32::new(7).unwrap()).0;
+        assert_eq!(b, bson::Bson::Int32(7));
+        let b = Bson::from(NonZeroI64::new(-7).unwrap()).0;
+        assert_eq!(b, bson::Bson::Int64(-7));
+    }
+
+    #[test]
+    fn unsigned_roundtrip() {
+        assert_eq!(Bson::from(u32::MAX).0, bson::Bson::Int64(u32::MAX as i64));
+        assert_eq!(u32::try_from(Bson::from(u32::MAX)).unwrap(), u32::MAX);
+        let b = as_decimal128::to_bson(u64::MAX).unwrap();
+        assert_eq!(b.to_string(), u64::MAX.to_string());
+        assert_eq!(as_decimal128::from_bson(b).unwrap(), u64::MAX);
+
+        assert!(u32::try_from(Bson(bson::Bson::Int32(-1))).is_err());
+        assert!(u32::try_from(Bson(bson::Bson::Int64(u32::MAX as i64 + 1))).is_err());
+        assert!(u64::try_from(Bson(bson::Bson::Int64(-1))).is_err());
+        assert!(usize::try_from(Bson(bson::Bson::Int64(-1))).is_err());
+        let d = bson::Decimal128::from_bytes(((6176u128 << 113) | (1 << 64)).to_le_bytes());
+        assert!(u64::try_from(Bson(bson::Bson::Decimal128(d))).is_err());
+        // 15E+2
+        let d = bson::Decimal128::from_bytes(((6178u128 << 113) | 15).to_le_bytes());
+        assert_eq!(
+            u64::try_from(Bson(bson::Bson::Decimal128(d))).unwrap(),
+            1500
+        );
     }
 
     #[test]
@@ -812,6 +2091,41 @@ mod test {
         assert_eq!(b, bson::Bson::String(v));
     }
 
+    #[test]
+    fn cow_to_bson() {
+        let b = Bson::from(Cow::Borrowed("abcd")).0;
+        assert_eq!(b, bson::Bson::String("abcd".to_owned()));
+        let b = Bson::from(Cow::<str>::Owned("abcd".to_owned())).0;
+        assert_eq!(b, bson::Bson::String("abcd".to_owned()));
+    }
+
+    #[test]
+    fn path_to_bson() {
+        let b = Bson::try_from(Path::new("/tmp/abcd")).unwrap().0;
+        assert_eq!(b, bson::Bson::String("/tmp/abcd".to_owned()));
+        let b = Bson::try_from(PathBuf::from("abcd")).unwrap().0;
+        assert_eq!(b, bson::Bson::String("abcd".to_owned()));
+    }
+
+    #[test]
+    fn shared_to_bson() {
+        let b = Bson::try_from(Rc::new("abcd".to_owned())).unwrap().0;
+        assert_eq!(b, bson::Bson::String("abcd".to_owned()));
+        let v = Arc::new(vec![1_i32, 2]);
+        let b = Bson::try_from(Arc::clone(&v)).unwrap().0;
+        assert_eq!(b, bson::Bson::Array(vec![1.into(), 2.into()]));
+        assert_eq!(*v, vec![1, 2]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path_to_bson() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(std::ffi::OsStr::from_bytes(b"/tmp/\xff"));
+        assert!(Bson::try_from(path).is_err());
+    }
+
     #[test]
     fn hashmap_to_bson() {
         let mut v: HashMap<String, String> = HashMap::new();
@@ -836,10 +2150,115 @@ mod test {
         assert_eq!(v.len(), l);
     }
 
+    #[test]
+    fn btreemap_to_bson() {
+        let mut v: BTreeMap<String, i32> = BTreeMap::new();
+        v.insert("b".to_owned(), 2);
+        v.insert("a".to_owned(), 1);
+        let b = Bson::try_from(v).unwrap().0;
+        assert_eq!(b, bson::bson!({ "a": 1, "b": 2 }));
+    }
+
+    #[test]
+    fn btreeset_to_bson() {
+        let v: BTreeSet<String> = vec!["b".to_owned(), "a".to_owned()].into_iter().collect();
+        let b = Bson::try_from(v).unwrap().0;
+        assert_eq!(b, bson::bson!(["a", "b"]));
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn indexmap_roundtrip() {
+        let mut v: indexmap::IndexMap<String, i32> = indexmap::IndexMap::new();
+        v.insert("b".to_owned(), 2);
+        v.insert("a".to_owned(), 1);
+        let b = Bson::try_from(v.clone()).unwrap().0;
+        assert_eq!(b, bson::bson!({ "b": 2, "a": 1 }));
+        let m = indexmap::IndexMap::<String, i32>::try_from(Bson(b)).unwrap();
+        assert_eq!(m.keys().collect::<Vec<_>>(), vec!["b", "a"]);
+
+        let v: indexmap::IndexSet<String> = vec!["b".to_owned(), "a".to_owned(), "b".to_owned()]
+            .into_iter()
+            .collect();
+        let b = Bson::try_from(v).unwrap().0;
+        assert_eq!(b, bson::bson!(["b", "a"]));
+        let s = indexmap::IndexSet::<String>::try_from(Bson(b)).unwrap();
+        assert_eq!(s.into_iter().collect::<Vec<_>>(), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn vecdeque_to_bson() {
+        let v: VecDeque<i32> = vec![1, 2].into();
+        assert_eq!(Bson::from(v).0, bson::bson!([1, 2]));
+        let l: LinkedList<i32> = vec![1, 2].into_iter().collect();
+        assert_eq!(Bson::from(l).0, bson::bson!([1, 2]));
+    }
+
+    #[test]
+    fn array_to_bson() {
+        assert_eq!(Bson::from([1u16, 2, 3]).0, bson::bson!([1, 2, 3]));
+        assert_eq!(Bson::from([0.5f64; 2]).0, bson::bson!([0.5, 0.5]));
+        let oid = bson::oid::ObjectId::new();
+        assert_eq!(Bson::from(oid.bytes()).0, bson::Bson::ObjectId(oid));
+        assert_eq!(Bson::from([1i32; 12]).0, bson::Bson::from(vec![1; 12]));
+    }
+
+    #[test]
+    fn bytes_to_bson() {
+        let binary = bson::Bson::Binary(bson::Binary {
+            subtype: bson::spec::BinarySubtype::Generic,
+            bytes: vec![1, 2, 3],
+        });
+        assert_eq!(Bson::from(vec![1u8, 2, 3]).0, binary);
+        assert_eq!(Bson::from(&[1u8, 2, 3][..]).0, binary);
+        assert_eq!(Bson::from([1u8, 2, 3]).0, binary);
+        assert_eq!(
+            as_array::to_bson(vec![1, 2, 3]).unwrap(),
+            bson::bson!([1, 2, 3])
+        );
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_crate_roundtrip() {
+        let v = bytes::Bytes::from_static(b"abcd");
+        let b = Bson::from(v.clone()).0;
+        assert_eq!(b, Bson::from(b"abcd".to_vec()).0);
+        assert_eq!(bytes::Bytes::try_from(Bson(b.clone())).unwrap(), v);
+
+        let v = bytes::BytesMut::from(&b"abcd"[..]);
+        assert_eq!(Bson::from(v.clone()).0, b);
+        assert_eq!(bytes::BytesMut::try_from(Bson(b)).unwrap(), v);
+        assert!(bytes::Bytes::try_from(Bson(bson::Bson::Int32(0))).is_err());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec_roundtrip() {
+        let v: smallvec::SmallVec<[i32; 2]> = smallvec::smallvec![1, 2, 3];
+        let b = Bson::from(v.clone()).0;
+        assert_eq!(b, bson::bson!([1, 2, 3]));
+        assert_eq!(
+            smallvec::SmallVec::<[i32; 2]>::try_from(Bson(b)).unwrap(),
+            v
+        );
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn arrayvec_roundtrip() {
+        let v: arrayvec::ArrayVec<i32, 3> = vec![1, 2].into_iter().collect();
+        let b = Bson::from(v.clone()).0;
+        assert_eq!(b, bson::bson!([1, 2]));
+        assert_eq!(arrayvec::ArrayVec::<i32, 3>::try_from(Bson(b)).unwrap(), v);
+        let err = arrayvec::ArrayVec::<i32, 1>::try_from(Bson(bson::bson!([1, 2]))).unwrap_err();
+        assert!(err.to_string().contains("at most length 1"));
+    }
+
     #[test]
     fn vec_to_bson() {
         let v: Vec<String> = vec!["abcd".to_owned()];
-        let b = Bson::try_from(v.clone()).unwrap().0;
+        let b = Bson::from(v.clone()).0;
         let mut l = 0;
         if let bson::Bson::Array(arr) = b {
             l = arr.len();
@@ -851,7 +2270,7 @@ mod test {
     #[test]
     fn chrono_to_bson() {
         let v: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
-        let b = Bson::try_from(v).unwrap().0;
+        let b = Bson::from(v).0;
         assert_eq!(b, bson::Bson::DateTime(bson::DateTime::from_chrono(v)));
     }
 
@@ -859,7 +2278,7 @@ mod test {
     fn bson_to_bool() {
         let b = Bson(bson::Bson::Boolean(true));
         let v = bool::try_from(b).unwrap();
-        assert_eq!(v, true);
+        assert!(v);
     }
 
     #[test]
@@ -939,6 +2358,42 @@ mod test {
         assert_eq!(v, 0);
     }
 
+    #[test]
+    fn bson_to_non_zero() {
+        let v = NonZeroU8::try_from(Bson(bson::Bson::Int32(7))).unwrap();
+        assert_eq!(v.get(), 7);
+        let err = NonZeroI64::try_from(Bson(bson::Bson::Int64(0))).unwrap_err();
+        assert!(err.to_string().contains("NonZeroI64"));
+        assert!(NonZeroU8::try_from(Bson(bson::Bson::Int32(256))).is_err());
+    }
+
+    #[test]
+    fn bson_to_numeric_strict() {
+        assert!(i32::try_from(Bson(bson::Bson::Int64(1))).is_err());
+        assert!(f64::try_from(Bson(bson::Bson::Int32(1))).is_err());
+    }
+
+    #[test]
+    fn coerce_numbers_roundtrip() {
+        let from = coerce_numbers::from_bson::<i32>;
+        assert_eq!(from(bson::Bson::Int64(1)).unwrap(), 1);
+        assert_eq!(from(bson::Bson::Double(2.0)).unwrap(), 2);
+        assert!(from(bson::Bson::Int64(i64::MAX)).is_err());
+        assert!(from(bson::Bson::Double(1.5)).is_err());
+        assert!(from(bson::Bson::String("1".to_owned())).is_err());
+        assert_eq!(
+            coerce_numbers::from_bson::<f64>(bson::Bson::Int64(5)).unwrap(),
+            5.0
+        );
+        assert!(coerce_numbers::from_bson::<f32>(bson::Bson::Int32(i32::MAX)).is_err());
+        assert_eq!(
+            coerce_numbers::from_bson::<u64>(bson::Bson::Double(4.0)).unwrap(),
+            4
+        );
+        assert_eq!(coerce_numbers::to_bson(1u8).unwrap(), bson::Bson::Int32(1));
+        assert!(coerce_numbers::to_bson(u64::MAX).is_err());
+    }
+
     #[test]
     fn bson_to_string() {
         let b = Bson(bson::Bson::String("foo".to_owned()));
@@ -946,6 +2401,21 @@ mod test {
         assert_eq!(v, String::from("foo"));
     }
 
+    #[test]
+    fn bson_to_cow() {
+        let b = Bson(bson::Bson::String("foo".to_owned()));
+        let v = Cow::<str>::try_from(b).unwrap();
+        assert_eq!(v, "foo");
+        assert!(Cow::<str>::try_from(Bson(bson::Bson::Int32(0))).is_err());
+    }
+
+    #[test]
+    fn bson_to_path() {
+        let b = Bson(bson::Bson::String("/tmp/abcd".to_owned()));
+        let v = PathBuf::try_from(b).unwrap();
+        assert_eq!(v, Path::new("/tmp/abcd"));
+    }
+
     #[test]
     fn bson_to_hashmap() {
         let mut doc: bson::Document = bson::Document::new();
@@ -964,6 +2434,59 @@ mod test {
         assert_eq!(s.len(), 1);
     }
 
+    #[test]
+    fn bson_to_btreemap() {
+        let b = Bson(bson::bson!({ "b": "bar", "a": "foo" }));
+        let m: BTreeMap<String, String> = BTreeMap::try_from(b).unwrap();
+        assert_eq!(m.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert!(BTreeMap::<String, i32>::try_from(Bson(bson::bson!(["a"]))).is_err());
+    }
+
+    #[test]
+    fn bson_to_btreeset() {
+        let b = Bson(bson::bson!(["abcd", "abcd", "a"]));
+        let s: BTreeSet<String> = BTreeSet::try_from(b).unwrap();
+        assert_eq!(s.into_iter().collect::<Vec<_>>(), vec!["a", "abcd"]);
+    }
+
+    #[test]
+    fn bson_to_vecdeque() {
+        let b = Bson(bson::bson!([1, 2]));
+        let v: VecDeque<i32> = VecDeque::try_from(b).unwrap();
+        assert_eq!(v, VecDeque::from(vec![1, 2]));
+        let b = Bson(bson::bson!([1, 2]));
+        let l: LinkedList<i32> = LinkedList::try_from(b).unwrap();
+        assert_eq!(l.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn bson_to_array() {
+        let a: [u8; 3] = <[u8; 3]>::try_from(Bson(bson::bson!([1, 2, 3]))).unwrap();
+        assert_eq!(a, [1, 2, 3]);
+        let err = <[u8; 2]>::try_from(Bson(bson::bson!([1, 2, 3]))).unwrap_err();
+        assert!(err.to_string().contains("an array of length 2"));
+        assert!(<[u8; 2]>::try_from(Bson(bson::bson!({}))).is_err());
+        let oid = bson::oid::ObjectId::new();
+        let b = Bson(bson::Bson::ObjectId(oid));
+        assert_eq!(<[u8; 12]>::try_from(b).unwrap(), oid.bytes());
+        assert!(<[u8; 11]>::try_from(Bson(bson::Bson::ObjectId(oid))).is_err());
+        assert!(<[i32; 12]>::try_from(Bson(bson::Bson::ObjectId(oid))).is_err());
+    }
+
+    #[test]
+    fn bson_to_bytes() {
+        let b = Bson::from(vec![1u8, 2, 3]);
+        assert_eq!(Vec::<u8>::try_from(b).unwrap(), vec![1, 2, 3]);
+        let b = Bson::from([1u8, 2, 3]);
+        assert_eq!(<[u8; 3]>::try_from(b).unwrap(), [1, 2, 3]);
+        let b = Bson(bson::bson!([1, 2, 3]));
+        assert_eq!(Vec::<u8>::try_from(b).unwrap(), vec![1, 2, 3]);
+        let b = bson::bson!([1, 2, 3]);
+        assert_eq!(as_array::from_bson(b).unwrap(), vec![1, 2, 3]);
+        assert!(Vec::<i32>::try_from(Bson::from(vec![1u8, 2, 3])).is_err());
+        assert!(<[i8; 3]>::try_from(Bson::from([1u8, 2, 3])).is_err());
+    }
+
     #[test]
     fn bson_to_vec() {
         let b = Bson(bson::Bson::Array(vec![bson::Bson::String(
@@ -973,6 +2496,26 @@ mod test {
         assert_eq!(s.len(), 1);
     }
 
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn ordered_float_roundtrip() {
+        use ordered_float::{NotNan, OrderedFloat};
+
+        let b = Bson::from(OrderedFloat(1.5f64)).0;
+        assert_eq!(b, bson::Bson::Double(1.5));
+        assert_eq!(
+            OrderedFloat::<f64>::try_from(Bson(b.clone())).unwrap(),
+            OrderedFloat(1.5)
+        );
+        assert_eq!(Bson::from(NotNan::new(1.5f32).unwrap()).0, b);
+        assert_eq!(
+            NotNan::<f32>::try_from(Bson(b)).unwrap(),
+            NotNan::new(1.5).unwrap()
+        );
+        let err = NotNan::<f64>::try_from(Bson(bson::Bson::Double(f64::NAN))).unwrap_err();
+        assert!(err.to_string().contains("NotNan<f64>"));
+    }
+
     #[cfg(feature = "chrono")]
     #[test]
     fn bson_to_chrono() {
@@ -980,7 +2523,149 @@ mod test {
         // NOTE: Bson truncates nanoseconds...
         v = v.with_nanosecond(0).unwrap();
         let b = Bson(bson::Bson::DateTime(bson::DateTime::from_chrono(v)));
-        let dt = chrono::DateTime::try_from(b).unwrap();
+        let dt: chrono::DateTime<chrono::Utc> = chrono::DateTime::try_from(b).unwrap();
+        assert_eq!(dt, v);
+    }
+
+    #[test]
+    fn decimal128_to_bson() {
+        let v = bson::Decimal128::from_bytes([1; 16]);
+        let b = Bson::from(v).0;
+        assert_eq!(b, bson::Bson::Decimal128(v));
+        assert_eq!(bson::Decimal128::try_from(Bson(b)).unwrap(), v);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn rust_decimal_roundtrip() {
+        use std::str::FromStr;
+
+        for s in &[
+            "0",
+            "1.50",
+            "-12345.6789",
+            "79228162514264337593543950335",
+            "0.000001",
+        ] {
+            let v = rust_decimal::Decimal::from_str(s).unwrap();
+            let b = Bson::from(v).0;
+            match &b {
+                bson::Bson::Decimal128(d) => assert_eq!(&d.to_string(), s),
+                _ => panic!("decimal should be decimal128"),
+            }
+            let d = rust_decimal::Decimal::try_from(Bson(b)).unwrap();
+            assert_eq!(d, v);
+            assert_eq!(d.scale(), v.scale());
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn bson_to_rust_decimal() {
+        use std::str::FromStr;
+
+        let bytes = |bits: u128| {
+            Bson(bson::Bson::Decimal128(bson::Decimal128::from_bytes(
+                bits.to_le_bytes(),
+            )))
+        };
+        // 15E+2
+        let d = rust_decimal::Decimal::try_from(bytes((6178 << 113) | 15)).unwrap();
+        assert_eq!(d, rust_decimal::Decimal::from(1500));
+        // 100E-30
+        let d = rust_decimal::Decimal::try_from(bytes((6146 << 113) | 100)).unwrap();
+        assert_eq!(
+            d,
+            rust_decimal::Decimal::from_str("0.0000000000000000000000000001").unwrap()
+        );
+        // 1E-29
+        assert!(rust_decimal::Decimal::try_from(bytes((6147 << 113) | 1)).is_err());
+        // Infinity
+        assert!(rust_decimal::Decimal::try_from(bytes(0b11110 << 122)).is_err());
+        assert!(rust_decimal::Decimal::try_from(Bson(bson::Bson::Double(1.0))).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_naive_roundtrip() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 3, 14).unwrap();
+        let time = chrono::NaiveTime::from_hms_milli_opt(15, 9, 26, 535).unwrap();
+        let expected = |millis| bson::Bson::DateTime(bson::DateTime::from_millis(millis));
+
+        let b = Bson::from(date).0;
+        assert_eq!(b, expected(1_615_680_000_000));
+        assert_eq!(chrono::NaiveDate::try_from(Bson(b)).unwrap(), date);
+
+        let b = Bson::from(time).0;
+        assert_eq!(b, expected(54_566_535));
+        assert_eq!(chrono::NaiveTime::try_from(Bson(b)).unwrap(), time);
+
+        let b = Bson::from(date.and_time(time)).0;
+        assert_eq!(b, expected(1_615_734_566_535));
+        assert_eq!(
+            chrono::NaiveDateTime::try_from(Bson(b)).unwrap(),
+            date.and_time(time)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_fixed_offset_to_bson() {
+        let v = chrono::DateTime::parse_from_rfc3339("2021-03-14T17:09:26.535+02:00").unwrap();
+        let b = Bson::from(v).0;
+        assert_eq!(
+            b,
+            bson::Bson::DateTime(bson::DateTime::from_millis(1_615_734_566_535))
+        );
+        let dt = chrono::DateTime::<chrono::FixedOffset>::try_from(Bson(b)).unwrap();
+        assert_eq!(dt, v);
+        assert_eq!(dt.offset().local_minus_utc(), 0);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn bson_to_time() {
+        let v = time::OffsetDateTime::now_utc();
+        // NOTE: Bson truncates to milliseconds...
+        let v = v.replace_millisecond(v.millisecond()).unwrap();
+        let b = Bson::from(v).0;
+        assert_eq!(b, bson::Bson::DateTime(bson::DateTime::from_time_0_3(v)));
+        let dt = time::OffsetDateTime::try_from(Bson(b)).unwrap();
         assert_eq!(dt, v);
+
+        let v = time::Date::from_calendar_date(2021, time::Month::March, 14).unwrap();
+        let b = Bson::from(v).0;
+        assert_eq!(
+            b,
+            bson::Bson::DateTime(bson::DateTime::from_millis(1_615_680_000_000))
+        );
+        assert_eq!(time::Date::try_from(Bson(b)).unwrap(), v);
+        assert!(time::Date::try_from(Bson(bson::Bson::Int64(0))).is_err());
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_roundtrip() {
+        let v = url::Url::parse("https://example.com/a?b=c").unwrap();
+        let b = Bson::from(v.clone()).0;
+        assert_eq!(
+            b,
+            bson::Bson::String("https://example.com/a?b=c".to_owned())
+        );
+        assert_eq!(url::Url::try_from(Bson(b)).unwrap(), v);
+        assert!(url::Url::try_from(Bson(bson::Bson::String("example".to_owned()))).is_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_roundtrip() {
+        let v = uuid::Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+
+        let b = Bson::from(v).0;
+        match &b {
+            bson::Bson::Binary(b) => assert_eq!(b.subtype, bson::spec::BinarySubtype::Uuid),
+            _ => panic!("uuid should be binary"),
+        }
+        assert_eq!(uuid::Uuid::try_from(Bson(b)).unwrap(), v);
     }
 }
@@ -0,0 +1,160 @@
+//! Helpers for rendering to and parsing from [Extended JSON][extjson].
+//!
+//! These render the exact documents that would be sent to mongo, which makes them useful for
+//! debugging, writing fixtures and reading the output of `mongoexport`.
+//!
+//! [extjson]: https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/
+//!
+//! # Examples
+//!
+//! Round tripping a document through relaxed Extended JSON.
+//!
+//! ```
+//! use mongod::bson::doc;
+//! use mongod::ext::json::{self, Mode};
+//!
+//! let doc = doc! { "name": "foo", "age": 42_i64 };
+//! let s = json::document_to_string(doc.clone(), Mode::Relaxed);
+//! assert_eq!(s, r#"{"name":"foo","age":42}"#);
+//!
+//! let s = json::document_to_string(doc.clone(), Mode::Canonical);
+//! assert_eq!(s, r#"{"name":"foo","age":{"$numberLong":"42"}}"#);
+//! assert_eq!(json::document_from_str(&s).unwrap(), doc);
+//! ```
+
+use std::convert::TryFrom;
+
+use bson::{Bson, Document};
+
+use crate::collection::Collection;
+use crate::error::Error;
+use crate::filter::Filter;
+use crate::update::Update;
+
+/// The format of the Extended JSON to render.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Preserves the type information of every value, i.e. `{"$numberLong": "42"}`.
+    Canonical,
+    /// Prefers native JSON values where possible, as output by `mongoexport` by default.
+    Relaxed,
+}
+
+/// Renders a `Document` as Extended JSON.
+pub fn document_to_string(document: Document, mode: Mode) -> String {
+    let bson = Bson::Document(document);
+    match mode {
+        Mode::Canonical => bson.into_canonical_extjson(),
+        Mode::Relaxed => bson.into_relaxed_extjson(),
+    }
+    .to_string()
+}
+
+/// Parses a `Document` from either canonical or relaxed Extended JSON.
+///
+/// # Errors
+///
+/// This function errors if the string is not a JSON object, or if it contains malformed Extended
+/// JSON.
+pub fn document_from_str(s: &str) -> crate::Result<Document> {
+    let map: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(s).map_err(Error::invalid_document)?;
+    Document::try_from(map).map_err(Error::invalid_document)
+}
+
+/// Renders a `Collection` as Extended JSON.
+pub fn collection_to_string<C: Collection>(collection: C, mode: Mode) -> crate::Result<String> {
+    Ok(document_to_string(collection.into_document()?, mode))
+}
+
+/// Parses a `Collection` from either canonical or relaxed Extended JSON.
+pub fn collection_from_str<C: Collection>(s: &str) -> crate::Result<C> {
+    C::from_document(document_from_str(s)?)
+}
+
+/// Parses `Collection`s from newline delimited Extended JSON, as output by `mongoexport`.
+///
+/// Blank lines are skipped.
+pub fn collection_from_lines<C: Collection>(s: &str) -> crate::Result<Vec<C>> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(collection_from_str)
+        .collect()
+}
+
+/// Renders a `Filter` as Extended JSON.
+pub fn filter_to_string<F: Filter>(filter: F, mode: Mode) -> crate::Result<String> {
+    Ok(document_to_string(filter.into_document()?, mode))
+}
+
+/// Renders an `Update`, including `Updates`, as Extended JSON.
+pub fn update_to_string<U: Update>(update: U, mode: Mode) -> crate::Result<String> {
+    Ok(document_to_string(update.into_document()?, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bson::oid::ObjectId;
+
+    struct User {
+        id: ObjectId,
+        name: String,
+    }
+
+    impl Collection for User {
+        const COLLECTION: &'static str = "users";
+
+        fn from_document(mut document: Document) -> crate::Result<Self> {
+            Ok(User {
+                id: document
+                    .get_object_id("_id")
+                    .map_err(Error::invalid_document)?,
+                name: document
+                    .remove("name")
+                    .and_then(|name| name.as_str().map(ToOwned::to_owned))
+                    .ok_or_else(|| Error::invalid_document("missing name"))?,
+            })
+        }
+
+        fn into_document(self) -> crate::Result<Document> {
+            Ok(doc! { "_id": self.id, "name": self.name })
+        }
+    }
+
+    #[test]
+    fn collection_roundtrip() {
+        let id = ObjectId::parse_str("5f2b9a8e0000000000000000").unwrap();
+        let user = User {
+            id,
+            name: "foo".to_owned(),
+        };
+        let s = collection_to_string(user, Mode::Relaxed).unwrap();
+        assert_eq!(
+            s,
+            r#"{"_id":{"$oid":"5f2b9a8e0000000000000000"},"name":"foo"}"#
+        );
+        let user: User = collection_from_str(&s).unwrap();
+        assert_eq!(user.id, id);
+        assert_eq!(user.name, "foo");
+    }
+
+    #[test]
+    fn collection_lines() {
+        let lines = r#"{"_id":{"$oid":"5f2b9a8e0000000000000000"},"name":"foo"}
+
+{"_id":{"$oid":"5f2b9a8e0000000000000001"},"name":"bar"}
+"#;
+        let users: Vec<User> = collection_from_lines(lines).unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[1].name, "bar");
+        assert!(collection_from_lines::<User>(r#"{"name":"foo"}"#).is_err());
+    }
+
+    #[test]
+    fn invalid_document() {
+        assert!(document_from_str("[]").is_err());
+        assert!(document_from_str(r#"{"a":{"$numberLong":1}}"#).is_err());
+    }
+}
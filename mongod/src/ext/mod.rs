@@ -4,3 +4,4 @@
 //!
 //! There is generally very little reason to be here. These exist to assist with `mongo-derive`.
 pub mod bson;
+pub mod json;
@@ -1,34 +1,153 @@
 use std::convert::{TryFrom, TryInto};
+use std::fmt::Debug;
+use std::ops::Range;
 
 use bson::{Bson, Document};
 
 use crate::error::Error;
+use crate::expr::Expr;
 use crate::ext;
+use crate::field::Field;
+use crate::geo::{Point, Shape};
+
+/// The BSON types that can be matched against using [`Comparator::Type`](./enum.Comparator.html#variant.Type).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BsonType {
+    /// An array.
+    Array,
+    /// Binary data.
+    BinData,
+    /// A boolean.
+    Bool,
+    /// A UTC datetime.
+    Date,
+    /// A 128-bit decimal.
+    Decimal,
+    /// A 64-bit floating point.
+    Double,
+    /// A 32-bit integer.
+    Int,
+    /// JavaScript code.
+    JavaScript,
+    /// A 64-bit integer.
+    Long,
+    /// The max key.
+    MaxKey,
+    /// The min key.
+    MinKey,
+    /// A null value.
+    Null,
+    /// Any numeric type, being a `Decimal`, `Double`, `Int` or `Long`.
+    Number,
+    /// An embedded document.
+    Object,
+    /// An object id.
+    ObjectId,
+    /// A regular expression.
+    Regex,
+    /// A string.
+    String,
+    /// A timestamp.
+    Timestamp,
+}
+
+impl BsonType {
+    /// Returns the alias used for the type by MongoDB.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BsonType::Array => "array",
+            BsonType::BinData => "binData",
+            BsonType::Bool => "bool",
+            BsonType::Date => "date",
+            BsonType::Decimal => "decimal",
+            BsonType::Double => "double",
+            BsonType::Int => "int",
+            BsonType::JavaScript => "javascript",
+            BsonType::Long => "long",
+            BsonType::MaxKey => "maxKey",
+            BsonType::MinKey => "minKey",
+            BsonType::Null => "null",
+            BsonType::Number => "number",
+            BsonType::Object => "object",
+            BsonType::ObjectId => "objectId",
+            BsonType::Regex => "regex",
+            BsonType::String => "string",
+            BsonType::Timestamp => "timestamp",
+        }
+    }
+}
 
 /// The BSON comparators for comparison of different BSON type values
+#[derive(Debug)]
 pub enum Comparator<T>
 where
     T: TryInto<ext::bson::Bson>,
     T::Error: Into<ext::bson::ser::Error>,
 {
+    /// Matches numeric values where all of the bits in the specified bitmask are clear.
+    BitsAllClear(T),
+    /// Matches numeric values where all of the bits in the specified bitmask are set.
+    BitsAllSet(T),
+    /// Matches numeric values where any of the bits in the specified bitmask are clear.
+    BitsAnyClear(T),
+    /// Matches numeric values where any of the bits in the specified bitmask are set.
+    BitsAnySet(T),
     /// Matches values that are equal to a specified value.
     Eq(T),
+    /// Matches documents that have (`true`) or do not have (`false`) the field, unlike `Null`
+    /// which also matches fields set to null.
+    Exists(bool),
+    /// Matches geospatial values that are within the specified shape.
+    GeoWithin(Shape),
     /// Matches values that are greater than a specified value.
     Gt(T),
     /// Matches values that are greater than or equal to a specified value.
     Gte(T),
     /// Matches any of the values specified in an array.
     In(Vec<T>),
+    /// Matches documents where the field is missing.
+    IsMissing,
+    /// Matches documents where the field is present and explicitly set to null.
+    IsNull,
+    /// Matches documents where the field is present and not null.
+    IsSet,
     /// Matches values that are less than a specified value.
     Lt(T),
     /// Matches values that are less than or equal to a specified value.
     Lte(T),
     /// Matches all values that are not equal to a specified value.
     Ne(T),
+    /// Matches geospatial values in order of proximity to a point, optionally limited to within
+    /// `max_distance` meters.
+    ///
+    /// This requires a geospatial index on the field.
+    Near {
+        /// The point to measure proximity from.
+        point: Point,
+        /// The maximum distance from the point in meters.
+        max_distance: Option<f64>,
+    },
     /// Matches none of the values specified in an array.
     Nin(Vec<T>),
-    /// Matches values that are equal to null.
+    /// Matches values that are equal to null, or documents where the field is missing.
+    ///
+    /// Use `IsNull` or `IsMissing` to tell them apart.
     Null,
+    /// Matches values that are greater than or equal to the start of the range, and less than its
+    /// end.
+    Range(Range<T>),
+    /// Matches string values against a regular expression.
+    ///
+    /// The `options` are the regular expression flags, such as `i` for case-insensitive matching,
+    /// and are omitted from the query when empty.
+    Regex {
+        /// The regular expression pattern.
+        pattern: String,
+        /// The regular expression options.
+        options: String,
+    },
+    /// Matches values that are of the specified BSON type.
+    Type(BsonType),
 }
 
 impl<T> TryFrom<Comparator<T>> for Bson
@@ -39,7 +158,21 @@ where
     type Error = ext::bson::ser::Error;
     fn try_from(value: Comparator<T>) -> Result<Self, Self::Error> {
         Ok(match value {
+            Comparator::BitsAllClear(t) => {
+                bson!({ "$bitsAllClear": t.try_into().map_err(|e| e.into())?.0 })
+            }
+            Comparator::BitsAllSet(t) => {
+                bson!({ "$bitsAllSet": t.try_into().map_err(|e| e.into())?.0 })
+            }
+            Comparator::BitsAnyClear(t) => {
+                bson!({ "$bitsAnyClear": t.try_into().map_err(|e| e.into())?.0 })
+            }
+            Comparator::BitsAnySet(t) => {
+                bson!({ "$bitsAnySet": t.try_into().map_err(|e| e.into())?.0 })
+            }
             Comparator::Eq(t) => bson!({ "$eq": t.try_into().map_err(|e| e.into())?.0 }),
+            Comparator::Exists(b) => bson!({ "$exists": b }),
+            Comparator::GeoWithin(shape) => bson!({ "$geoWithin": Document::from(shape) }),
             Comparator::Gt(t) => bson!({ "$gt": t.try_into().map_err(|e| e.into())?.0 }),
             Comparator::Gte(t) => bson!({ "$gte": t.try_into().map_err(|e| e.into())?.0 }),
             Comparator::In(t) => {
@@ -50,9 +183,22 @@ where
                     .map_err(|e| e.into())?;
                 bson!({ "$in": Bson::Array(int.into_iter().map(|b| b.0).collect()) })
             }
+            Comparator::IsMissing => bson!({ "$exists": false }),
+            Comparator::IsNull => bson!({ "$type": BsonType::Null.as_str() }),
+            Comparator::IsSet => bson!({ "$ne": Bson::Null }),
             Comparator::Lt(t) => bson!({ "$lt": t.try_into().map_err(|e| e.into())?.0 }),
             Comparator::Lte(t) => bson!({ "$lte": t.try_into().map_err(|e| e.into())?.0 }),
             Comparator::Ne(t) => bson!({ "$ne": t.try_into().map_err(|e| e.into())?.0 }),
+            Comparator::Near {
+                point,
+                max_distance,
+            } => {
+                let mut near = doc! { "$geometry": Bson::from(point) };
+                if let Some(distance) = max_distance {
+                    near.insert("$maxDistance", distance);
+                }
+                bson!({ "$near": near })
+            }
             Comparator::Nin(t) => {
                 let int = t
                     .into_iter()
@@ -62,6 +208,18 @@ where
                 bson!({ "$nin": Bson::Array(int.into_iter().map(|b| b.0).collect()) })
             }
             Comparator::Null => Bson::Null,
+            Comparator::Range(r) => bson!({
+                "$gte": r.start.try_into().map_err(|e| e.into())?.0,
+                "$lt": r.end.try_into().map_err(|e| e.into())?.0,
+            }),
+            Comparator::Regex { pattern, options } => {
+                if options.is_empty() {
+                    bson!({ "$regex": pattern })
+                } else {
+                    bson!({ "$regex": pattern, "$options": options })
+                }
+            }
+            Comparator::Type(t) => bson!({ "$type": t.as_str() }),
         })
     }
 }
@@ -77,6 +235,35 @@ where
     }
 }
 
+#[cfg(feature = "chrono")]
+impl Comparator<bson::oid::ObjectId> {
+    /// Matches object ids that were generated within the time range.
+    ///
+    /// This uses the timestamp embedded in an object id, allowing time windows to be scanned using
+    /// the `_id` index. Object id timestamps only have second precision, so the range is truncated
+    /// to seconds.
+    pub fn created_within(range: Range<chrono::DateTime<chrono::Utc>>) -> Self {
+        Comparator::Range(oid_at(range.start.timestamp())..oid_at(range.end.timestamp()))
+    }
+}
+
+#[cfg(feature = "time")]
+impl Comparator<bson::oid::ObjectId> {
+    /// Matches object ids that were generated within the time range.
+    ///
+    /// This is the `time` equivalent of `created_within`, the range is also truncated to seconds.
+    pub fn created_within_offset(range: Range<time::OffsetDateTime>) -> Self {
+        Comparator::Range(oid_at(range.start.unix_timestamp())..oid_at(range.end.unix_timestamp()))
+    }
+}
+
+// The smallest object id that could have been generated at the unix timestamp
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn oid_at(secs: i64) -> bson::oid::ObjectId {
+    let secs = u32::try_from(secs.max(0)).unwrap_or(u32::MAX);
+    bson::oid::ObjectId::from_parts(secs, [0; 5], [0; 3])
+}
+
 /// Used to tie a type implementing [`Collection`](./trait.Collection.html) to its companion `Filter` type.
 ///
 /// # Examples
@@ -137,6 +324,44 @@ pub trait AsFilter<T: Filter> {
     fn into_filter(self) -> T;
 }
 
+/// Used to tie a type to its companion `Filter` type so that it can be filtered field-by-field
+/// when embedded within another type.
+///
+/// The nested `Filter`'s keys are prefixed with the name of the embedding field using dot-notation,
+/// i.e. `address.city`. This is implemented by the `Mongo` derive for any type using
+/// `#[mongo(filter)]`, and used by fields marked with the `#[mongo(filter)]` field attribute.
+///
+/// Optional embedded documents are filtered using the inner type's `Filter`.
+pub trait NestedFilter {
+    /// The companion `Filter` type.
+    type Filter: Filter
+        + MergeFilter
+        + Debug
+        + Default
+        + TryInto<ext::bson::Bson, Error = ext::bson::ser::Error>;
+
+    /// Converts the instance into its nested filter.
+    fn into_nested_filter(self) -> Self::Filter;
+}
+
+impl<T: NestedFilter> NestedFilter for Option<T> {
+    type Filter = T::Filter;
+
+    fn into_nested_filter(self) -> Self::Filter {
+        // There is nothing to match field-by-field when absent
+        self.map(T::into_nested_filter).unwrap_or_default()
+    }
+}
+
+/// Used to merge a `Filter` with another of the same type field-wise.
+///
+/// This is implemented by the `Mongo` derive for any type using `#[mongo(filter)]`, where the
+/// fields set in `other` take precedence, while nested filters are merged recursively.
+pub trait MergeFilter {
+    /// Merges `other` into this `Filter`.
+    fn merge(self, other: Self) -> Self;
+}
+
 /// Used to mark a type as a filter for use in queries.
 ///
 /// # Examples
@@ -174,6 +399,225 @@ pub trait Filter {
     fn new() -> Self;
     /// Converts a `Filter` into a BSON `Document`.
     fn into_document(self) -> Result<Document, Error>;
+
+    /// Converts a `Filter` into relaxed Extended JSON.
+    ///
+    /// This renders the exact query that would be sent, so that it can be logged or pasted into
+    /// `mongosh`, see [`ext::json`](./ext/json/index.html) for canonical Extended JSON.
+    fn into_extended_json(self) -> Result<String, Error>
+    where
+        Self: Sized,
+    {
+        ext::json::filter_to_string(self, ext::json::Mode::Relaxed)
+    }
+
+    /// Combines this `Filter` with another, matching documents that match both.
+    fn and<T>(self, other: T) -> FilterExpr<Self>
+    where
+        Self: Sized,
+        T: Into<FilterExpr<Self>>,
+    {
+        FilterExpr::Filter(self).and(other)
+    }
+
+    /// Combines this `Filter` with another, matching documents that match either.
+    fn or<T>(self, other: T) -> FilterExpr<Self>
+    where
+        Self: Sized,
+        T: Into<FilterExpr<Self>>,
+    {
+        FilterExpr::Filter(self).or(other)
+    }
+
+    /// Combines this `Filter` with another, matching documents that match neither.
+    fn nor<T>(self, other: T) -> FilterExpr<Self>
+    where
+        Self: Sized,
+        T: Into<FilterExpr<Self>>,
+    {
+        FilterExpr::Filter(self).nor(other)
+    }
+
+    /// Negates this `Filter`, matching documents that do not match it.
+    fn not(self) -> FilterExpr<Self>
+    where
+        Self: Sized,
+    {
+        FilterExpr::Filter(self).not()
+    }
+}
+
+/// A logical expression over `Filter`s, allowing them to be composed using `$and`, `$or` & `$nor`.
+///
+/// A `FilterExpr` can be given to any querier that its inner filter can be.
+///
+/// # Examples
+///
+/// Finding users called `foo` or `bar`.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Comparator, Filter};
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection="users", field, filter, update)]
+/// pub struct User {
+///     pub name: String,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut foo = User::filter();
+/// foo.name = Some(Comparator::Eq("foo".to_owned()));
+/// let mut bar = User::filter();
+/// bar.name = Some(Comparator::Eq("bar".to_owned()));
+///
+/// let _cursor = mongod::query::Find::<User>::new()
+///     .filter(foo.or(bar))?
+///     .query(&client)
+///     .await?;
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum FilterExpr<F: Filter> {
+    /// Matches documents that match the filter.
+    Filter(F),
+    /// Matches documents that match all of the expressions.
+    And(Vec<FilterExpr<F>>),
+    /// Matches documents that match any of the expressions.
+    Or(Vec<FilterExpr<F>>),
+    /// Matches documents that match none of the expressions.
+    Nor(Vec<FilterExpr<F>>),
+    /// Matches documents that do not match the expression.
+    Not(Box<FilterExpr<F>>),
+    /// Matches documents where the aggregation expression evaluates to `true`.
+    Expr(Bson),
+    /// Matches documents that satisfy the JSON schema.
+    JsonSchema(Document),
+}
+
+impl<F: Filter> FilterExpr<F> {
+    /// Matches documents that satisfy the JSON schema.
+    ///
+    /// Negating the expression with [`not`](#method.not) matches the documents that violate the
+    /// schema instead, which is useful when auditing a collection.
+    pub fn json_schema(schema: Document) -> Self {
+        FilterExpr::JsonSchema(schema)
+    }
+
+    /// Combines this expression with another, matching documents that match both.
+    pub fn and<T: Into<Self>>(self, other: T) -> Self {
+        match self {
+            FilterExpr::And(mut exprs) => {
+                exprs.push(other.into());
+                FilterExpr::And(exprs)
+            }
+            expr => FilterExpr::And(vec![expr, other.into()]),
+        }
+    }
+
+    /// Combines this expression with another, matching documents that match either.
+    pub fn or<T: Into<Self>>(self, other: T) -> Self {
+        match self {
+            FilterExpr::Or(mut exprs) => {
+                exprs.push(other.into());
+                FilterExpr::Or(exprs)
+            }
+            expr => FilterExpr::Or(vec![expr, other.into()]),
+        }
+    }
+
+    /// Combines this expression with another, matching documents that match neither.
+    pub fn nor<T: Into<Self>>(self, other: T) -> Self {
+        match self {
+            FilterExpr::Nor(mut exprs) => {
+                exprs.push(other.into());
+                FilterExpr::Nor(exprs)
+            }
+            expr => FilterExpr::Nor(vec![expr, other.into()]),
+        }
+    }
+
+    /// Negates this expression, matching documents that do not match it.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        match self {
+            FilterExpr::Not(expr) => *expr,
+            expr => FilterExpr::Not(Box::new(expr)),
+        }
+    }
+}
+
+impl<F: Filter> From<F> for FilterExpr<F> {
+    fn from(filter: F) -> Self {
+        FilterExpr::Filter(filter)
+    }
+}
+
+impl<F, T> From<Expr<T>> for FilterExpr<F>
+where
+    F: Filter,
+    T: Field + Into<String>,
+{
+    fn from(expr: Expr<T>) -> Self {
+        FilterExpr::Expr(expr.into_bson())
+    }
+}
+
+impl<F: Filter> FilterExpr<F> {
+    /// Converts a `FilterExpr` into a BSON `Document`.
+    pub fn into_document(self) -> Result<Document, Error> {
+        fn into_array<F: Filter>(exprs: Vec<FilterExpr<F>>) -> Result<Bson, Error> {
+            exprs
+                .into_iter()
+                .map(|e| e.into_document().map(Bson::Document))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Bson::Array)
+        }
+        Ok(match self {
+            FilterExpr::Filter(filter) => filter.into_document()?,
+            FilterExpr::And(exprs) => doc! { "$and": into_array(exprs)? },
+            FilterExpr::Or(exprs) => doc! { "$or": into_array(exprs)? },
+            FilterExpr::Nor(exprs) => doc! { "$nor": into_array(exprs)? },
+            // NOTE: `$not` is a field level operator in mongo, so we negate using `$nor` instead
+            FilterExpr::Not(expr) => doc! { "$nor": [expr.into_document()?] },
+            FilterExpr::Expr(expr) => doc! { "$expr": expr },
+            FilterExpr::JsonSchema(schema) => doc! { "$jsonSchema": schema },
+        })
+    }
+}
+
+/// Used to mark a type as something that can filter the `Collection` `C` in queries.
+///
+/// This is implemented for any `Filter` tied to `C` through [`AsFilter`](./trait.AsFilter.html),
+/// along with any `FilterExpr` over one.
+pub trait FilterOf<C> {
+    /// Converts the filter into a BSON `Document`.
+    fn into_filter_document(self) -> Result<Document, Error>;
+}
+
+impl<C, F> FilterOf<C> for F
+where
+    C: AsFilter<F>,
+    F: Filter,
+{
+    fn into_filter_document(self) -> Result<Document, Error> {
+        self.into_document()
+    }
+}
+
+impl<C, F> FilterOf<C> for FilterExpr<F>
+where
+    C: AsFilter<F>,
+    F: Filter,
+{
+    fn into_filter_document(self) -> Result<Document, Error> {
+        self.into_document()
+    }
 }
 
 #[cfg(test)]
@@ -249,4 +693,228 @@ mod tests {
             "foo".to_owned()
         );
     }
+
+    #[test]
+    fn comparator_bits() {
+        let mask = |c: Comparator<i32>| Bson::try_from(c).unwrap();
+        assert_eq!(
+            mask(Comparator::BitsAllClear(0b10)),
+            bson!({ "$bitsAllClear": 0b10 })
+        );
+        assert_eq!(
+            mask(Comparator::BitsAllSet(0b11)),
+            bson!({ "$bitsAllSet": 0b11 })
+        );
+        assert_eq!(
+            mask(Comparator::BitsAnyClear(0b101)),
+            bson!({ "$bitsAnyClear": 0b101 })
+        );
+        assert_eq!(
+            mask(Comparator::BitsAnySet(0b1)),
+            bson!({ "$bitsAnySet": 0b1 })
+        );
+    }
+
+    #[test]
+    fn comparator_geo() {
+        let near = Comparator::<Point>::Near {
+            point: Point::new(1.0, 2.0),
+            max_distance: Some(100.0),
+        };
+        assert_eq!(
+            Bson::try_from(near).unwrap(),
+            bson!({
+                "$near": {
+                    "$geometry": { "type": "Point", "coordinates": [1.0, 2.0] },
+                    "$maxDistance": 100.0,
+                }
+            })
+        );
+
+        let within = Comparator::<Point>::GeoWithin(Shape::CenterSphere {
+            center: Point::new(1.0, 2.0),
+            radius: 0.1,
+        });
+        assert_eq!(
+            Bson::try_from(within).unwrap(),
+            bson!({ "$geoWithin": { "$centerSphere": [[1.0, 2.0], 0.1] } })
+        );
+    }
+
+    #[test]
+    fn comparator_range() {
+        let range = Comparator::Range(1..10);
+        assert_eq!(
+            Bson::try_from(range).unwrap(),
+            bson!({ "$gte": 1, "$lt": 10 })
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn comparator_created_within() {
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.timestamp_opt(1_600_000_000, 500).unwrap();
+        let end = chrono::Utc.timestamp_opt(1_600_000_060, 0).unwrap();
+        let range = match Comparator::created_within(start..end) {
+            Comparator::Range(range) => range,
+            _ => panic!("expected a range comparator"),
+        };
+        assert_eq!(
+            range.start.timestamp().timestamp_millis(),
+            1_600_000_000_000
+        );
+        assert_eq!(range.end.timestamp().timestamp_millis(), 1_600_000_060_000);
+        assert_eq!(range.start.bytes()[4..], [0; 8]);
+
+        let oid = bson::oid::ObjectId::from_parts(1_600_000_030, [1; 5], [1; 3]);
+        assert!(range.contains(&oid));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn comparator_created_within_offset() {
+        let start =
+            time::OffsetDateTime::from_unix_timestamp_nanos(1_600_000_000_000_000_500).unwrap();
+        let end = time::OffsetDateTime::from_unix_timestamp(1_600_000_060).unwrap();
+        let range = match Comparator::created_within_offset(start..end) {
+            Comparator::Range(range) => range,
+            _ => panic!("expected a range comparator"),
+        };
+        assert_eq!(
+            range.start.timestamp().timestamp_millis(),
+            1_600_000_000_000
+        );
+        assert_eq!(range.end.timestamp().timestamp_millis(), 1_600_000_060_000);
+
+        let before = time::OffsetDateTime::from_unix_timestamp(-1).unwrap();
+        match Comparator::created_within_offset(before..end) {
+            Comparator::Range(range) => assert_eq!(range.start.timestamp().timestamp_millis(), 0),
+            _ => panic!("expected a range comparator"),
+        }
+    }
+
+    #[test]
+    fn comparator_null_or_missing() {
+        let bson = |c: Comparator<Option<String>>| Bson::try_from(c).unwrap();
+        assert_eq!(bson(Comparator::Null), Bson::Null);
+        assert_eq!(bson(Comparator::IsMissing), bson!({ "$exists": false }));
+        assert_eq!(bson(Comparator::IsNull), bson!({ "$type": "null" }));
+        assert_eq!(bson(Comparator::IsSet), bson!({ "$ne": null }));
+    }
+
+    #[test]
+    fn filter_into_extended_json() {
+        let json = name("foo").into_extended_json().unwrap();
+        assert_eq!(json, r#"{"name":{"$eq":"foo"}}"#);
+    }
+
+    #[test]
+    fn comparator_exists() {
+        let filter = UserFilter {
+            name: Some(Comparator::Exists(false)),
+        };
+        let doc = filter.into_document().unwrap();
+        assert_eq!(doc, doc! { "name": { "$exists": false } });
+    }
+
+    #[test]
+    fn comparator_regex() {
+        let filter = UserFilter {
+            name: Some(Comparator::Regex {
+                pattern: "^fo".to_owned(),
+                options: "i".to_owned(),
+            }),
+        };
+        let doc = filter.into_document().unwrap();
+        assert_eq!(doc, doc! { "name": { "$regex": "^fo", "$options": "i" } });
+
+        let filter = UserFilter {
+            name: Some(Comparator::Regex {
+                pattern: "oo".to_owned(),
+                options: String::new(),
+            }),
+        };
+        let doc = filter.into_document().unwrap();
+        assert_eq!(doc, doc! { "name": { "$regex": "oo" } });
+    }
+
+    #[test]
+    fn comparator_type() {
+        let filter = UserFilter {
+            name: Some(Comparator::Type(BsonType::String)),
+        };
+        let doc = filter.into_document().unwrap();
+        assert_eq!(doc, doc! { "name": { "$type": "string" } });
+    }
+
+    fn name(name: &str) -> UserFilter {
+        UserFilter {
+            name: Some(Comparator::Eq(name.to_owned())),
+        }
+    }
+
+    #[test]
+    fn filter_expr_into_document() {
+        let doc = name("foo")
+            .or(name("bar"))
+            .or(name("baz"))
+            .into_document()
+            .unwrap();
+        assert_eq!(
+            doc,
+            doc! { "$or": [
+                { "name": { "$eq": "foo" } },
+                { "name": { "$eq": "bar" } },
+                { "name": { "$eq": "baz" } },
+            ] }
+        );
+
+        let doc = name("foo")
+            .and(name("bar").nor(name("baz")))
+            .into_document()
+            .unwrap();
+        assert_eq!(
+            doc,
+            doc! { "$and": [
+                { "name": { "$eq": "foo" } },
+                { "$nor": [{ "name": { "$eq": "bar" } }, { "name": { "$eq": "baz" } }] },
+            ] }
+        );
+    }
+
+    #[test]
+    fn filter_expr_not() {
+        let doc = name("foo").not().into_document().unwrap();
+        assert_eq!(doc, doc! { "$nor": [{ "name": { "$eq": "foo" } }] });
+
+        let doc = name("foo").not().not().into_document().unwrap();
+        assert_eq!(doc, doc! { "name": { "$eq": "foo" } });
+    }
+
+    #[test]
+    fn filter_expr_json_schema() {
+        let schema = doc! { "required": ["name"] };
+        let expr = FilterExpr::<UserFilter>::json_schema(schema.clone()).not();
+        assert_eq!(
+            expr.into_document().unwrap(),
+            doc! { "$nor": [{ "$jsonSchema": schema }] }
+        );
+    }
+
+    #[test]
+    fn filter_of() {
+        fn into_document<F: FilterOf<User>>(filter: F) -> Document {
+            filter.into_filter_document().unwrap()
+        }
+        assert_eq!(
+            into_document(name("foo")),
+            doc! { "name": { "$eq": "foo" } }
+        );
+        assert_eq!(
+            into_document(name("foo").not()),
+            doc! { "$nor": [{ "name": { "$eq": "foo" } }] }
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//! GeoJSON types used by the geospatial `Comparator`s.
+//!
+//! MongoDB expects coordinates in longitude, latitude order, these types take care of that so
+//! that they do not get mixed up.
+
+use std::convert::TryFrom;
+
+use bson::{Bson, Document};
+use serde::de::Error as _;
+
+use crate::ext;
+
+/// A GeoJSON point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    /// The longitude, between -180 and 180.
+    pub longitude: f64,
+    /// The latitude, between -90 and 90.
+    pub latitude: f64,
+}
+
+impl Point {
+    /// Constructs a new `Point`.
+    pub fn new(longitude: f64, latitude: f64) -> Self {
+        Point {
+            longitude,
+            latitude,
+        }
+    }
+
+    fn coordinates(&self) -> Bson {
+        Bson::Array(vec![
+            Bson::Double(self.longitude),
+            Bson::Double(self.latitude),
+        ])
+    }
+}
+
+impl From<Point> for Bson {
+    fn from(point: Point) -> Self {
+        bson!({ "type": "Point", "coordinates": point.coordinates() })
+    }
+}
+
+impl From<Point> for ext::bson::Bson {
+    fn from(point: Point) -> Self {
+        ext::bson::Bson(point.into())
+    }
+}
+
+impl TryFrom<ext::bson::Bson> for Point {
+    type Error = ext::bson::de::Error;
+    fn try_from(bson: ext::bson::Bson) -> Result<Self, Self::Error> {
+        let invalid = || -> Self::Error {
+            bson::de::Error::custom("invalid GeoJSON, expected a `Point`").into()
+        };
+        let doc = match bson.0 {
+            Bson::Document(doc) => doc,
+            _ => return Err(invalid()),
+        };
+        if doc.get_str("type").map_err(|_| invalid())? != "Point" {
+            return Err(invalid());
+        }
+        let coordinates = doc.get_array("coordinates").map_err(|_| invalid())?;
+        match coordinates.as_slice() {
+            [longitude, latitude] => Ok(Point {
+                longitude: f64::try_from(ext::bson::Bson(longitude.clone()))?,
+                latitude: f64::try_from(ext::bson::Bson(latitude.clone()))?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A shape used to match points within it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// A circle on a sphere, where the `radius` is in radians.
+    CenterSphere {
+        /// The center of the circle.
+        center: Point,
+        /// The radius of the circle in radians.
+        radius: f64,
+    },
+    /// A GeoJSON multi polygon, made up of many polygons.
+    MultiPolygon(Vec<Vec<Vec<Point>>>),
+    /// A GeoJSON polygon, made up of closed rings where the first is the exterior ring and any
+    /// others are holes within it.
+    Polygon(Vec<Vec<Point>>),
+}
+
+fn ring(points: Vec<Point>) -> Bson {
+    Bson::Array(points.iter().map(Point::coordinates).collect())
+}
+
+fn polygon(rings: Vec<Vec<Point>>) -> Bson {
+    Bson::Array(rings.into_iter().map(ring).collect())
+}
+
+impl From<Shape> for Document {
+    fn from(shape: Shape) -> Self {
+        match shape {
+            Shape::CenterSphere { center, radius } => {
+                doc! { "$centerSphere": [center.coordinates(), radius] }
+            }
+            Shape::MultiPolygon(polygons) => doc! {
+                "$geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": Bson::Array(polygons.into_iter().map(polygon).collect()),
+                }
+            },
+            Shape::Polygon(rings) => doc! {
+                "$geometry": {
+                    "type": "Polygon",
+                    "coordinates": polygon(rings),
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_roundtrip() {
+        let point = Point::new(-0.1, 51.5);
+        let bson = ext::bson::Bson::from(point);
+        assert_eq!(
+            bson.0,
+            bson!({ "type": "Point", "coordinates": [-0.1, 51.5] })
+        );
+        assert_eq!(Point::try_from(bson).unwrap(), point);
+
+        let bson = ext::bson::Bson(bson!({ "type": "Polygon", "coordinates": [] }));
+        assert!(Point::try_from(bson).is_err());
+    }
+
+    #[test]
+    fn shape_into_document() {
+        let shape = Shape::CenterSphere {
+            center: Point::new(1.0, 2.0),
+            radius: 0.5,
+        };
+        assert_eq!(
+            Document::from(shape),
+            doc! { "$centerSphere": [[1.0, 2.0], 0.5] }
+        );
+
+        let square = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 0.0),
+        ];
+        let shape = Shape::Polygon(vec![square.clone()]);
+        assert_eq!(
+            Document::from(shape),
+            doc! {
+                "$geometry": {
+                    "type": "Polygon",
+                    "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]],
+                }
+            }
+        );
+
+        let shape = Shape::MultiPolygon(vec![vec![square]]);
+        assert_eq!(
+            Document::from(shape),
+            doc! {
+                "$geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]],
+                }
+            }
+        );
+    }
+}
@@ -34,11 +34,17 @@
 //! This crate is opinionated, here are some examples on how to use it for interaction with
 //! mongodb. For more complex interactions see the individual implementations:
 //!
+//! - [`Aggregate`](query::Aggregate): Run an aggregation pipeline on a collection
+//! - [`Bulk`](query::Bulk): Send many write operations to a collection at once
 //! - [`Delete`](query::Delete): Delete documents from a collection
 //! - [`Find`](query::Find): Fetch documents from a collection
+//! - [`FindOneAndDelete`](query::FindOneAndDelete): Atomically fetch and delete a document
+//! - [`FindOneAndReplace`](query::FindOneAndReplace): Atomically fetch and replace a document
+//! - [`FindOneAndUpdate`](query::FindOneAndUpdate): Atomically fetch and update a document
 //! - [`Insert`](query::Insert): Insert documents into a collection
 //! - [`Replace`](query::Replace): Replace documents in a collection
 //! - [`Update`](query::Update): Update documents in a collection
+//! - [`Watch`](query::Watch): Watch the changes made to a collection
 //!
 //! ### Deleting
 //!
@@ -127,7 +133,7 @@
 //! };
 //!
 //! let result = client.insert(vec![user]).await.unwrap();
-//! println!("(index: oid) {:?}", result);
+//! println!("(index: id) {:?}", result);
 //! # Ok(())
 //! # }
 //! # }
@@ -183,7 +189,7 @@
 //! #     email: Option<String>,
 //! # }
 //! # async fn doc() -> Result<(), mongod::Error> {
-//! use mongod::{AsFilter, Comparator, AsUpdate};
+//! use mongod::{AsFilter, Comparator, AsUpdate, FieldUpdate};
 //!
 //! let client = mongod::Client::new();
 //!
@@ -191,7 +197,7 @@
 //! filter.name = Some(Comparator::Eq("foo".to_owned()));
 //!
 //! let mut update = User::update();
-//! update.age = Some(Some(0));
+//! update.age = FieldUpdate::Set(0);
 //!
 //! let updates = mongod::Updates {
 //!     set: Some(update),
@@ -209,17 +215,48 @@
 //!
 //! The following are a list of [Cargo Features][cargo-features] that cna be enabled or disabled:
 //!
+//! - **arrayvec**: Provides the [arrayvec][arrayvec] support for the [`ext::bson`][ext-bson].
+//! - **aws-auth**: Provides support for the `MONGODB-AWS` authentication mechanism.
 //! - **blocking**: Provides the [blocking][] client API.
+//! - **bytes**: Provides the [bytes][bytes] support for the [`ext::bson`][ext-bson], stored as
+//!   binary.
+//! - **cert-key-password**: Allows password protected certificate keys, decrypting them with
+//!   OpenSSL, see [`options`][options].
 //! - **chrono**: Provides the [chrono][chrono] support for the [`ext::bson`][ext-bson].
 //! - **derive**: Provides the `derive` macros from the [mongo-derive][derive] crate.
-//!
+//! - **indexmap**: Provides the [indexmap][indexmap] support for the [`ext::bson`][ext-bson],
+//!   preserving the order of keys & values.
+//! - **openssl-tls**: Uses OpenSSL rather than `rustls` for TLS, see [`options`][options].
+//! - **ordered-float**: Provides the [ordered-float][ordered-float] support for the
+//!   [`ext::bson`][ext-bson].
+//! - **regex**: Provides the `regex` check for the [`validate`][validate] field attribute.
+//! - **rust_decimal**: Provides the [rust_decimal][rust_decimal] support for the
+//!   [`ext::bson`][ext-bson], stored as `Decimal128`.
+//! - **smallvec**: Provides the [smallvec][smallvec] support for the [`ext::bson`][ext-bson].
+//! - **time**: Provides the [time][time] support for the [`ext::bson`][ext-bson].
+//! - **toml**: Allows a `ClientBuilder` to be loaded from a TOML file.
+//! - **url**: Provides the [url][url] support for the [`ext::bson`][ext-bson], stored as a string.
+//! - **uuid**: Provides the [uuid][uuid] support for the [`ext::bson`][ext-bson].
+//! - **yaml**: Allows a `ClientBuilder` to be loaded from a YAML file.
+//!
+//! [arrayvec]: https://docs.rs/arrayvec
 //! [blocking]: ./blocking/index.html
 //! [bson]: https://docs.rs/bson
+//! [bytes]: https://docs.rs/bytes
 //! [client]: ./struct.Client.html
 //! [chrono]: https://docs.rs/chrono
 //! [derive]: ../mongod_derive/index.html
 //! [ext-bson]: ./ext/bson/index.html
+//! [indexmap]: https://docs.rs/indexmap
+//! [options]: ./options/index.html
+//! [ordered-float]: https://docs.rs/ordered-float
+//! [rust_decimal]: https://docs.rs/rust_decimal
 //! [schema]: ./schema/index.html
+//! [smallvec]: https://docs.rs/smallvec
+//! [time]: https://docs.rs/time
+//! [url]: https://docs.rs/url
+//! [uuid]: https://docs.rs/uuid
+//! [validate]: ./validate/index.html
 //! [cargo-features]: https://doc.rust-lang.org/stable/cargo/reference/manifest.html#the-features-section
 
 #![deny(missing_docs)]
@@ -236,26 +273,45 @@ extern crate serde;
 
 pub use self::collection::Collection;
 pub use self::error::{Error, Kind as ErrorKind};
+pub use self::expr::Expr;
 pub use self::field::{AsField, Field};
-pub use self::filter::{AsFilter, Comparator, Filter};
+pub use self::filter::{
+    AsFilter, BsonType, Comparator, Filter, FilterExpr, FilterOf, MergeFilter, NestedFilter,
+};
 pub use self::query::Query;
-pub use self::r#async::{Client, ClientBuilder, TypedCursor};
+pub use self::r#async::{
+    ChangeStream, Client, ClientBuilder, SessionCursor, TypedCursor, TypedSessionCursor, ViewCursor,
+};
+pub use self::reference::Ref;
+pub use self::schema::JsonSchema;
 pub use self::sort::{Order, Sort};
-pub use self::update::{AsUpdate, Update, Updates};
+pub use self::update::{AsUpdate, Bit, FieldUpdate, Position, Rename, Update, Updates};
+pub use self::view::View;
 
 pub(crate) use error::Result;
 
 mod r#async;
 #[cfg(feature = "blocking")]
 pub mod blocking;
+pub mod change_stream;
 mod collection;
 mod error;
+mod expr;
 pub mod ext;
 mod field;
 mod filter;
+pub mod geo;
+pub mod options;
 pub mod query;
+pub mod query_string;
+mod reference;
+mod schema;
 mod sort;
+#[cfg(test)]
+mod tests;
 mod update;
+pub mod validate;
+mod view;
 
 #[cfg(feature = "mongod-derive")]
 #[allow(unused_imports)]
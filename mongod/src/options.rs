@@ -0,0 +1,23 @@
+//! The option types used to configure a `Client`.
+//!
+//! These are re-exported from the `mongodb` crate so that a `Client` can be configured without
+//! having to depend on it directly.
+//!
+//! # TLS
+//!
+//! By default TLS is provided by `rustls`, which means that no system libraries are required to
+//! build this crate. When the optional `openssl-tls` feature is enabled OpenSSL is used instead,
+//! this is required for the options that `rustls` cannot support, such as allowing invalid
+//! hostnames.
+//!
+//! Independently of the backend, the **cert-key-password** feature allows password protected
+//! certificate keys to be used, these are decrypted using OpenSSL.
+
+pub use bson::uuid::UuidRepresentation;
+pub use mongodb::options::{
+    Acknowledgment, AuthMechanism, ClientOptions, Collation, CollationStrength,
+    CreateCollectionOptions, Credential, FullDocumentBeforeChangeType, FullDocumentType,
+    GridFsBucketOptions, GridFsUploadOptions, ReadConcern, ReadConcernLevel, ReadPreference,
+    ReadPreferenceOptions, ResolverConfig, ReturnDocument, SelectionCriteria, ServerAddress,
+    SessionOptions, Tls, TlsOptions, WriteConcern,
+};
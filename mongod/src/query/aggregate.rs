@@ -0,0 +1,335 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use mongodb::bson::{doc, Document};
+use mongodb::options::{AggregateOptions, Collation, Hint, ReadConcern, SelectionCriteria};
+use mongodb::ClientSession;
+
+use crate::collection::Collection;
+use crate::field::{AsField, Field};
+use crate::filter::FilterOf;
+use crate::r#async::{Client, SessionCursor};
+use crate::sort::Sort;
+
+/// A querier to run an aggregation pipeline on a MongoDB collection.
+///
+/// As the stages of a pipeline can reshape the documents, the results are returned as raw
+/// documents rather than as the `Collection`.
+///
+/// # Examples
+///
+/// Count the users of each age.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+///
+/// use futures::stream::StreamExt;
+///
+/// use mongod::bson::doc;
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="users", field, filter, update)]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut cursor = mongod::query::Aggregate::<User>::new()
+///     .stage(doc! { "$group": { "_id": "$age", "count": { "$sum": 1 } } })
+///     .query(&client)
+///     .await?;
+/// while let Some(res) = cursor.next().await {
+///     if let Ok(document) = res {
+///         println!("{}", document);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Aggregate<C: Collection> {
+    pipeline: Vec<Document>,
+    options: AggregateOptions,
+
+    query_type: PhantomData<C>,
+}
+
+impl<C: Collection> Default for Aggregate<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Collection> Aggregate<C> {
+    /// Constructs an `Aggregate` querier with an empty pipeline.
+    pub fn new() -> Self {
+        Self {
+            pipeline: vec![],
+            options: AggregateOptions::default(),
+
+            query_type: PhantomData,
+        }
+    }
+
+    /// Enables writing to temporary files by the server.
+    ///
+    /// When set to true, aggregation stages can write data to the _tmp subdirectory in the dbPath
+    /// directory.
+    pub fn allow_disk_use(mut self, enable: bool) -> Self {
+        self.options.allow_disk_use = Some(enable);
+        self
+    }
+
+    /// The number of documents the server should return per cursor batch.
+    ///
+    /// # Notes
+    ///
+    /// This does not have any affect on the documents that are returned by a cursor, only the
+    /// number of documents kept in memory at a given time (and by extension, the number of round
+    /// trips needed to return the entire set of documents returned by the query.
+    pub fn batch_size(mut self, size: u32) -> Self {
+        self.options.batch_size = Some(size);
+        self
+    }
+
+    /// The collation to use for the operation.
+    ///
+    /// Collation allows users to specify language-specific rules for string comparison, such as
+    /// rules for lettercase and accent marks.
+    pub fn collation(mut self, value: Collation) -> Self {
+        self.options.collation = Some(value);
+        self
+    }
+
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
+    /// Tags the query with an arbitrary string.
+    ///
+    /// Used to help trace the operation through the database profiler, currentOp and logs.
+    pub fn comment(mut self, value: String) -> Self {
+        self.options.comment = Some(value);
+        self
+    }
+
+    /// Appends a `$match` stage to the pipeline, using the given filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn filter<F>(self, filter: F) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        Ok(self.stage(doc! { "$match": filter.into_filter_document()? }))
+    }
+
+    /// A document or string that specifies the index to use to support the query predicate.
+    pub fn hint(mut self, value: Hint) -> Self {
+        self.options.hint = Some(value);
+        self
+    }
+
+    /// Appends a `$limit` stage to the pipeline.
+    pub fn limit(self, value: i64) -> Self {
+        self.stage(doc! { "$limit": value })
+    }
+
+    /// The maximum amount of time for the server to wait on new documents to satisfy a tailable
+    /// await cursor query.
+    pub fn max_await_time(mut self, duration: Duration) -> Self {
+        self.options.max_await_time = Some(duration);
+        self
+    }
+
+    /// The maximum amount of time to allow the query to run.
+    ///
+    /// This options maps to the maxTimeMS MongoDB query option, so the duration will be sent
+    /// across the wire as an integer number of milliseconds.
+    pub fn max_time(mut self, duration: Duration) -> Self {
+        self.options.max_time = Some(duration);
+        self
+    }
+
+    /// Appends the stages to the pipeline.
+    pub fn pipeline<I>(mut self, stages: I) -> Self
+    where
+        I: IntoIterator<Item = Document>,
+    {
+        self.pipeline.extend(stages);
+        self
+    }
+
+    /// The read concern to use for this aggregation.
+    ///
+    /// If none specified, the default set on the collection will be used.
+    pub fn read_concern(mut self, concern: ReadConcern) -> Self {
+        self.options.read_concern = Some(concern);
+        self
+    }
+
+    /// The criteria used to select a server for this aggregation.
+    ///
+    /// If none specified, the default set on the collection will be used.
+    pub fn selection_criteria(mut self, criteria: SelectionCriteria) -> Self {
+        self.options.selection_criteria = Some(criteria);
+        self
+    }
+
+    /// Appends a `$skip` stage to the pipeline.
+    pub fn skip(self, value: i64) -> Self {
+        self.stage(doc! { "$skip": value })
+    }
+
+    /// Appends a `$sort` stage to the pipeline.
+    pub fn sort<F>(self, sort: Sort<F>) -> Self
+    where
+        C: AsField<F>,
+        F: Field + Into<String>,
+    {
+        self.stage(doc! { "$sort": sort.into_document() })
+    }
+
+    /// Appends a stage to the pipeline.
+    pub fn stage(mut self, stage: Document) -> Self {
+        self.pipeline.push(stage);
+        self
+    }
+
+    /// Query the database with this querier.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub async fn query(self, client: &Client) -> crate::Result<mongodb::Cursor<Document>> {
+        let collection = client.database().collection::<Document>(&C::collection());
+        client
+            .with_timeout(collection.aggregate(self.pipeline, self.options))
+            .await
+    }
+
+    /// Query the database with this querier, binding the returned cursor to the session.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// use mongod::bson::doc;
+    ///
+    /// #[derive(Debug, Bson, Mongo)]
+    /// #[mongo(collection="users", field, filter, update)]
+    /// pub struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    /// let client = mongod::Client::new();
+    ///
+    /// let options = mongod::options::SessionOptions::builder()
+    ///     .causal_consistency(true)
+    ///     .build();
+    /// let mut session = client.start_session(Some(options)).await?;
+    ///
+    /// let mut cursor = mongod::query::Aggregate::<User>::new()
+    ///     .stage(doc! { "$group": { "_id": "$age", "count": { "$sum": 1 } } })
+    ///     .query_with_session(&client, &mut session)
+    ///     .await?;
+    /// while let Some(res) = cursor.next().await {
+    ///     println!("{}", res?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub async fn query_with_session<'s>(
+        self,
+        client: &Client,
+        session: &'s mut ClientSession,
+    ) -> crate::Result<SessionCursor<'s>> {
+        let collection = client.database().collection::<Document>(&C::collection());
+        let cursor = client
+            .with_timeout(collection.aggregate_with_session(self.pipeline, self.options, session))
+            .await?;
+        Ok(SessionCursor::new(cursor, session))
+    }
+
+    /// Query the database with this querier in a blocking context.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(
+        self,
+        client: &crate::blocking::Client,
+    ) -> crate::Result<crate::blocking::Cursor> {
+        let resp = client.execute(crate::blocking::Request::Aggregate(
+            C::collection(),
+            self.pipeline,
+            self.options,
+        ))?;
+        if let crate::blocking::Response::Aggregate(r) = resp {
+            return Ok(r);
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User;
+
+    impl Collection for User {
+        const COLLECTION: &'static str = "users";
+
+        fn from_document(_: Document) -> crate::Result<Self> {
+            Ok(User)
+        }
+
+        fn into_document(self) -> crate::Result<Document> {
+            Ok(Document::new())
+        }
+    }
+
+    #[test]
+    fn pipeline_order() {
+        let aggregate = Aggregate::<User>::new()
+            .stage(doc! { "$match": { "age": 1 } })
+            .pipeline(vec![doc! { "$group": { "_id": "$name" } }])
+            .skip(2)
+            .limit(1);
+        assert_eq!(
+            aggregate.pipeline,
+            vec![
+                doc! { "$match": { "age": 1 } },
+                doc! { "$group": { "_id": "$name" } },
+                doc! { "$skip": 2_i64 },
+                doc! { "$limit": 1_i64 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bson::{doc, Bson, Document};
+use mongodb::options::{ReadPreference, SelectionCriteria, WriteConcern};
+
+use crate::collection::Collection;
+use crate::filter::FilterOf;
+use crate::r#async::Client;
+use crate::update::{AsUpdate, Update, Updates};
+
+// The maximum number of operations sent in a single write command.
+const BATCH_SIZE: usize = 1000;
+
+// A single write operation of a `Bulk`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum WriteModel {
+    Delete {
+        filter: Document,
+        many: bool,
+    },
+    Insert(Document),
+    Update {
+        filter: Document,
+        update: Document,
+        many: bool,
+        upsert: bool,
+    },
+}
+
+impl WriteModel {
+    fn command(&self) -> &'static str {
+        match self {
+            WriteModel::Delete { .. } => "delete",
+            WriteModel::Insert(_) => "insert",
+            WriteModel::Update { .. } => "update",
+        }
+    }
+
+    fn into_statement(self) -> Document {
+        match self {
+            WriteModel::Delete { filter, many } => doc! {
+                "q": filter,
+                "limit": if many { 0 } else { 1 },
+            },
+            WriteModel::Insert(document) => document,
+            WriteModel::Update {
+                filter,
+                update,
+                many,
+                upsert,
+            } => doc! {
+                "q": filter,
+                "u": update,
+                "multi": many,
+                "upsert": upsert,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct BulkOptions {
+    bypass_document_validation: Option<bool>,
+    ordered: bool,
+    write_concern: Option<WriteConcern>,
+}
+
+/// The result of a `Bulk` write.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkResult {
+    /// The number of documents deleted.
+    pub deleted: u64,
+    /// The number of documents inserted.
+    pub inserted: u64,
+    /// The number of documents matched by the updates and replacements.
+    pub matched: u64,
+    /// The number of documents modified by the updates and replacements.
+    pub modified: u64,
+    /// The `_id`s of the upserted documents, keyed by the index of their operation.
+    pub upserted: HashMap<usize, Bson>,
+}
+
+/// A querier to send many write operations to a MongoDB collection at once.
+///
+/// Consecutive operations of the same kind are sent to mongo in a single command, so that a batch
+/// job does not need a round trip per document.
+///
+/// # Examples
+///
+/// Insert a user, and delete another.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, Comparator};
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="users", field, filter, update)]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut filter = User::filter();
+/// filter.name = Some(Comparator::Eq("foo".to_owned()));
+///
+/// let result = mongod::query::Bulk::<User>::new()
+///     .insert(User { name: "bar".to_owned() })?
+///     .delete_one(filter)?
+///     .query(&client)
+///     .await?;
+/// println!("inserted {}, deleted {}", result.inserted, result.deleted);
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Bulk<C: Collection> {
+    models: Vec<WriteModel>,
+    options: BulkOptions,
+
+    query_type: PhantomData<C>,
+}
+
+impl<C: Collection> Default for Bulk<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Collection> Bulk<C> {
+    /// Constructs an empty, ordered, `Bulk` querier.
+    pub fn new() -> Self {
+        Self {
+            models: vec![],
+            options: BulkOptions {
+                bypass_document_validation: None,
+                ordered: true,
+                write_concern: None,
+            },
+
+            query_type: PhantomData,
+        }
+    }
+
+    /// Opt out of document-level validation.
+    pub fn bypass_document_validation(mut self, enable: bool) -> Self {
+        self.options.bypass_document_validation = Some(enable);
+        self
+    }
+
+    /// Adds an operation to delete all documents matching the filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn delete_many<F>(self, filter: F) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.delete(filter, true)
+    }
+
+    /// Adds an operation to delete the first document matching the filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn delete_one<F>(self, filter: F) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.delete(filter, false)
+    }
+
+    /// Adds an operation to insert the document.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the document could not be converted into a BSON `Document`.
+    pub fn insert(mut self, document: C) -> crate::Result<Self> {
+        self.models
+            .push(WriteModel::Insert(document.into_document()?));
+        Ok(self)
+    }
+
+    /// Returns whether there are no operations to send.
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// Returns the number of operations to send.
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Sets whether the operations are run in order, stopping at the first that fails.
+    ///
+    /// Unordered operations may be run in parallel by mongo, and continue past failures.
+    /// Defaults to `true`.
+    pub fn ordered(mut self, enable: bool) -> Self {
+        self.options.ordered = enable;
+        self
+    }
+
+    /// Adds an operation to replace the first document matching the filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter or document could not be converted into a BSON
+    /// `Document`.
+    pub fn replace_one<F>(mut self, filter: F, document: C, upsert: bool) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.models.push(WriteModel::Update {
+            filter: filter.into_filter_document()?,
+            update: document.into_document()?,
+            many: false,
+            upsert,
+        });
+        Ok(self)
+    }
+
+    /// Adds an operation to update all documents matching the filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter or updates could not be converted into a BSON `Document`.
+    pub fn update_many<F, U>(self, filter: F, updates: Updates<U>) -> crate::Result<Self>
+    where
+        C: AsUpdate<U>,
+        F: FilterOf<C>,
+        U: Update,
+    {
+        self.update(filter, updates, true, false)
+    }
+
+    /// Adds an operation to update the first document matching the filter.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter or updates could not be converted into a BSON `Document`.
+    pub fn update_one<F, U>(self, filter: F, updates: Updates<U>) -> crate::Result<Self>
+    where
+        C: AsUpdate<U>,
+        F: FilterOf<C>,
+        U: Update,
+    {
+        self.update(filter, updates, false, false)
+    }
+
+    /// Adds an operation to update the first document matching the filter, inserting one should
+    /// none match.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter or updates could not be converted into a BSON `Document`.
+    pub fn upsert_one<F, U>(self, filter: F, updates: Updates<U>) -> crate::Result<Self>
+    where
+        C: AsUpdate<U>,
+        F: FilterOf<C>,
+        U: Update,
+    {
+        self.update(filter, updates, false, true)
+    }
+
+    /// The write concern for the operation.
+    pub fn write_concern(mut self, concern: WriteConcern) -> Self {
+        self.options.write_concern = Some(concern);
+        self
+    }
+
+    fn delete<F>(mut self, filter: F, many: bool) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.models.push(WriteModel::Delete {
+            filter: filter.into_filter_document()?,
+            many,
+        });
+        Ok(self)
+    }
+
+    fn update<F, U>(
+        mut self,
+        filter: F,
+        updates: Updates<U>,
+        many: bool,
+        upsert: bool,
+    ) -> crate::Result<Self>
+    where
+        C: AsUpdate<U>,
+        F: FilterOf<C>,
+        U: Update,
+    {
+        self.models.push(WriteModel::Update {
+            filter: filter.into_filter_document()?,
+            update: updates.into_document()?,
+            many,
+            upsert,
+        });
+        Ok(self)
+    }
+
+    /// Query the database with this querier.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. an operation failed.
+    pub async fn query(self, client: &Client) -> crate::Result<BulkResult> {
+        bulk_write(client, C::collection(), self.models, self.options).await
+    }
+
+    /// Query the database with this querier in a blocking context.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error, i.e. an operation failed.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(self, client: &crate::blocking::Client) -> crate::Result<BulkResult> {
+        let resp = client.execute(crate::blocking::Request::BulkWrite(
+            C::collection(),
+            self.models,
+            self.options,
+        ))?;
+        if let crate::blocking::Response::BulkWrite(r) = resp {
+            return Ok(r);
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
+
+// Sends the operations as write commands, shared with the blocking client
+pub(crate) async fn bulk_write(
+    client: &Client,
+    collection: String,
+    models: Vec<WriteModel>,
+    options: BulkOptions,
+) -> crate::Result<BulkResult> {
+    let database = client.database();
+    let mut result = BulkResult::default();
+    let mut offset = 0;
+    for (name, statements) in batches(models) {
+        let count = statements.len();
+        let command = command(&collection, name, statements, &options)?;
+        let reply = client
+            .with_timeout(database.run_command(
+                command,
+                SelectionCriteria::ReadPreference(ReadPreference::Primary),
+            ))
+            .await?;
+        let n = number(&reply, "n");
+        match name {
+            "delete" => result.deleted += n,
+            "insert" => result.inserted += n,
+            _ => {
+                let upserted = match reply.get("upserted") {
+                    Some(Bson::Array(upserted)) => upserted.as_slice(),
+                    _ => &[],
+                };
+                for upsert in upserted.iter().filter_map(Bson::as_document) {
+                    let index = number(upsert, "index") as usize;
+                    if let Some(id) = upsert.get("_id") {
+                        result.upserted.insert(offset + index, id.clone());
+                    }
+                }
+                result.matched += n - upserted.len() as u64;
+                result.modified += number(&reply, "nModified");
+            }
+        }
+        if let Some(error) = write_error(&reply, offset) {
+            return Err(crate::error::mongodb(error));
+        }
+        offset += count;
+    }
+    Ok(result)
+}
+
+// Groups consecutive operations of the same kind, up to the batch size.
+fn batches(models: Vec<WriteModel>) -> Vec<(&'static str, Vec<Document>)> {
+    let mut batches: Vec<(&'static str, Vec<Document>)> = vec![];
+    for model in models {
+        let name = model.command();
+        match batches.last_mut() {
+            Some((last, statements)) if *last == name && statements.len() < BATCH_SIZE => {
+                statements.push(model.into_statement())
+            }
+            _ => batches.push((name, vec![model.into_statement()])),
+        }
+    }
+    batches
+}
+
+fn command(
+    collection: &str,
+    name: &str,
+    statements: Vec<Document>,
+    options: &BulkOptions,
+) -> crate::Result<Document> {
+    let field = match name {
+        "delete" => "deletes",
+        "insert" => "documents",
+        _ => "updates",
+    };
+    let mut command = Document::new();
+    command.insert(name, collection);
+    command.insert(field, statements);
+    command.insert("ordered", options.ordered);
+    if let (Some(bypass), true) = (options.bypass_document_validation, name != "delete") {
+        command.insert("bypassDocumentValidation", bypass);
+    }
+    if let Some(concern) = &options.write_concern {
+        command.insert(
+            "writeConcern",
+            bson::to_document(concern).map_err(crate::error::bson)?,
+        );
+    }
+    Ok(command)
+}
+
+fn number(document: &Document, key: &str) -> u64 {
+    match document.get(key) {
+        Some(Bson::Int32(n)) => *n as u64,
+        Some(Bson::Int64(n)) => *n as u64,
+        _ => 0,
+    }
+}
+
+fn write_error(reply: &Document, offset: usize) -> Option<String> {
+    if let Some(Bson::Array(errors)) = reply.get("writeErrors") {
+        if let Some(error) = errors.iter().find_map(Bson::as_document) {
+            return Some(format!(
+                "write error at operation {}: {}",
+                offset + number(error, "index") as usize,
+                error.get_str("errmsg").unwrap_or("unknown error"),
+            ));
+        }
+    }
+    if let Ok(error) = reply.get_document("writeConcernError") {
+        return Some(format!(
+            "write concern error: {}",
+            error.get_str("errmsg").unwrap_or("unknown error"),
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_consecutive() {
+        let delete = WriteModel::Delete {
+            filter: doc! { "name": "foo" },
+            many: false,
+        };
+        let models = vec![
+            WriteModel::Insert(doc! { "name": "foo" }),
+            WriteModel::Insert(doc! { "name": "bar" }),
+            delete.clone(),
+            WriteModel::Insert(doc! { "name": "baz" }),
+        ];
+        let batched = batches(models);
+        assert_eq!(
+            batched,
+            vec![
+                (
+                    "insert",
+                    vec![doc! { "name": "foo" }, doc! { "name": "bar" }]
+                ),
+                ("delete", vec![doc! { "q": { "name": "foo" }, "limit": 1 }]),
+                ("insert", vec![doc! { "name": "baz" }]),
+            ]
+        );
+
+        let models = vec![WriteModel::Insert(Document::new()); BATCH_SIZE + 1];
+        let sizes: Vec<_> = batches(models).iter().map(|(_, s)| s.len()).collect();
+        assert_eq!(sizes, vec![BATCH_SIZE, 1]);
+    }
+
+    #[test]
+    fn write_errors() {
+        let reply = doc! {
+            "n": 1,
+            "writeErrors": [{ "index": 1, "code": 11000, "errmsg": "duplicate key" }],
+        };
+        assert_eq!(
+            write_error(&reply, 10).unwrap(),
+            "write error at operation 11: duplicate key"
+        );
+        assert!(write_error(&doc! { "n": 1 }, 0).is_none());
+    }
+}
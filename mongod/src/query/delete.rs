@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use mongodb::options::{Collation, DeleteOptions, Hint, WriteConcern};
 
 use crate::collection::Collection;
-use crate::filter::{AsFilter, Filter};
+use crate::filter::FilterOf;
 use crate::r#async::Client;
 use bson::Document;
 
@@ -72,6 +72,14 @@ impl<C: Collection> Delete<C> {
         self
     }
 
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
     /// The filter to use for the operation.
     ///
     /// # Errors
@@ -79,10 +87,9 @@ impl<C: Collection> Delete<C> {
     /// This method errors if the filter could not be converted into a BSON `Document`.
     pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
     where
-        C: AsFilter<F>,
-        F: Filter,
+        F: FilterOf<C>,
     {
-        self.filter = Some(filter.into_document()?);
+        self.filter = Some(filter.into_filter_document()?);
         Ok(self)
     }
 
@@ -116,20 +123,16 @@ impl<C: Collection> Delete<C> {
             Some(f) => f,
             None => bson::Document::new(),
         };
+        let collection = client.database().collection::<Document>(&C::collection());
         let result = if self.many {
             client
-                .database()
-                .collection::<Document>(C::COLLECTION)
-                .delete_many(filter, Some(self.options))
+                .with_timeout(collection.delete_many(filter, Some(self.options)))
                 .await
         } else {
             client
-                .database()
-                .collection::<Document>(C::COLLECTION)
-                .delete_one(filter, Some(self.options))
+                .with_timeout(collection.delete_one(filter, Some(self.options)))
                 .await
-        }
-        .map_err(crate::error::mongodb)?;
+        }?;
         Ok(result.deleted_count)
     }
 
@@ -150,7 +153,7 @@ impl<C: Collection> Delete<C> {
         };
         let resp = client.execute(crate::blocking::Request::Delete(
             self.many,
-            C::COLLECTION,
+            C::collection(),
             filter,
             self.options,
         ))?;
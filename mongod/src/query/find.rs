@@ -1,14 +1,16 @@
 use std::marker::PhantomData;
 use std::time::Duration;
 
-use mongodb::bson::Document;
+use mongodb::bson::{doc, Bson, Document};
 use mongodb::options::{Collation, CursorType, FindOptions, Hint, ReadConcern, SelectionCriteria};
+use mongodb::ClientSession;
 
 use crate::collection::Collection;
 use crate::field::{AsField, Field};
-use crate::filter::{AsFilter, Filter};
-use crate::r#async::{Client, TypedCursor};
+use crate::filter::FilterOf;
+use crate::r#async::{Client, SessionCursor, TypedCursor, TypedSessionCursor, ViewCursor};
 use crate::sort::Sort;
+use crate::view::View;
 
 /// A querier to find documents in a MongoDB collection.
 ///
@@ -110,6 +112,14 @@ impl<C: Collection> Find<C> {
         self
     }
 
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
     /// Tags the query with an arbitrary string.
     ///
     /// Used to help trace the operation through the database profiler, currentOp and logs.
@@ -131,13 +141,24 @@ impl<C: Collection> Find<C> {
     /// This method errors if the filter could not be converted into a BSON `Document`.
     pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
     where
-        C: AsFilter<F>,
-        F: Filter,
+        F: FilterOf<C>,
     {
-        self.filter = Some(filter.into_document()?);
+        self.filter = Some(filter.into_filter_document()?);
         Ok(self)
     }
 
+    // Filters on the document's `_id`.
+    pub(crate) fn id(mut self, id: Bson) -> Self {
+        self.filter = Some(doc! { "_id": id });
+        self
+    }
+
+    // Filters using a raw filter document.
+    pub(crate) fn raw_filter(mut self, filter: Document) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// A document or string that specifies the index to use to support the query predicate.
     pub fn hint(mut self, value: Hint) -> Self {
         self.options.hint = Some(value);
@@ -205,6 +226,22 @@ impl<C: Collection> Find<C> {
         self
     }
 
+    /// Converts this querier into one that returns the view `V` of the found documents.
+    ///
+    /// The projection is replaced with the view's projection.
+    pub fn into_view<V>(mut self) -> FindView<V>
+    where
+        V: View<Collection = C>,
+    {
+        self.options.projection = Some(V::projection());
+        FindView {
+            filter: self.filter,
+            options: self.options,
+
+            view_type: PhantomData,
+        }
+    }
+
     /// The read concern to use for this find query.
     ///
     /// If none specified, the default set on the collection will be used.
@@ -255,13 +292,59 @@ impl<C: Collection> Find<C> {
     ///
     /// This method fails if the mongodb encountered an error.
     pub async fn query(self, client: &Client) -> crate::Result<TypedCursor<C>> {
+        let collection = client.database().collection::<Document>(&C::collection());
         client
-            .database()
-            .collection::<Document>(C::COLLECTION)
-            .find(self.filter, self.options)
+            .with_timeout(collection.find(self.filter, self.options))
             .await
             .map(TypedCursor::from)
-            .map_err(crate::error::mongodb)
+    }
+
+    /// Query the database with this querier, binding the returned cursor to the session.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod wrapper {
+    /// # use mongod_derive::{Bson, Mongo};
+    /// #[derive(Debug, Bson, Mongo)]
+    /// #[mongo(collection="users", field, filter, update)]
+    /// pub struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// # async fn doc() -> Result<(), mongod::Error> {
+    /// let client = mongod::Client::new();
+    ///
+    /// let options = mongod::options::SessionOptions::builder()
+    ///     .causal_consistency(true)
+    ///     .build();
+    /// let mut session = client.start_session(Some(options)).await?;
+    ///
+    /// let mut cursor = mongod::query::Find::<User>::new()
+    ///     .query_with_session(&client, &mut session)
+    ///     .await?;
+    /// while let Some(res) = cursor.next().await {
+    ///     let (_id, user) = res?;
+    ///     println!("{:?}", user);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub async fn query_with_session<'s>(
+        self,
+        client: &Client,
+        session: &'s mut ClientSession,
+    ) -> crate::Result<TypedSessionCursor<'s, C>> {
+        let collection = client.database().collection::<Document>(&C::collection());
+        let cursor = client
+            .with_timeout(collection.find_with_session(self.filter, self.options, session))
+            .await?;
+        Ok(SessionCursor::new(cursor, session).into())
     }
 
     /// Query the database with this querier in a blocking context.
@@ -279,7 +362,7 @@ impl<C: Collection> Find<C> {
         client: &crate::blocking::Client,
     ) -> crate::Result<crate::blocking::TypedCursor<C>> {
         let resp = client.execute(crate::blocking::Request::Find(
-            C::COLLECTION,
+            C::collection(),
             self.filter,
             self.options,
         ))?;
@@ -291,3 +374,92 @@ impl<C: Collection> Find<C> {
         ))
     }
 }
+
+/// A querier to find views of documents in a MongoDB collection.
+///
+/// This is constructed using [`Find::into_view`].
+///
+/// # Examples
+///
+/// Find the names of all users.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+///
+/// use futures::stream::StreamExt;
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="users", view(Name = [name]))]
+/// pub struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut cursor = mongod::query::Find::<User>::new()
+///     .into_view::<UserName>()
+///     .query(&client)
+///     .await?;
+/// while let Some(res) = cursor.next().await {
+///     if let Ok(user) = res {
+///         println!("{}", user.name);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FindView<V: View> {
+    filter: Option<Document>,
+    options: FindOptions,
+
+    view_type: PhantomData<V>,
+}
+
+impl<V: View> FindView<V> {
+    /// Query the database with this querier.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub async fn query(self, client: &Client) -> crate::Result<ViewCursor<V>> {
+        let collection = client
+            .database()
+            .collection::<Document>(&V::Collection::collection());
+        client
+            .with_timeout(collection.find(self.filter, self.options))
+            .await
+            .map(ViewCursor::from)
+    }
+
+    /// Query the database with this querier in a blocking context.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(
+        self,
+        client: &crate::blocking::Client,
+    ) -> crate::Result<crate::blocking::ViewCursor<V>> {
+        let resp = client.execute(crate::blocking::Request::Find(
+            V::Collection::collection(),
+            self.filter,
+            self.options,
+        ))?;
+        if let crate::blocking::Response::Find(r) = resp {
+            return Ok(crate::blocking::ViewCursor::from(r));
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
@@ -0,0 +1,172 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bson::Document;
+use mongodb::options::{Collation, FindOneAndDeleteOptions, Hint, WriteConcern};
+
+use crate::collection::Collection;
+use crate::field::{AsField, Field};
+use crate::filter::FilterOf;
+use crate::r#async::Client;
+use crate::sort::Sort;
+
+/// A querier to atomically find and delete a document in a MongoDB collection.
+///
+/// # Examples
+///
+/// Take the oldest job from a queue.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{Order, Sort};
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="jobs", field, filter, update)]
+/// pub struct Job {
+///     name: String,
+///     created: i64,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut sort = Sort::new();
+/// sort.push(job::Field::Created, Order::Asc);
+///
+/// let job = mongod::query::FindOneAndDelete::<Job>::new()
+///     .sort(sort)
+///     .query(&client)
+///     .await?;
+/// println!("{:?}", job);
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FindOneAndDelete<C: Collection> {
+    filter: Option<Document>,
+    options: FindOneAndDeleteOptions,
+
+    query_type: PhantomData<C>,
+}
+
+impl<C: Collection> Default for FindOneAndDelete<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Collection> FindOneAndDelete<C> {
+    /// Constructs a `FindOneAndDelete` querier.
+    pub fn new() -> Self {
+        Self {
+            filter: None,
+            options: FindOneAndDeleteOptions::default(),
+
+            query_type: PhantomData,
+        }
+    }
+
+    /// The collation to use for the operation.
+    ///
+    /// Collation allows users to specify language-specific rules for string comparison, such as
+    /// rules for lettercase and accent marks.
+    pub fn collation(mut self, collation: Collation) -> Self {
+        self.options.collation = Some(collation);
+        self
+    }
+
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
+    /// The filter to use for the operation.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.filter = Some(filter.into_filter_document()?);
+        Ok(self)
+    }
+
+    /// A document or string that specifies the index to use to support the query predicate.
+    pub fn hint(mut self, value: Hint) -> Self {
+        self.options.hint = Some(value);
+        self
+    }
+
+    /// The maximum amount of time to allow the query to run.
+    pub fn max_time(mut self, duration: Duration) -> Self {
+        self.options.max_time = Some(duration);
+        self
+    }
+
+    /// The order used to pick the document to delete, should the filter match many.
+    pub fn sort<F>(mut self, sort: Sort<F>) -> Self
+    where
+        C: AsField<F>,
+        F: Field + Into<String>,
+    {
+        self.options.sort = Some(sort.into_document());
+        self
+    }
+
+    /// The write concern for the operation.
+    pub fn write_concern(mut self, concern: WriteConcern) -> Self {
+        self.options.write_concern = Some(concern);
+        self
+    }
+
+    /// Query the database with this querier, returning the deleted document.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if:
+    /// - the mongodb encountered an error.
+    /// - the deleted document could not be converted into the `Collection`.
+    pub async fn query(self, client: &Client) -> crate::Result<Option<C>> {
+        let filter = self.filter.unwrap_or_default();
+        let collection = client.database().collection::<Document>(&C::collection());
+        client
+            .with_timeout(collection.find_one_and_delete(filter, self.options))
+            .await?
+            .map(C::from_document)
+            .transpose()
+    }
+
+    /// Query the database with this querier in a blocking context, returning the deleted
+    /// document.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if:
+    /// - the mongodb encountered an error.
+    /// - the deleted document could not be converted into the `Collection`.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(self, client: &crate::blocking::Client) -> crate::Result<Option<C>> {
+        let resp = client.execute(crate::blocking::Request::FindOneAndDelete(
+            C::collection(),
+            self.filter.unwrap_or_default(),
+            self.options,
+        ))?;
+        if let crate::blocking::Response::FindOneAndDelete(r) = resp {
+            return r.map(C::from_document).transpose();
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
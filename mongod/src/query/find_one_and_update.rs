@@ -0,0 +1,230 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bson::Document;
+use mongodb::options::{Collation, FindOneAndUpdateOptions, Hint, ReturnDocument, WriteConcern};
+
+use crate::collection::Collection;
+use crate::field::{AsField, Field};
+use crate::filter::FilterOf;
+use crate::r#async::Client;
+use crate::sort::Sort;
+use crate::update::{AsUpdate, Updates};
+
+/// A querier to atomically find and update a document in a MongoDB collection.
+///
+/// # Examples
+///
+/// Increment a counter, returning its new value.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::{AsFilter, AsUpdate, Comparator};
+/// use mongod::options::ReturnDocument;
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="counters", field, filter, update)]
+/// pub struct Counter {
+///     name: String,
+///     value: i64,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut filter = Counter::filter();
+/// filter.name = Some(Comparator::Eq("visits".to_owned()));
+///
+/// let mut inc = Counter::update();
+/// inc.value = Some(1);
+///
+/// let counter = mongod::query::FindOneAndUpdate::<Counter>::new()
+///     .filter(filter)?
+///     .return_document(ReturnDocument::After)
+///     .upsert(true)
+///     .query(
+///         &client,
+///         mongod::Updates {
+///             inc: Some(inc),
+///             ..Default::default()
+///         },
+///     )
+///     .await?;
+/// println!("{:?}", counter);
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FindOneAndUpdate<C: Collection> {
+    filter: Option<Document>,
+    options: FindOneAndUpdateOptions,
+
+    query_type: PhantomData<C>,
+}
+
+impl<C: Collection> Default for FindOneAndUpdate<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Collection> FindOneAndUpdate<C> {
+    /// Constructs a `FindOneAndUpdate` querier.
+    pub fn new() -> Self {
+        Self {
+            filter: None,
+            options: FindOneAndUpdateOptions::default(),
+
+            query_type: PhantomData,
+        }
+    }
+
+    /// An array of filters specifying to which array elements an update should apply.
+    pub fn array_filters(mut self, filters: Vec<Document>) -> Self {
+        self.options.array_filters = Some(filters);
+        self
+    }
+
+    /// Opt out of document-level validation.
+    pub fn bypass_document_validation(mut self, enable: bool) -> Self {
+        self.options.bypass_document_validation = Some(enable);
+        self
+    }
+
+    /// The collation to use for the operation.
+    ///
+    /// Collation allows users to specify language-specific rules for string comparison, such as
+    /// rules for lettercase and accent marks.
+    pub fn collation(mut self, collation: Collation) -> Self {
+        self.options.collation = Some(collation);
+        self
+    }
+
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
+    /// The filter to use for the operation.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
+    where
+        F: FilterOf<C>,
+    {
+        self.filter = Some(filter.into_filter_document()?);
+        Ok(self)
+    }
+
+    /// A document or string that specifies the index to use to support the query predicate.
+    pub fn hint(mut self, value: Hint) -> Self {
+        self.options.hint = Some(value);
+        self
+    }
+
+    /// The maximum amount of time to allow the query to run.
+    pub fn max_time(mut self, duration: Duration) -> Self {
+        self.options.max_time = Some(duration);
+        self
+    }
+
+    /// Whether to return the document as it was before or after the update.
+    ///
+    /// Defaults to `ReturnDocument::Before`.
+    pub fn return_document(mut self, value: ReturnDocument) -> Self {
+        self.options.return_document = Some(value);
+        self
+    }
+
+    /// The order used to pick the document to update, should the filter match many.
+    pub fn sort<F>(mut self, sort: Sort<F>) -> Self
+    where
+        C: AsField<F>,
+        F: Field + Into<String>,
+    {
+        self.options.sort = Some(sort.into_document());
+        self
+    }
+
+    /// Insert a document if no matching document is found.
+    pub fn upsert(mut self, enable: bool) -> Self {
+        self.options.upsert = Some(enable);
+        self
+    }
+
+    /// The write concern for the operation.
+    pub fn write_concern(mut self, concern: WriteConcern) -> Self {
+        self.options.write_concern = Some(concern);
+        self
+    }
+
+    /// Query the database with this querier, returning the found document.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if:
+    /// - the updates could not be converted into a BSON `Document`.
+    /// - the mongodb encountered an error.
+    /// - the found document could not be converted into the `Collection`.
+    pub async fn query<U>(self, client: &Client, updates: Updates<U>) -> crate::Result<Option<C>>
+    where
+        C: AsUpdate<U>,
+        U: crate::update::Update,
+    {
+        let filter = self.filter.unwrap_or_default();
+        let collection = client.database().collection::<Document>(&C::collection());
+        client
+            .with_timeout(collection.find_one_and_update(
+                filter,
+                updates.into_document()?,
+                self.options,
+            ))
+            .await?
+            .map(C::from_document)
+            .transpose()
+    }
+
+    /// Query the database with this querier in a blocking context, returning the found
+    /// document.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if:
+    /// - the updates could not be converted into a BSON `Document`.
+    /// - the mongodb encountered an error.
+    /// - the found document could not be converted into the `Collection`.
+    #[cfg(feature = "blocking")]
+    pub fn blocking<U>(
+        self,
+        client: &crate::blocking::Client,
+        updates: Updates<U>,
+    ) -> crate::Result<Option<C>>
+    where
+        C: AsUpdate<U>,
+        U: crate::update::Update,
+    {
+        let resp = client.execute(crate::blocking::Request::FindOneAndUpdate(
+            C::collection(),
+            self.filter.unwrap_or_default(),
+            updates.into_document()?,
+            self.options,
+        ))?;
+        if let crate::blocking::Response::FindOneAndUpdate(r) = resp {
+            return r.map(C::from_document).transpose();
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
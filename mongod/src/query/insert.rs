@@ -34,7 +34,7 @@ use crate::r#async::Client;
 ///     .await
 ///     .unwrap();
 ///
-/// println!("(index: oid) {:?}", result);
+/// println!("(index: id) {:?}", result);
 /// # Ok(())
 /// # }
 /// # }
@@ -102,13 +102,11 @@ impl<C: Collection> Insert<C> {
             .into_iter()
             .map(|s| s.into_document())
             .collect::<Result<Vec<Document>, _>>()?;
+        let collection = client.database().collection(&C::collection());
         client
-            .database()
-            .collection(C::COLLECTION)
-            .insert_many(documents, self.options)
+            .with_timeout(collection.insert_many(documents, self.options))
             .await
             .map(|r| r.inserted_ids)
-            .map_err(crate::error::mongodb)
     }
 
     /// Query the database with this querier in a blocking context.
@@ -137,7 +135,7 @@ impl<C: Collection> Insert<C> {
             .collect::<Result<Vec<Document>, _>>()
             .map_err(crate::error::bson)?;
         let resp = client.execute(crate::blocking::Request::Insert(
-            C::COLLECTION,
+            C::collection(),
             documents,
             self.options,
         ))?;
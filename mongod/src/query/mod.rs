@@ -1,22 +1,54 @@
 //! The query operations that can be perfomed on a MongoDB.
+use mongodb::options::{Collation, CollationStrength};
+
 use crate::collection::Collection;
 
+mod aggregate;
+mod bulk;
 mod delete;
 mod find;
+mod find_one_and_delete;
+mod find_one_and_replace;
+mod find_one_and_update;
 mod insert;
 mod replace;
 mod update;
+mod watch;
 
+pub use self::aggregate::Aggregate;
+#[cfg(feature = "blocking")]
+pub(crate) use self::bulk::{bulk_write, BulkOptions, WriteModel};
+pub use self::bulk::{Bulk, BulkResult};
 pub use self::delete::Delete;
-pub use self::find::Find;
+pub use self::find::{Find, FindView};
+pub use self::find_one_and_delete::FindOneAndDelete;
+pub use self::find_one_and_replace::FindOneAndReplace;
+pub use self::find_one_and_update::FindOneAndUpdate;
 pub use self::insert::Insert;
 pub use self::replace::Replace;
 pub use self::update::Update;
+pub use self::watch::Watch;
 
 /// A convenience wrapper for easy access to queriers.
 pub struct Query;
 
 impl Query {
+    /// Returns an `Aggregate` querier.
+    pub fn aggregate<C>() -> Aggregate<C>
+    where
+        C: Collection,
+    {
+        Aggregate::new()
+    }
+
+    /// Returns a `Bulk` querier.
+    pub fn bulk<C>() -> Bulk<C>
+    where
+        C: Collection,
+    {
+        Bulk::new()
+    }
+
     /// Returns a `Delete` querier.
     pub fn delete<C>() -> Delete<C>
     where
@@ -33,6 +65,30 @@ impl Query {
         Find::new()
     }
 
+    /// Returns a `FindOneAndDelete` querier.
+    pub fn find_one_and_delete<C>() -> FindOneAndDelete<C>
+    where
+        C: Collection,
+    {
+        FindOneAndDelete::new()
+    }
+
+    /// Returns a `FindOneAndReplace` querier.
+    pub fn find_one_and_replace<C>() -> FindOneAndReplace<C>
+    where
+        C: Collection,
+    {
+        FindOneAndReplace::new()
+    }
+
+    /// Returns a `FindOneAndUpdate` querier.
+    pub fn find_one_and_update<C>() -> FindOneAndUpdate<C>
+    where
+        C: Collection,
+    {
+        FindOneAndUpdate::new()
+    }
+
     /// Returns a `Insert` querier.
     pub fn insert<C>() -> Insert<C>
     where
@@ -56,4 +112,35 @@ impl Query {
     {
         Update::new()
     }
+
+    /// Returns a `Watch`
+    pub fn watch<C>() -> Watch<C>
+    where
+        C: Collection,
+    {
+        Watch::new()
+    }
+}
+
+// Secondary strength compares base characters and accents, but not case.
+pub(crate) fn case_insensitive(locale: &str) -> Collation {
+    Collation::builder()
+        .locale(locale)
+        .strength(CollationStrength::Secondary)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_insensitive_collation() {
+        let collation = case_insensitive("en");
+        assert_eq!(collation.locale, "en");
+        assert!(matches!(
+            collation.strength,
+            Some(CollationStrength::Secondary)
+        ));
+    }
 }
@@ -1,10 +1,10 @@
 use std::marker::PhantomData;
 
-use bson::Document;
+use bson::{doc, Bson, Document};
 use mongodb::options::{Collation, Hint, ReplaceOptions, WriteConcern};
 
 use crate::collection::Collection;
-use crate::filter::{AsFilter, Filter};
+use crate::filter::FilterOf;
 use crate::r#async::Client;
 
 /// A querier to replace a document in a MongoDB collection.
@@ -87,6 +87,14 @@ impl<C: Collection> Replace<C> {
         self
     }
 
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
     /// The filter to use for the operation.
     ///
     /// # Errors
@@ -94,13 +102,18 @@ impl<C: Collection> Replace<C> {
     /// This method errors if the filter could not be converted into a BSON `Document`.
     pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
     where
-        C: AsFilter<F>,
-        F: Filter,
+        F: FilterOf<C>,
     {
-        self.filter = Some(filter.into_document()?);
+        self.filter = Some(filter.into_filter_document()?);
         Ok(self)
     }
 
+    // Filters on the document's `_id`.
+    pub(crate) fn id(mut self, id: Bson) -> Self {
+        self.filter = Some(doc! { "_id": id });
+        self
+    }
+
     /// A document or string that specifies the index to use to support the query predicate.
     pub fn hint(mut self, value: Hint) -> Self {
         self.options.hint = Some(value);
@@ -131,12 +144,10 @@ impl<C: Collection> Replace<C> {
             Some(f) => f,
             None => Document::new(),
         };
+        let collection = client.database().collection(&C::collection());
         let result = client
-            .database()
-            .collection(C::COLLECTION)
-            .replace_one(filter, document.into_document()?, self.options)
-            .await
-            .map_err(crate::error::mongodb)?;
+            .with_timeout(collection.replace_one(filter, document.into_document()?, self.options))
+            .await?;
         if result.modified_count > 0 {
             return Ok(true);
         }
@@ -161,7 +172,7 @@ impl<C: Collection> Replace<C> {
             None => bson::Document::new(),
         };
         let resp = client.execute(crate::blocking::Request::Replace(
-            C::COLLECTION,
+            C::collection(),
             filter,
             document.into_document()?,
             self.options,
@@ -1,12 +1,13 @@
 use std::marker::PhantomData;
 
-use bson::Document;
+use bson::{Bson, Document};
 use mongodb::options::{Collation, Hint, UpdateOptions, WriteConcern};
 
 use crate::collection::Collection;
-use crate::filter::{AsFilter, Filter};
+use crate::field::{AsField, Field};
+use crate::filter::{Filter, FilterOf};
 use crate::r#async::Client;
-use crate::update::{AsUpdate, Updates};
+use crate::update::{merge, AsUpdate, Position, Updates};
 
 /// A querier to update documents in a MongoDB collection.
 ///
@@ -55,11 +56,53 @@ use crate::update::{AsUpdate, Updates};
 /// # }
 /// ```
 ///
+/// Updates the elements of an array using a typed array filter.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Bson, Mongo, Deserialize, Serialize)]
+/// #[mongo(filter, update)]
+/// pub struct Grade {
+///     score: u32,
+///     passed: bool,
+/// }
+///
+/// #[derive(Bson, Mongo, Deserialize, Serialize)]
+/// #[mongo(collection="students", field, filter, update)]
+/// pub struct Student {
+///     name: String,
+///     #[mongo(serde)]
+///     grades: Vec<Grade>,
+/// }
+/// use mongod::{AsFilter, AsUpdate, Comparator, Position};
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut failed = Grade::filter();
+/// failed.score = Some(Comparator::Lt(50));
+///
+/// let mut update = Grade::update();
+/// update.passed = Some(false);
+///
+/// let updated = mongod::query::Update::<Student>::new()
+///     .positional(student::Field::Grades, Position::Filtered("g".to_owned()), update)?
+///     .array_filter("g", failed)?
+///     .query(&client, mongod::Updates::<student::Update>::default())
+///     .await?;
+/// # Ok(())
+/// # }
+/// # }
+/// ```
 #[derive(Clone)]
 pub struct Update<C: Collection> {
     filter: Option<Document>,
     many: bool,
     options: UpdateOptions,
+    positional: Document,
 
     query_type: std::marker::PhantomData<C>,
 }
@@ -77,6 +120,7 @@ impl<C: Collection> Update<C> {
             filter: None,
             many: true,
             options: UpdateOptions::default(),
+            positional: Document::new(),
 
             query_type: PhantomData,
         }
@@ -88,6 +132,24 @@ impl<C: Collection> Update<C> {
         self
     }
 
+    /// Adds a filter on the array elements bound to the `identifier`, for use with
+    /// `Position::Filtered`.
+    ///
+    /// The filter is one for the array's element type, whose fields are prefixed with the
+    /// identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the filter could not be converted into a BSON `Document`.
+    pub fn array_filter<F: Filter>(mut self, identifier: &str, filter: F) -> crate::Result<Self> {
+        let doc = prefix(identifier, filter.into_document()?);
+        self.options
+            .array_filters
+            .get_or_insert_with(Vec::new)
+            .push(doc);
+        Ok(self)
+    }
+
     /// Opt out of document-level validation.
     pub fn bypass_document_validation(mut self, enable: bool) -> Self {
         self.options.bypass_document_validation = Some(enable);
@@ -103,6 +165,14 @@ impl<C: Collection> Update<C> {
         self
     }
 
+    /// Use a case-insensitive collation for the operation, in the given locale, i.e. `"en"`.
+    ///
+    /// String comparisons will ignore lettercase but not accent marks. For an index to be used by
+    /// the operation it must have been created with the same collation.
+    pub fn collation_ci(self, locale: &str) -> Self {
+        self.collation(super::case_insensitive(locale))
+    }
+
     /// The filter to use for the operation.
     ///
     /// # Errors
@@ -110,10 +180,9 @@ impl<C: Collection> Update<C> {
     /// This method errors if the filter could not be converted into a BSON `Document`.
     pub fn filter<F>(mut self, filter: F) -> crate::Result<Self>
     where
-        C: AsFilter<F>,
-        F: Filter,
+        F: FilterOf<C>,
     {
-        self.filter = Some(filter.into_document()?);
+        self.filter = Some(filter.into_filter_document()?);
         Ok(self)
     }
 
@@ -131,6 +200,43 @@ impl<C: Collection> Update<C> {
         self
     }
 
+    /// Sets the fields of the elements of the array `field` that are targeted by the `position`,
+    /// where `update` is an update for the array's element type.
+    ///
+    /// These are merged into the `$set` of the updates given when querying, while any fields of
+    /// `update` that are unset are merged into its `$unset`.
+    ///
+    /// # Errors
+    ///
+    /// This method errors if the update could not be converted into a BSON `Document`.
+    pub fn positional<F, U>(
+        mut self,
+        field: F,
+        position: Position,
+        update: U,
+    ) -> crate::Result<Self>
+    where
+        C: AsField<F>,
+        F: Field + Into<String>,
+        U: crate::update::Update,
+    {
+        let path = position.path(&field.into());
+        let (set, unset) = update.into_set_document()?;
+        let mut document = Document::new();
+        if !set.is_empty() {
+            document.insert("$set", prefix(&path, set));
+        }
+        if !unset.is_empty() {
+            let unset = unset
+                .into_iter()
+                .map(|field| (field, Bson::from("")))
+                .collect();
+            document.insert("$unset", prefix(&path, unset));
+        }
+        merge(&mut self.positional, document);
+        Ok(self)
+    }
+
     /// Insert a document if no matching document is found.
     pub fn upsert(mut self, enable: bool) -> Self {
         self.options.upsert = Some(enable);
@@ -159,20 +265,18 @@ impl<C: Collection> Update<C> {
             Some(f) => f,
             None => bson::Document::new(),
         };
+        let mut updates = updates.into_document()?;
+        merge(&mut updates, self.positional);
+        let collection = client.database().collection::<Document>(&C::collection());
         let result = if self.many {
             client
-                .database()
-                .collection::<Document>(C::COLLECTION)
-                .update_many(filter, updates.into_document()?, self.options)
+                .with_timeout(collection.update_many(filter, updates, self.options))
                 .await
         } else {
             client
-                .database()
-                .collection::<Document>(C::COLLECTION)
-                .update_one(filter, updates.into_document()?, self.options)
+                .with_timeout(collection.update_one(filter, updates, self.options))
                 .await
-        }
-        .map_err(crate::error::mongodb)?;
+        }?;
         Ok(result.matched_count as i64)
     }
 
@@ -201,11 +305,13 @@ impl<C: Collection> Update<C> {
             Some(f) => f,
             None => bson::Document::new(),
         };
+        let mut updates = updates.into_document()?;
+        merge(&mut updates, self.positional);
         let resp = client.execute(crate::blocking::Request::Update(
             self.many,
-            C::COLLECTION,
+            C::collection(),
             filter,
-            updates.into_document()?,
+            updates,
             self.options,
         ))?;
         if let crate::blocking::Response::Update(r) = resp {
@@ -216,3 +322,103 @@ impl<C: Collection> Update<C> {
         ))
     }
 }
+
+fn prefix(path: &str, doc: Document) -> Document {
+    doc.into_iter()
+        .map(|(k, v)| (format!("{}.{}", path, k), v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::update::FieldUpdate;
+
+    struct Grade {
+        passed: FieldUpdate<bool>,
+        note: FieldUpdate<String>,
+    }
+
+    impl crate::update::Update for Grade {
+        fn new() -> Self {
+            Grade {
+                passed: FieldUpdate::Keep,
+                note: FieldUpdate::Keep,
+            }
+        }
+
+        fn into_document(self) -> crate::Result<Document> {
+            Ok(self.into_set_document()?.0)
+        }
+
+        fn into_set_document(self) -> crate::Result<(Document, Vec<String>)> {
+            let (mut set, mut unset) = (Document::new(), vec![]);
+            if let FieldUpdate::Set(passed) = self.passed {
+                set.insert("passed", passed);
+            }
+            match self.note {
+                FieldUpdate::Set(note) => {
+                    set.insert("note", note);
+                }
+                FieldUpdate::Unset => unset.push("note".to_owned()),
+                _ => {}
+            }
+            Ok((set, unset))
+        }
+    }
+
+    struct Student;
+
+    impl Collection for Student {
+        const COLLECTION: &'static str = "students";
+
+        fn from_document(_: Document) -> crate::Result<Self> {
+            Ok(Student)
+        }
+
+        fn into_document(self) -> crate::Result<Document> {
+            Ok(Document::new())
+        }
+    }
+
+    struct Grades;
+
+    impl Field for Grades {}
+
+    impl From<Grades> for String {
+        fn from(_: Grades) -> String {
+            "grades".to_owned()
+        }
+    }
+
+    impl AsField<Grades> for Student {}
+
+    #[test]
+    fn merge_positional() {
+        let grade = Grade {
+            passed: FieldUpdate::Set(false),
+            note: FieldUpdate::Unset,
+        };
+        let update = Update::<Student>::new()
+            .positional(Grades, Position::Filtered("g".to_owned()), grade)
+            .unwrap();
+        assert_eq!(
+            update.positional,
+            doc! {
+                "$set": { "grades.$[g].passed": false },
+                "$unset": { "grades.$[g].note": "" },
+            }
+        );
+
+        let mut updates = doc! { "$set": { "name": "foo" }, "$unset": { "age": "" } };
+        merge(&mut updates, update.positional);
+        assert_eq!(
+            updates,
+            doc! {
+                "$set": { "name": "foo", "grades.$[g].passed": false },
+                "$unset": { "age": "", "grades.$[g].note": "" },
+            }
+        );
+    }
+}
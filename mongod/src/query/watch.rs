@@ -0,0 +1,201 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bson::Timestamp;
+use mongodb::bson::Document;
+use mongodb::change_stream::event::ResumeToken;
+use mongodb::options::{
+    ChangeStreamOptions, Collation, FullDocumentBeforeChangeType, FullDocumentType,
+};
+
+use crate::change_stream::TokenStore;
+use crate::collection::Collection;
+use crate::r#async::{ChangeStream, Client};
+
+/// A querier to watch the changes made to a MongoDB collection.
+///
+/// # Examples
+///
+/// Print the users as they are inserted.
+///
+/// ```no_run
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+///
+/// use futures::stream::StreamExt;
+///
+/// use mongod::change_stream::Change;
+///
+/// #[derive(Debug, Bson, Mongo)]
+/// #[mongo(collection="users", field, filter, update)]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let mut stream = mongod::query::Watch::<User>::new().query(&client).await?;
+/// while let Some(res) = stream.next().await {
+///     if let Change::Insert(user) = res?.change {
+///         println!("{:?}", user);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Watch<C: Collection> {
+    pipeline: Vec<Document>,
+    options: ChangeStreamOptions,
+
+    query_type: PhantomData<C>,
+}
+
+impl<C: Collection> Default for Watch<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Collection> Watch<C> {
+    /// Constructs a `Watch` querier for every change made to the collection.
+    pub fn new() -> Self {
+        Self {
+            pipeline: vec![],
+            options: ChangeStreamOptions::default(),
+
+            query_type: PhantomData,
+        }
+    }
+
+    /// The number of events the server should return per batch.
+    pub fn batch_size(mut self, size: u32) -> Self {
+        self.options.batch_size = Some(size);
+        self
+    }
+
+    /// The collation to use for the operation.
+    pub fn collation(mut self, value: Collation) -> Self {
+        self.options.collation = Some(value);
+        self
+    }
+
+    /// Configures when the document after the change is returned with an event.
+    ///
+    /// By default it is not returned for updates.
+    pub fn full_document(mut self, value: FullDocumentType) -> Self {
+        self.options.full_document = Some(value);
+        self
+    }
+
+    /// Configures when the document before the change is returned with an event.
+    ///
+    /// This requires pre-images to be enabled on the collection.
+    pub fn full_document_before_change(mut self, value: FullDocumentBeforeChangeType) -> Self {
+        self.options.full_document_before_change = Some(value);
+        self
+    }
+
+    /// The maximum amount of time for the server to wait on new events.
+    pub fn max_await_time(mut self, duration: Duration) -> Self {
+        self.options.max_await_time = Some(duration);
+        self
+    }
+
+    /// Appends the stages to the pipeline used to filter or modify the events.
+    pub fn pipeline<I>(mut self, stages: I) -> Self
+    where
+        I: IntoIterator<Item = Document>,
+    {
+        self.pipeline.extend(stages);
+        self
+    }
+
+    /// Resumes watching after the event with the given token.
+    ///
+    /// This cannot be used along side `start_after`.
+    pub fn resume_after(mut self, token: ResumeToken) -> Self {
+        self.options.resume_after = Some(token);
+        self
+    }
+
+    /// Resumes watching after the token held by the store, should it hold one.
+    ///
+    /// This uses `start_after`, so that watching can also be resumed after an invalidate event.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the token could not be loaded from the store.
+    pub fn resume_from<S>(self, store: &S) -> crate::Result<Self>
+    where
+        S: TokenStore + ?Sized,
+    {
+        Ok(match store.load()? {
+            Some(token) => self.start_after(token),
+            None => self,
+        })
+    }
+
+    /// Appends a stage to the pipeline used to filter or modify the events.
+    pub fn stage(mut self, stage: Document) -> Self {
+        self.pipeline.push(stage);
+        self
+    }
+
+    /// Starts watching after the event with the given token.
+    ///
+    /// Unlike `resume_after`, this can be used to start watching after an invalidate event, such
+    /// as the collection being dropped.
+    pub fn start_after(mut self, token: ResumeToken) -> Self {
+        self.options.start_after = Some(token);
+        self
+    }
+
+    /// Only watch for changes that occurred at or after the given operation time.
+    pub fn start_at_operation_time(mut self, time: Timestamp) -> Self {
+        self.options.start_at_operation_time = Some(time);
+        self
+    }
+
+    /// Query the database with this querier.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    pub async fn query(self, client: &Client) -> crate::Result<ChangeStream<C>> {
+        let collection = client.database().collection::<Document>(&C::collection());
+        client
+            .with_timeout(collection.watch(self.pipeline, self.options))
+            .await
+            .map(ChangeStream::from)
+    }
+
+    /// Query the database with this querier in a blocking context.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `blocking` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the mongodb encountered an error.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(
+        self,
+        client: &crate::blocking::Client,
+    ) -> crate::Result<crate::blocking::ChangeStream<C>> {
+        let resp = client.execute(crate::blocking::Request::Watch(
+            C::collection(),
+            self.pipeline,
+            self.options,
+        ))?;
+        if let crate::blocking::Response::Watch(r) = resp {
+            return Ok(r.into());
+        }
+        Err(crate::error::runtime(
+            "incorrect response from blocking client",
+        ))
+    }
+}
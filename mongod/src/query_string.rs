@@ -0,0 +1,226 @@
+//! Parsing of URL query strings into filters & sorts.
+//!
+//! This is used by the `Mongo` derive's `#[mongo(query_string)]` attribute to build a derived
+//! `Filter` and `Sort` from query strings such as `?name=foo&age[gte]=18&sort=-created_at`.
+//!
+//! - `field=value` is equivalent to `field[eq]=value`
+//! - `field[op]=value` uses one of the comparators `eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`,
+//!   `nin` or `exists`, where `in` & `nin` take a comma separated list of values
+//! - `sort=field,-other` sorts ascending on `field` and then descending on `other`
+//!
+//! Values are converted into the field's type by trying, in order, a 32-bit integer, a 64-bit
+//! integer, a double, a boolean, an object id, `null` and finally a string.
+
+use std::convert::TryInto;
+use std::str::FromStr;
+
+use bson::oid::ObjectId;
+use bson::Bson;
+use percent_encoding::percent_decode_str;
+
+use crate::error::Error;
+use crate::ext;
+use crate::field::Field;
+use crate::filter::Comparator;
+use crate::sort::{Order, Sort};
+
+/// The reserved query string parameter used for sorting.
+pub const SORT: &str = "sort";
+
+/// A single parameter parsed from a query string.
+#[derive(Debug, PartialEq)]
+pub struct Param {
+    /// The name of the field being filtered.
+    pub field: String,
+    /// The comparator operator, if one was given using `field[op]`.
+    pub op: Option<String>,
+    /// The value to compare against.
+    pub value: String,
+}
+
+fn decode(s: &str) -> crate::Result<String> {
+    percent_decode_str(&s.replace('+', " "))
+        .decode_utf8()
+        .map(|s| s.into_owned())
+        .map_err(Error::invalid_query_string)
+}
+
+/// Parses a query string into its parameters.
+///
+/// # Errors
+///
+/// This function errors if the query string is not valid UTF-8 once decoded, or if a parameter's
+/// operator is malformed.
+pub fn parse(query: &str) -> crate::Result<Vec<Param>> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = decode(key)?;
+            let value = decode(value)?;
+            let (field, op) = match key.split_once('[') {
+                Some((field, op)) => match op.strip_suffix(']') {
+                    Some(op) if !op.is_empty() => (field.to_owned(), Some(op.to_owned())),
+                    _ => {
+                        return Err(Error::invalid_query_string(format!(
+                            "malformed parameter `{}`",
+                            key
+                        )))
+                    }
+                },
+                None => (key, None),
+            };
+            Ok(Param { field, op, value })
+        })
+        .collect()
+}
+
+fn convert<T, E, F>(value: &str, convert: &F) -> crate::Result<T>
+where
+    F: Fn(Bson) -> Result<T, E>,
+{
+    let mut candidates = vec![];
+    if let Ok(i) = value.parse::<i32>() {
+        candidates.push(Bson::Int32(i));
+    }
+    if let Ok(i) = value.parse::<i64>() {
+        candidates.push(Bson::Int64(i));
+    }
+    if let Ok(f) = value.parse::<f64>() {
+        candidates.push(Bson::Double(f));
+    }
+    if let Ok(b) = value.parse::<bool>() {
+        candidates.push(Bson::Boolean(b));
+    }
+    if let Ok(oid) = ObjectId::parse_str(value) {
+        candidates.push(Bson::ObjectId(oid));
+    }
+    if value == "null" {
+        candidates.push(Bson::Null);
+    }
+    candidates.push(Bson::String(value.to_owned()));
+    candidates
+        .into_iter()
+        .find_map(|c| convert(c).ok())
+        .ok_or_else(|| Error::invalid_query_string(format!("invalid value `{}`", value)))
+}
+
+/// Builds a `Comparator` from a parameter's operator & value, using `convert` to convert the
+/// value into the field's type.
+///
+/// # Errors
+///
+/// This function errors if the operator is unknown, or if the value could not be converted.
+pub fn comparator<T, E, F>(
+    op: Option<&str>,
+    value: &str,
+    convert: F,
+) -> crate::Result<Comparator<T>>
+where
+    T: TryInto<ext::bson::Bson>,
+    T::Error: Into<ext::bson::ser::Error>,
+    F: Fn(Bson) -> Result<T, E>,
+{
+    let many = || -> crate::Result<Vec<T>> {
+        value
+            .split(',')
+            .map(|v| self::convert(v, &convert))
+            .collect()
+    };
+    Ok(match op.unwrap_or("eq") {
+        "eq" => Comparator::Eq(self::convert(value, &convert)?),
+        "exists" => Comparator::Exists(value.parse().map_err(Error::invalid_query_string)?),
+        "gt" => Comparator::Gt(self::convert(value, &convert)?),
+        "gte" => Comparator::Gte(self::convert(value, &convert)?),
+        "in" => Comparator::In(many()?),
+        "lt" => Comparator::Lt(self::convert(value, &convert)?),
+        "lte" => Comparator::Lte(self::convert(value, &convert)?),
+        "ne" => Comparator::Ne(self::convert(value, &convert)?),
+        "nin" => Comparator::Nin(many()?),
+        op => {
+            return Err(Error::invalid_query_string(format!(
+                "unknown operator `{}`",
+                op
+            )))
+        }
+    })
+}
+
+/// Builds a `Sort` from a comma separated list of fields, where fields prefixed with `-` are
+/// sorted in descending order.
+///
+/// # Errors
+///
+/// This function errors if any of the fields are unknown.
+pub fn sort<F>(value: &str) -> crate::Result<Sort<F>>
+where
+    F: Field + FromStr<Err = Error> + Into<String>,
+{
+    let mut sort = Sort::new();
+    for field in value.split(',').filter(|f| !f.is_empty()) {
+        match field.strip_prefix('-') {
+            Some(field) => sort.push(field.parse()?, Order::Desc),
+            None => sort.push(field.parse()?, Order::Asc),
+        };
+    }
+    Ok(sort)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+
+    #[test]
+    fn parse_query_string() {
+        let params = parse("?name=foo+bar&age[gte]=18&empty&email=a%40b.c").unwrap();
+        assert_eq!(
+            params,
+            vec![
+                Param {
+                    field: "name".to_owned(),
+                    op: None,
+                    value: "foo bar".to_owned(),
+                },
+                Param {
+                    field: "age".to_owned(),
+                    op: Some("gte".to_owned()),
+                    value: "18".to_owned(),
+                },
+                Param {
+                    field: "empty".to_owned(),
+                    op: None,
+                    value: "".to_owned(),
+                },
+                Param {
+                    field: "email".to_owned(),
+                    op: None,
+                    value: "a@b.c".to_owned(),
+                },
+            ]
+        );
+        assert!(parse("age[gte=18").is_err());
+        assert!(parse("age[]=18").is_err());
+    }
+
+    #[test]
+    fn comparator_from_query_string() {
+        let to_u32 = |b| u32::try_from(ext::bson::Bson(b));
+        let to_string = |b| String::try_from(ext::bson::Bson(b));
+
+        let c = comparator(Some("gte"), "18", to_u32).unwrap();
+        assert_eq!(Bson::try_from(c).unwrap(), bson!({ "$gte": 18 }));
+        let c = comparator(None, "18", to_string).unwrap();
+        assert_eq!(Bson::try_from(c).unwrap(), bson!({ "$eq": "18" }));
+        let c = comparator(Some("in"), "1,2", to_u32).unwrap();
+        assert_eq!(Bson::try_from(c).unwrap(), bson!({ "$in": [1, 2] }));
+        let c = comparator(Some("exists"), "false", to_u32).unwrap();
+        assert_eq!(Bson::try_from(c).unwrap(), bson!({ "$exists": false }));
+
+        assert!(comparator(None, "foo", to_u32).is_err());
+        assert!(comparator(Some("like"), "foo", to_string).is_err());
+    }
+}
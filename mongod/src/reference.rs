@@ -0,0 +1,188 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use bson::oid::ObjectId;
+use bson::{doc, Document};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::collection::Collection;
+use crate::ext;
+use crate::schema::JsonSchema;
+
+/// A typed reference to a document in another collection.
+///
+/// A `Ref` is stored as the `ObjectId` of the referenced document, and can be resolved using
+/// [`Client::resolve`](`crate::Client::resolve`) or
+/// [`Client::resolve_many`](`crate::Client::resolve_many`).
+///
+/// # Examples
+///
+/// ```
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::bson::oid::ObjectId;
+/// use mongod::Ref;
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users")]
+/// pub struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "posts")]
+/// pub struct Post {
+///     author: Ref<User>,
+///     title: String,
+/// }
+///
+/// # async fn doc() -> Result<(), mongod::Error> {
+/// let client = mongod::Client::new();
+///
+/// let post = Post { author: Ref::new(ObjectId::new()), title: "foo".to_owned() };
+/// let author: Option<User> = client.resolve(&post.author).await?;
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+pub struct Ref<T> {
+    id: ObjectId,
+    collection: PhantomData<fn() -> T>,
+}
+
+impl<T> Ref<T> {
+    /// Constructs a new `Ref` to the document with the given `_id`.
+    pub fn new(id: ObjectId) -> Self {
+        Ref {
+            id,
+            collection: PhantomData,
+        }
+    }
+
+    /// The `_id` of the referenced document.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<T: Collection> Ref<T> {
+    /// Constructs a `Ref` to the given document, if it holds an `ObjectId` as its `_id`.
+    pub fn to(document: &T) -> Option<Self> {
+        match document.id()? {
+            bson::Bson::ObjectId(id) => Some(Ref::new(id)),
+            _ => None,
+        }
+    }
+
+    // The filter matching all of the referenced documents.
+    pub(crate) fn filter(refs: &[Self]) -> Document {
+        let ids = refs.iter().map(|r| r.id).collect::<Vec<_>>();
+        doc! { "_id": { "$in": ids } }
+    }
+}
+
+// NOTE: These are implemented by hand as deriving them would bound `T`
+impl<T> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Ref<T> {}
+
+impl<T> fmt::Debug for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Ref").field(&self.id).finish()
+    }
+}
+
+impl<T> PartialEq for Ref<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Ref<T> {}
+
+impl<T> Hash for Ref<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl<T> From<ObjectId> for Ref<T> {
+    fn from(id: ObjectId) -> Self {
+        Ref::new(id)
+    }
+}
+
+impl<T> From<Ref<T>> for ObjectId {
+    fn from(r: Ref<T>) -> Self {
+        r.id
+    }
+}
+
+impl<T> From<Ref<T>> for bson::Bson {
+    fn from(r: Ref<T>) -> Self {
+        bson::Bson::ObjectId(r.id)
+    }
+}
+
+impl<T> From<Ref<T>> for ext::bson::Bson {
+    fn from(r: Ref<T>) -> Self {
+        ext::bson::Bson(r.into())
+    }
+}
+
+impl<T> TryFrom<ext::bson::Bson> for Ref<T> {
+    type Error = ext::bson::de::Error;
+    fn try_from(bson: ext::bson::Bson) -> Result<Self, Self::Error> {
+        Ok(Ref::new(ObjectId::try_from(bson)?))
+    }
+}
+
+impl<T> Serialize for Ref<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Ref<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ObjectId::deserialize(deserializer).map(Ref::new)
+    }
+}
+
+impl<T> JsonSchema for Ref<T> {
+    fn json_schema() -> Document {
+        ObjectId::json_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User;
+
+    #[test]
+    fn ref_to_bson() {
+        let id = ObjectId::new();
+        let r: Ref<User> = Ref::new(id);
+        assert_eq!(bson::Bson::from(r), bson::Bson::ObjectId(id));
+        let r: Ref<User> = Ref::try_from(ext::bson::Bson(bson::Bson::ObjectId(id))).unwrap();
+        assert_eq!(r.id(), id);
+        assert!(Ref::<User>::try_from(ext::bson::Bson(bson::Bson::Int32(1))).is_err());
+    }
+
+    #[test]
+    fn ref_serde() {
+        let id = ObjectId::new();
+        let r: Ref<User> = Ref::new(id);
+        assert_eq!(bson::to_bson(&r).unwrap(), bson::Bson::ObjectId(id));
+        let r: Ref<User> = bson::from_bson(bson::Bson::ObjectId(id)).unwrap();
+        assert_eq!(r, Ref::new(id));
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use bson::{doc, Bson, Document};
+
+/// Used to describe a type as a [`$jsonSchema`][schema].
+///
+/// This trait is implemented for the types supported by [`ext::bson`](`crate::ext::bson`) and can
+/// be derived for structs using `#[mongo(json_schema)]`, allowing server-side validation to be
+/// generated from the Rust definition.
+///
+/// [schema]: https://www.mongodb.com/docs/manual/reference/operator/query/jsonSchema/
+///
+/// # Examples
+///
+/// Using a schema as a collection's validator.
+///
+/// ```
+/// use mongod::bson::doc;
+/// use mongod::JsonSchema;
+///
+/// pub struct Name(String);
+///
+/// impl JsonSchema for Name {
+///     fn json_schema() -> mongod::bson::Document {
+///         doc! { "bsonType": "string", "minLength": 1 }
+///     }
+/// }
+///
+/// let validator = doc! { "$jsonSchema": Name::json_schema() };
+/// assert_eq!(validator, doc! { "$jsonSchema": { "bsonType": "string", "minLength": 1 } });
+/// ```
+pub trait JsonSchema {
+    /// The schema describing the BSON representation of the type.
+    fn json_schema() -> Document;
+
+    /// Whether a field of this type must be present in its parent document.
+    ///
+    /// By default this is `true`, it is only `false` for `Option`.
+    fn required() -> bool {
+        true
+    }
+
+    /// The schema describing a `Vec` of the type.
+    ///
+    /// By default this is an array of the type, it is only binary for `u8`.
+    #[doc(hidden)]
+    fn vec_json_schema() -> Document {
+        doc! { "bsonType": "array", "items": Self::json_schema() }
+    }
+}
+
+macro_rules! bson_type {
+    ($ty:ty, $name:literal) => {
+        impl JsonSchema for $ty {
+            fn json_schema() -> Document {
+                doc! { "bsonType": $name }
+            }
+        }
+    };
+}
+bson_type!(bson::Binary, "binData");
+bson_type!(bson::DateTime, "date");
+bson_type!(bson::Decimal128, "decimal");
+bson_type!(bson::Document, "object");
+bson_type!(bson::JavaScriptCodeWithScope, "javascriptWithScope");
+bson_type!(bson::oid::ObjectId, "objectId");
+bson_type!(bson::Regex, "regex");
+bson_type!(bson::Timestamp, "timestamp");
+bson_type!(&str, "string");
+bson_type!(bool, "bool");
+bson_type!(char, "string");
+bson_type!(f32, "double");
+bson_type!(f64, "double");
+bson_type!(i8, "int");
+bson_type!(i16, "int");
+bson_type!(i32, "int");
+bson_type!(i64, "long");
+bson_type!(u16, "int");
+bson_type!(String, "string");
+bson_type!(std::borrow::Cow<'_, str>, "string");
+bson_type!(&std::path::Path, "string");
+bson_type!(std::path::PathBuf, "string");
+#[cfg(feature = "bytes")]
+bson_type!(bytes::Bytes, "binData");
+#[cfg(feature = "bytes")]
+bson_type!(bytes::BytesMut, "binData");
+#[cfg(feature = "chrono")]
+bson_type!(chrono::DateTime<chrono::Utc>, "date");
+#[cfg(feature = "chrono")]
+bson_type!(chrono::DateTime<chrono::FixedOffset>, "date");
+#[cfg(feature = "chrono")]
+bson_type!(chrono::NaiveDate, "date");
+#[cfg(feature = "chrono")]
+bson_type!(chrono::NaiveDateTime, "date");
+#[cfg(feature = "chrono")]
+bson_type!(chrono::NaiveTime, "date");
+#[cfg(feature = "ordered-float")]
+bson_type!(ordered_float::NotNan<f32>, "double");
+#[cfg(feature = "ordered-float")]
+bson_type!(ordered_float::NotNan<f64>, "double");
+#[cfg(feature = "ordered-float")]
+bson_type!(ordered_float::OrderedFloat<f32>, "double");
+#[cfg(feature = "ordered-float")]
+bson_type!(ordered_float::OrderedFloat<f64>, "double");
+#[cfg(feature = "rust_decimal")]
+bson_type!(rust_decimal::Decimal, "decimal");
+#[cfg(feature = "time")]
+bson_type!(time::Date, "date");
+#[cfg(feature = "time")]
+bson_type!(time::OffsetDateTime, "date");
+#[cfg(feature = "url")]
+bson_type!(url::Url, "string");
+#[cfg(feature = "uuid")]
+bson_type!(uuid::Uuid, "binData");
+
+macro_rules! non_zero {
+    ($ty:ty, $int:ty) => {
+        impl JsonSchema for $ty {
+            fn json_schema() -> Document {
+                let mut schema = <$int>::json_schema();
+                schema.insert("not", doc! { "enum": [0] });
+                schema
+            }
+        }
+    };
+}
+non_zero!(std::num::NonZeroI8, i8);
+non_zero!(std::num::NonZeroI16, i16);
+non_zero!(std::num::NonZeroI32, i32);
+non_zero!(std::num::NonZeroI64, i64);
+non_zero!(std::num::NonZeroU8, u8);
+non_zero!(std::num::NonZeroU16, u16);
+non_zero!(std::num::NonZeroU32, u32);
+non_zero!(std::num::NonZeroU64, u64);
+
+impl JsonSchema for u8 {
+    fn json_schema() -> Document {
+        doc! { "bsonType": "int" }
+    }
+
+    fn vec_json_schema() -> Document {
+        doc! { "bsonType": "binData" }
+    }
+}
+
+// Unsigned integers are widened when too large for their signed equivalent
+impl JsonSchema for u32 {
+    fn json_schema() -> Document {
+        doc! { "bsonType": ["int", "long"] }
+    }
+}
+
+impl JsonSchema for u64 {
+    fn json_schema() -> Document {
+        doc! { "bsonType": ["long", "decimal"] }
+    }
+}
+
+impl JsonSchema for Bson {
+    fn json_schema() -> Document {
+        // Any BSON value is valid
+        Document::new()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    fn json_schema() -> Document {
+        let mut schema = T::json_schema();
+        match schema.get_mut("bsonType") {
+            Some(Bson::String(ty)) => {
+                let ty = std::mem::take(ty);
+                schema.insert("bsonType", vec![ty, "null".to_owned()]);
+            }
+            Some(Bson::Array(tys)) if !tys.contains(&Bson::String("null".to_owned())) => {
+                tys.push(Bson::String("null".to_owned()));
+            }
+            _ => {}
+        }
+        if let Some(Bson::Array(values)) = schema.get_mut("enum") {
+            if !values.contains(&Bson::Null) {
+                values.push(Bson::Null);
+            }
+        }
+        schema
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+// Smart pointers are stored as their pointee
+macro_rules! pointer {
+    ($ty:ident) => {
+        impl<T: JsonSchema> JsonSchema for $ty<T> {
+            fn json_schema() -> Document {
+                T::json_schema()
+            }
+
+            fn required() -> bool {
+                T::required()
+            }
+        }
+    };
+}
+pointer!(Box);
+pointer!(Rc);
+pointer!(Arc);
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn json_schema() -> Document {
+        T::vec_json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for VecDeque<T> {
+    fn json_schema() -> Document {
+        Vec::<T>::json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for LinkedList<T> {
+    fn json_schema() -> Document {
+        Vec::<T>::json_schema()
+    }
+}
+
+impl<T: JsonSchema, const N: usize> JsonSchema for [T; N] {
+    fn json_schema() -> Document {
+        let mut schema = Vec::<T>::json_schema();
+        if schema.get_str("bsonType") == Ok("array") {
+            schema.insert("minItems", N as i64);
+            schema.insert("maxItems", N as i64);
+        }
+        schema
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> JsonSchema for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: JsonSchema,
+{
+    fn json_schema() -> Document {
+        Vec::<A::Item>::json_schema()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T: JsonSchema, const CAP: usize> JsonSchema for arrayvec::ArrayVec<T, CAP> {
+    fn json_schema() -> Document {
+        let mut schema = Vec::<T>::json_schema();
+        if schema.get_str("bsonType") == Ok("array") {
+            schema.insert("maxItems", CAP as i64);
+        }
+        schema
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for HashSet<T> {
+    fn json_schema() -> Document {
+        doc! { "bsonType": "array", "items": T::json_schema(), "uniqueItems": true }
+    }
+}
+
+impl<K, V: JsonSchema> JsonSchema for HashMap<K, V> {
+    fn json_schema() -> Document {
+        doc! { "bsonType": "object", "additionalProperties": V::json_schema() }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<T: JsonSchema> JsonSchema for indexmap::IndexSet<T> {
+    fn json_schema() -> Document {
+        HashSet::<T>::json_schema()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V: JsonSchema> JsonSchema for indexmap::IndexMap<K, V> {
+    fn json_schema() -> Document {
+        HashMap::<K, V>::json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeSet<T> {
+    fn json_schema() -> Document {
+        doc! { "bsonType": "array", "items": T::json_schema(), "uniqueItems": true }
+    }
+}
+
+impl<K, V: JsonSchema> JsonSchema for BTreeMap<K, V> {
+    fn json_schema() -> Document {
+        doc! { "bsonType": "object", "additionalProperties": V::json_schema() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_json_schema() {
+        assert_eq!(
+            Option::<String>::json_schema(),
+            doc! { "bsonType": ["string", "null"] }
+        );
+        assert_eq!(Option::<Bson>::json_schema(), doc! {});
+        assert!(!Option::<String>::required());
+        assert!(String::required());
+    }
+
+    #[test]
+    fn pointer_json_schema() {
+        assert_eq!(Box::<String>::json_schema(), String::json_schema());
+        assert_eq!(
+            Arc::<Option<i32>>::json_schema(),
+            doc! { "bsonType": ["int", "null"] }
+        );
+        assert!(!Rc::<Option<i32>>::required());
+    }
+
+    #[test]
+    fn non_zero_json_schema() {
+        assert_eq!(
+            Option::<std::num::NonZeroU64>::json_schema(),
+            doc! { "bsonType": ["long", "decimal", "null"], "not": { "enum": [0] } }
+        );
+    }
+
+    #[test]
+    fn collection_json_schema() {
+        assert_eq!(
+            Vec::<Option<i64>>::json_schema(),
+            doc! { "bsonType": "array", "items": { "bsonType": ["long", "null"] } }
+        );
+        assert_eq!(
+            <[u16; 2]>::json_schema(),
+            doc! { "bsonType": "array", "items": { "bsonType": "int" }, "minItems": 2_i64, "maxItems": 2_i64 }
+        );
+        assert_eq!(<[u8; 2]>::json_schema(), doc! { "bsonType": "binData" });
+        assert_eq!(
+            Option::<Vec<u8>>::json_schema(),
+            doc! { "bsonType": ["binData", "null"] }
+        );
+        assert_eq!(
+            HashMap::<String, bool>::json_schema(),
+            doc! { "bsonType": "object", "additionalProperties": { "bsonType": "bool" } }
+        );
+    }
+}
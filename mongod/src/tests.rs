@@ -0,0 +1,28 @@
+//! Fixtures shared by the unit tests.
+
+use bson::Document;
+
+use crate::collection::Collection;
+use crate::error::Error;
+
+#[derive(Debug)]
+pub(crate) struct User {
+    pub(crate) name: String,
+}
+
+impl Collection for User {
+    const COLLECTION: &'static str = "users";
+
+    fn from_document(document: Document) -> crate::Result<Self> {
+        Ok(User {
+            name: document
+                .get_str("name")
+                .map_err(Error::invalid_document)?
+                .to_owned(),
+        })
+    }
+
+    fn into_document(self) -> crate::Result<Document> {
+        Ok(doc! { "name": self.name })
+    }
+}
@@ -1,6 +1,8 @@
-use bson::Document;
+use bson::{Bson, Document};
+use serde::{Serialize, Serializer};
 
 use crate::error::Error;
+use crate::field::Field;
 
 /// Used to tie a type implementing [`Collection`](./trait.Collection.html) to its companion `Update` type.
 ///
@@ -91,6 +93,105 @@ pub trait Update {
     fn new() -> Self;
     /// Converts a `Filter` into a BSON `Document`.
     fn into_document(self) -> Result<Document, Error>;
+    /// Converts an `Update` into a BSON `Document` for use with `$set`, along with the paths of
+    /// any fields that should be unset instead.
+    ///
+    /// By default no fields are unset, derived updates return those marked as
+    /// [`FieldUpdate::Unset`](./enum.FieldUpdate.html#variant.Unset).
+    fn into_set_document(self) -> Result<(Document, Vec<String>), Error>
+    where
+        Self: Sized,
+    {
+        Ok((self.into_document()?, vec![]))
+    }
+}
+
+/// The update to make to an optional field of a derived `Update`.
+///
+/// With a plain `Option` there is no way to tell apart leaving a field untouched, setting it to
+/// `null` and removing it from the document altogether, this makes that choice explicit.
+///
+/// # Examples
+///
+/// ```
+/// # mod wrap {
+/// # use mongod_derive::Mongo;
+/// use mongod::{AsUpdate, FieldUpdate, Updates};
+///
+/// # #[derive(mongod_derive::Bson)]
+/// #[derive(Mongo)]
+/// #[mongo(update)]
+/// pub struct User {
+///     name: String,
+///     age: Option<u32>,
+///     email: Option<String>,
+/// }
+///
+/// # pub fn main() {
+/// let mut update = User::update();
+/// update.age = FieldUpdate::Null;
+/// update.email = FieldUpdate::Unset;
+///
+/// let updates = Updates {
+///     set: Some(update),
+///     ..Updates::default()
+/// };
+/// assert_eq!(
+///     updates.into_document().unwrap(),
+///     mongod::bson::doc! { "$set": { "age": null }, "$unset": { "email": "" } },
+/// );
+/// # }
+/// # }
+/// # wrap::main();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FieldUpdate<T> {
+    /// Leaves the field untouched.
+    #[default]
+    Keep,
+    /// Sets the field to the value.
+    Set(T),
+    /// Sets the field to `null`.
+    Null,
+    /// Removes the field from the document.
+    Unset,
+}
+
+impl<T> FieldUpdate<T> {
+    /// Returns `true` if the field is left untouched.
+    pub fn is_keep(&self) -> bool {
+        matches!(self, FieldUpdate::Keep)
+    }
+
+    /// Returns `true` if the field is removed from the document.
+    pub fn is_unset(&self) -> bool {
+        matches!(self, FieldUpdate::Unset)
+    }
+
+    /// Returns `true` if the field has no value to write, in that it is either left untouched or
+    /// removed from the document.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, FieldUpdate::Keep | FieldUpdate::Unset)
+    }
+}
+
+/// Converts an optional value, where `None` sets the field to `null`.
+impl<T> From<Option<T>> for FieldUpdate<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => FieldUpdate::Set(value),
+            None => FieldUpdate::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for FieldUpdate<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FieldUpdate::Set(value) => serializer.serialize_some(value),
+            _ => serializer.serialize_none(),
+        }
+    }
 }
 
 /// Used for complex updates using MongoDB's update operators.
@@ -176,26 +277,264 @@ pub trait Update {
 // TODO: Implement the other update operators: https://docs.mongodb.com/manual/reference/operator/update/#id1
 #[derive(Default)]
 pub struct Updates<U: Update> {
+    /// Performs a bitwise update of the field.
+    pub bit: Option<Bit<U>>,
+    /// Increments the value of the field by the specified amount.
+    pub inc: Option<U>,
+    /// Only updates the field if the specified value is greater than the existing field value.
+    pub max: Option<U>,
+    /// Only updates the field if the specified value is less than the existing field value.
+    pub min: Option<U>,
+    /// Multiplies the value of the field by the specified amount.
+    pub mul: Option<U>,
+    /// A raw update document for operators that are not supported, which is merged into the
+    /// generated update document.
+    pub raw: Option<Document>,
+    /// Renames a field.
+    pub rename: Option<Rename>,
     /// Sets the value of a field in a document.
     pub set: Option<U>,
+    /// Sets the value of a field if an update results in an insert of a document, having no
+    /// effect on updates that modify existing documents.
+    pub set_on_insert: Option<U>,
     /// Removes the specified field from a document.
     pub unset: Option<U>,
+    /// Removes the fields at the specified paths from a document, without needing a value for
+    /// them like `unset` does.
+    pub unset_fields: Vec<String>,
 }
 
 impl<U: Update> Updates<U> {
+    /// Merges a raw update document into the `Updates`, allowing unsupported operators to be
+    /// mixed with the typed ones.
+    ///
+    /// Fields of an operator that is also generated are merged, with those in the raw document
+    /// taking precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mongod::bson::{doc, Document};
+    /// use mongod::{Error, Update, Updates};
+    ///
+    /// #[derive(Default)]
+    /// pub struct UserUpdate {
+    ///     pub name: Option<String>,
+    /// }
+    ///
+    /// impl Update for UserUpdate {
+    ///     fn new() -> Self {
+    ///        UserUpdate::default()
+    ///     }
+    ///     fn into_document(self) -> Result<Document, Error> {
+    ///         let mut doc = Document::new();
+    ///         if let Some(value) = self.name {
+    ///             doc.insert("name", value);
+    ///         }
+    ///         Ok(doc)
+    ///     }
+    /// }
+    ///
+    /// let updates = Updates {
+    ///     set: Some(UserUpdate { name: Some("foo".to_owned()) }),
+    ///     ..Updates::default()
+    /// }
+    /// .raw(doc! { "$push": { "tags": "bar" }, "$set": { "updated": true } });
+    ///
+    /// assert_eq!(
+    ///     updates.into_document().unwrap(),
+    ///     doc! { "$set": { "name": "foo", "updated": true }, "$push": { "tags": "bar" } },
+    /// );
+    /// ```
+    pub fn raw(mut self, document: Document) -> Self {
+        match &mut self.raw {
+            Some(raw) => merge(raw, document),
+            None => self.raw = Some(document),
+        }
+        self
+    }
+
     /// Convert `Updates` into a BSON `Document`.
     pub fn into_document(self) -> Result<Document, Error> {
         let mut document = crate::bson::Document::new();
+        if let Some(bit) = self.bit {
+            document.insert("$bit", bit.into_document()?);
+        }
+        if let Some(inc) = self.inc {
+            document.insert("$inc", inc.into_document()?);
+        }
+        if let Some(max) = self.max {
+            document.insert("$max", max.into_document()?);
+        }
+        if let Some(min) = self.min {
+            document.insert("$min", min.into_document()?);
+        }
+        if let Some(mul) = self.mul {
+            document.insert("$mul", mul.into_document()?);
+        }
+        if let Some(rename) = self.rename {
+            document.insert("$rename", rename.into_document());
+        }
+        let mut unset_fields = self.unset_fields;
         if let Some(set) = self.set {
-            document.insert("$set", set.into_document()?);
+            let (set, unset) = set.into_set_document()?;
+            if !set.is_empty() || unset.is_empty() {
+                document.insert("$set", set);
+            }
+            unset_fields.extend(unset);
+        }
+        if let Some(set_on_insert) = self.set_on_insert {
+            document.insert("$setOnInsert", set_on_insert.into_document()?);
+        }
+        if self.unset.is_some() || !unset_fields.is_empty() {
+            let mut unset = match self.unset {
+                Some(unset) => unset.into_document()?,
+                None => Document::new(),
+            };
+            for field in unset_fields {
+                unset.insert(field, "");
+            }
+            document.insert("$unset", unset);
         }
-        if let Some(unset) = self.unset {
-            document.insert("$unset", unset.into_document()?);
+        if let Some(raw) = self.raw {
+            merge(&mut document, raw);
+        }
+        Ok(document)
+    }
+}
+
+pub(crate) fn merge(document: &mut Document, other: Document) {
+    for (op, value) in other {
+        match (document.get_mut(&op), value) {
+            (Some(Bson::Document(existing)), Bson::Document(fields)) => existing.extend(fields),
+            (_, value) => {
+                document.insert(op, value);
+            }
+        }
+    }
+}
+
+/// The bitwise operations of a `$bit` update, where each holds the integer to apply to the field.
+#[derive(Default)]
+pub struct Bit<U: Update> {
+    /// Performs a bitwise `and` of the field with the specified integer.
+    pub and: Option<U>,
+    /// Performs a bitwise `or` of the field with the specified integer.
+    pub or: Option<U>,
+    /// Performs a bitwise `xor` of the field with the specified integer.
+    pub xor: Option<U>,
+}
+
+impl<U: Update> Bit<U> {
+    /// Convert `Bit` into a BSON `Document`.
+    pub fn into_document(self) -> Result<Document, Error> {
+        let mut document = Document::new();
+        for (op, update) in [("and", self.and), ("or", self.or), ("xor", self.xor)] {
+            let update = match update {
+                Some(update) => update.into_document()?,
+                None => continue,
+            };
+            for (field, value) in update {
+                match document.get_mut(&field) {
+                    Some(Bson::Document(ops)) => {
+                        ops.insert(op, value);
+                    }
+                    _ => {
+                        document.insert(field, doc! { op: value });
+                    }
+                }
+            }
         }
         Ok(document)
     }
 }
 
+/// The array elements targeted by a positional update.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Position {
+    /// Targets all elements in the array, using `$[]`.
+    All,
+    /// Targets the elements that match the array filter with the identifier, using
+    /// `$[<identifier>]`.
+    Filtered(String),
+    /// Targets the first element that matched the query filter, using `$`.
+    First,
+}
+
+impl Position {
+    /// Returns the update path targeting the elements of the array `field`.
+    pub fn path(&self, field: &str) -> String {
+        match self {
+            Position::All => format!("{}.$[]", field),
+            Position::Filtered(identifier) => format!("{}.$[{}]", field, identifier),
+            Position::First => format!("{}.$", field),
+        }
+    }
+}
+
+/// The fields to rename in a `$rename` update, mapping the old field name to the new one.
+///
+/// # Examples
+///
+/// Renaming a user's `name` to `username`, along with a field that is no longer in the schema.
+///
+/// ```
+/// # mod wrapper {
+/// # use mongod_derive::{Bson, Mongo};
+/// use mongod::Rename;
+///
+/// #[derive(Bson, Mongo)]
+/// #[mongo(collection = "users", field)]
+/// pub struct User {
+///     name: String,
+///     username: String,
+/// }
+///
+/// # pub fn main() {
+/// let rename = Rename::new()
+///     .field(user::Field::Name, user::Field::Username)
+///     .path("nickname", "alias");
+///
+/// assert_eq!(
+///     rename.into_document(),
+///     mongod::bson::doc! { "name": "username", "nickname": "alias" },
+/// );
+/// # }
+/// # }
+/// # wrapper::main();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Rename(Vec<(String, String)>);
+
+impl Rename {
+    /// Creates an empty `Rename`.
+    pub fn new() -> Self {
+        Rename(vec![])
+    }
+
+    /// Renames the field `from` to `to`.
+    pub fn field<F: Field + Into<String>>(mut self, from: F, to: F) -> Self {
+        self.0.push((from.into(), to.into()));
+        self
+    }
+
+    /// Renames the field at the path `from` to `to`, for fields that are not part of a `Field`
+    /// such as those that have been removed from the schema.
+    pub fn path<S: Into<String>>(mut self, from: S, to: S) -> Self {
+        self.0.push((from.into(), to.into()));
+        self
+    }
+
+    /// Converts the `Rename` into a BSON [`Document`](bson::Document).
+    pub fn into_document(self) -> Document {
+        let mut doc = Document::new();
+        for (from, to) in self.0 {
+            doc.insert(from, to);
+        }
+        doc
+    }
+}
+
 impl<U: Default + Update> Update for Updates<U> {
     fn new() -> Self {
         Updates::default()
@@ -204,3 +543,97 @@ impl<U: Default + Update> Update for Updates<U> {
         self.into_document()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct UserUpdate {
+        pub age: Option<u32>,
+    }
+
+    impl Update for UserUpdate {
+        fn new() -> Self {
+            UserUpdate::default()
+        }
+        fn into_document(self) -> Result<Document, Error> {
+            let mut doc = Document::new();
+            if let Some(value) = self.age {
+                doc.insert("age", value);
+            }
+            Ok(doc)
+        }
+    }
+
+    #[test]
+    fn updates_numeric_operators() {
+        let updates = Updates {
+            max: Some(UserUpdate { age: Some(100) }),
+            min: Some(UserUpdate { age: Some(18) }),
+            mul: Some(UserUpdate { age: Some(2) }),
+            ..Updates::default()
+        };
+        assert_eq!(
+            updates.into_document().unwrap(),
+            doc! {
+                "$max": { "age": 100 },
+                "$min": { "age": 18 },
+                "$mul": { "age": 2 },
+            }
+        );
+    }
+
+    #[test]
+    fn updates_unset_fields() {
+        let updates = Updates {
+            inc: Some(UserUpdate { age: Some(1) }),
+            unset: Some(UserUpdate { age: Some(0) }),
+            unset_fields: vec!["email".to_owned()],
+            ..Updates::default()
+        };
+        assert_eq!(
+            updates.into_document().unwrap(),
+            doc! { "$inc": { "age": 1 }, "$unset": { "age": 0, "email": "" } }
+        );
+    }
+
+    #[test]
+    fn updates_bit() {
+        let updates = Updates {
+            bit: Some(Bit {
+                and: Some(UserUpdate { age: Some(0b1010) }),
+                or: Some(UserUpdate { age: Some(0b0001) }),
+                xor: None,
+            }),
+            ..Updates::default()
+        };
+        assert_eq!(
+            updates.into_document().unwrap(),
+            doc! { "$bit": { "age": { "and": 0b1010, "or": 0b0001 } } }
+        );
+    }
+
+    #[test]
+    fn updates_set_on_insert() {
+        let updates = Updates {
+            set_on_insert: Some(UserUpdate { age: Some(0) }),
+            ..Updates::default()
+        };
+        assert_eq!(
+            updates.into_document().unwrap(),
+            doc! { "$setOnInsert": { "age": 0 } }
+        );
+    }
+
+    #[test]
+    fn field_update_serialize() {
+        let to_bson = |update: FieldUpdate<u32>| bson::to_bson(&update).unwrap();
+        assert_eq!(to_bson(FieldUpdate::Set(1)), Bson::Int64(1));
+        assert_eq!(to_bson(FieldUpdate::Null), Bson::Null);
+        assert_eq!(FieldUpdate::from(Some(1)), FieldUpdate::Set(1));
+        assert_eq!(FieldUpdate::<u32>::from(None), FieldUpdate::Null);
+        assert!(FieldUpdate::<u32>::default().is_keep());
+        assert!(FieldUpdate::<u32>::Unset.is_skipped());
+    }
+}
@@ -0,0 +1,200 @@
+//! Checks used by the `Mongo` derive's `#[mongo(validate(...))]` field attribute.
+//!
+//! Each check returns a `Kind::InvalidDocument` error naming the offending field, so that invalid
+//! data is rejected before it is sent to mongo.
+//!
+//! - `min = ...` & `max = ...` bound the value of the field
+//! - `length(min = ..., max = ...)` bounds the [`Length`] of the field
+//! - `regex = "..."` requires the field to match the pattern, using the `regex` feature
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+use crate::error::Error;
+
+/// The length of a value, as checked by `length(...)`.
+///
+/// Strings are measured in characters rather than bytes.
+pub trait Length {
+    /// Returns the length of the value.
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T: Length + ?Sized> Length for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for VecDeque<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for HashSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for HashMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Checks that `value` is not less than `min`.
+pub fn min<T: PartialOrd + Display>(field: &str, value: &T, min: T) -> Result<(), Error> {
+    if *value < min {
+        return Err(Error::invalid_document(format!(
+            "field `{}` must be at least {}",
+            field, min
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that `value` is not greater than `max`.
+pub fn max<T: PartialOrd + Display>(field: &str, value: &T, max: T) -> Result<(), Error> {
+    if *value > max {
+        return Err(Error::invalid_document(format!(
+            "field `{}` must be at most {}",
+            field, max
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the length of `value` is within the optional bounds.
+pub fn length<T: Length + ?Sized>(
+    field: &str,
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<(), Error> {
+    let length = value.length();
+    if let Some(min) = min {
+        if length < min {
+            return Err(Error::invalid_document(format!(
+                "field `{}` must have a length of at least {}",
+                field, min
+            )));
+        }
+    }
+    if let Some(max) = max {
+        if length > max {
+            return Err(Error::invalid_document(format!(
+                "field `{}` must have a length of at most {}",
+                field, max
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A lazily compiled regular expression, checked by `regex = "..."`.
+///
+/// The pattern is compiled on first use, an invalid pattern fails every check.
+#[cfg(feature = "regex")]
+pub struct Pattern {
+    pattern: &'static str,
+    regex: std::sync::OnceLock<Result<regex::Regex, regex::Error>>,
+}
+
+#[cfg(feature = "regex")]
+impl Pattern {
+    /// Constructs a new `Pattern`.
+    pub const fn new(pattern: &'static str) -> Self {
+        Pattern {
+            pattern,
+            regex: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Checks that `value` matches the pattern.
+    pub fn check<T: AsRef<str> + ?Sized>(&self, field: &str, value: &T) -> Result<(), Error> {
+        let regex = self
+            .regex
+            .get_or_init(|| regex::Regex::new(self.pattern))
+            .as_ref()
+            .map_err(|e| Error::invalid_document(e.clone()))?;
+        if !regex.is_match(value.as_ref()) {
+            return Err(Error::invalid_document(format!(
+                "field `{}` must match the pattern `{}`",
+                field, self.pattern
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        assert!(min("age", &18, 0).is_ok());
+        assert!(min("age", &-1, 0).is_err());
+        assert!(max("age", &150, 150).is_ok());
+        assert!(max("age", &151, 150).is_err());
+        let err = max("age", &151, 150).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid document: field `age` must be at most 150"
+        );
+    }
+
+    #[test]
+    fn lengths() {
+        assert!(length("name", "föö", Some(3), Some(3)).is_ok());
+        assert!(length("name", "", Some(1), None).is_err());
+        assert!(length("tags", &vec![1, 2, 3], None, Some(2)).is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn pattern() {
+        static PATTERN: Pattern = Pattern::new("^[a-z]+$");
+        assert!(PATTERN.check("name", "foo").is_ok());
+        assert!(PATTERN.check("name", "Foo").is_err());
+        assert!(Pattern::new("(").check("name", "foo").is_err());
+    }
+}
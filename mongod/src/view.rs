@@ -0,0 +1,66 @@
+use bson::Document;
+
+use crate::collection::Collection;
+use crate::error::Error;
+
+/// Used to read a projection of a collection's documents into a type.
+///
+/// A view is a subset of a collection's fields, only the fields in its
+/// [`projection`](`View::projection`) are returned by the server. Views are queried using
+/// [`Find::into_view`](`crate::query::Find::into_view`) and can be derived using
+/// `#[mongo(view(...))]`.
+///
+/// # Examples
+///
+/// Defining a view by hand.
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use mongod::bson::{doc, Document};
+/// use mongod::{Collection, Error, View};
+/// use mongod::ext;
+///
+/// pub struct User {
+///     pub name: String,
+///     pub age: u32,
+/// }
+///
+/// # impl Collection for User {
+/// #     const COLLECTION: &'static str = "users";
+/// #     fn from_document(_: Document) -> Result<Self, Error> { unimplemented!() }
+/// #     fn into_document(self) -> Result<Document, Error> { unimplemented!() }
+/// # }
+/// #
+/// pub struct UserName {
+///     pub name: String,
+/// }
+///
+/// impl View for UserName {
+///     type Collection = User;
+///
+///     fn projection() -> Document {
+///         doc! { "_id": 0, "name": 1 }
+///     }
+///
+///     fn from_document(document: Document) -> Result<Self, Error> {
+///         let mut document = document;
+///         let name = document
+///             .remove("name")
+///             .ok_or_else(|| Error::invalid_document("missing required fields"))?;
+///         Ok(Self {
+///             name: String::try_from(ext::bson::Bson(name))?,
+///         })
+///     }
+/// }
+/// ```
+pub trait View: Sized {
+    /// The collection that this is a view of.
+    type Collection: Collection;
+
+    /// The projection used to fetch the view's fields.
+    fn projection() -> Document;
+
+    /// Convert from a projected BSON `Document` into the view.
+    fn from_document(document: Document) -> Result<Self, Error>;
+}
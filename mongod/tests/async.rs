@@ -1,5 +1,9 @@
 use futures::stream::StreamExt;
+use mongod::bson::{doc, Bson};
+use mongod::options::SessionOptions;
+use mongod::query::{Aggregate, Find};
 use mongod::{AsFilter, AsUpdate, Comparator, Updates};
+use mongod_derive::{Bson, Mongo};
 
 use user::User;
 
@@ -57,3 +61,148 @@ async fn async_client() {
     let deleted = client.delete::<User, _>(None).await.unwrap();
     assert_eq!(deleted, 2);
 }
+
+#[derive(Bson, Mongo)]
+#[mongo(collection = "accounts", filter)]
+struct Account {
+    #[mongo(id)]
+    email: String,
+    name: String,
+}
+
+#[derive(Bson, Mongo)]
+#[mongo(collection = "tickets", filter)]
+struct Ticket {
+    #[mongo(id)]
+    number: i64,
+    title: String,
+}
+
+#[tokio::test]
+async fn async_client_natural_ids() {
+    // NOTE: A database of its own so that it is not dropped by the other tests
+    let client = mongod::Client::builder()
+        .database("mongod_ids")
+        .build()
+        .unwrap();
+    client.database().drop(None).await.unwrap();
+
+    // String
+    let account = Account {
+        email: "foo@bar.com".to_owned(),
+        name: "foo".to_owned(),
+    };
+    let id = client.insert_one(account).await.unwrap();
+    assert_eq!(id, Bson::String("foo@bar.com".to_owned()));
+
+    let mut filter = Account::filter();
+    filter.email = Some(Comparator::Eq("foo@bar.com".to_owned()));
+    let (id, account) = client
+        .find_one::<Account, _>(filter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id, Bson::String("foo@bar.com".to_owned()));
+    assert_eq!(account.email, "foo@bar.com");
+    assert_eq!(account.name, "foo");
+
+    // Integer
+    let tickets = vec![
+        Ticket {
+            number: 1,
+            title: "foo".to_owned(),
+        },
+        Ticket {
+            number: 2,
+            title: "bar".to_owned(),
+        },
+    ];
+    let ids = client.insert(tickets).await.unwrap();
+    assert_eq!(ids.get(&0), Some(&Bson::Int64(1)));
+    assert_eq!(ids.get(&1), Some(&Bson::Int64(2)));
+
+    let mut cursor = client.find::<Ticket, _>(None).await.unwrap();
+    let mut numbers = vec![];
+    while let Some(res) = cursor.next().await {
+        let (id, ticket) = res.unwrap();
+        assert_eq!(id, Bson::Int64(ticket.number));
+        numbers.push(ticket.number);
+    }
+    numbers.sort_unstable();
+    assert_eq!(numbers, vec![1, 2]);
+
+    let ticket = client.get::<Ticket, _>(2_i64).await.unwrap().unwrap();
+    assert_eq!(ticket.title, "bar");
+}
+
+#[tokio::test]
+async fn async_client_session_cursors() {
+    // NOTE: A database of its own so that it is not dropped by the other tests
+    let client = mongod::Client::builder()
+        .database("mongod_sessions")
+        .build()
+        .unwrap();
+    client.database().drop(None).await.unwrap();
+
+    let tickets = (1..=3)
+        .map(|number| Ticket {
+            number,
+            title: number.to_string(),
+        })
+        .collect();
+    client.insert::<Ticket>(tickets).await.unwrap();
+
+    let options = SessionOptions::builder().causal_consistency(true).build();
+    let mut session = client.start_session(Some(options)).await.unwrap();
+
+    // Find
+    let mut cursor = Find::<Ticket>::new()
+        .query_with_session(&client, &mut session)
+        .await
+        .unwrap();
+    let mut numbers = vec![];
+    while let Some(res) = cursor.next().await {
+        let (id, ticket) = res.unwrap();
+        assert_eq!(id, Bson::Int64(ticket.number));
+        numbers.push(ticket.number);
+    }
+    numbers.sort_unstable();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    // Aggregate
+    let mut cursor = Aggregate::<Ticket>::new()
+        .stage(doc! { "$group": { "_id": null, "total": { "$sum": "$_id" } } })
+        .query_with_session(&client, &mut session)
+        .await
+        .unwrap();
+    let document = cursor.next().await.unwrap().unwrap();
+    assert_eq!(document.get_i64("total").unwrap(), 6);
+    assert!(cursor.next().await.is_none());
+
+    // Raw
+    let mut cursor = Find::<Ticket>::new()
+        .query_with_session(&client, &mut session)
+        .await
+        .unwrap()
+        .into_inner();
+    let document = cursor.next().await.unwrap().unwrap();
+    assert!(document.contains_key("title"));
+}
+
+#[tokio::test]
+async fn async_client_connect() {
+    let client = mongod::Client::builder().connect().await.unwrap();
+    client.database().list_collection_names(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn connect_unreachable() {
+    let res = mongod::Client::builder()
+        .uri("mongodb://127.0.0.1:1/?serverSelectionTimeoutMS=100")
+        .connect()
+        .await;
+    match res {
+        Err(e) => assert!(matches!(e.kind(), mongod::ErrorKind::Builder)),
+        Ok(_) => panic!("connect should fail for an unreachable server"),
+    }
+}
@@ -1,4 +1,5 @@
 mod common;
+#[allow(dead_code)]
 mod user;
 
 #[cfg(feature = "blocking")]
@@ -28,8 +29,8 @@ mod wrapper {
 
         // Fetch
         let mut count: u32 = 0;
-        let mut cursor = client.find::<User, _>(None).unwrap();
-        while let Some(res) = cursor.next() {
+        let cursor = client.find::<User, _>(None).unwrap();
+        for res in cursor {
             let (_id, _user) = res.unwrap();
             count += 1;
         }
@@ -1,13 +1,11 @@
-use mongod;
-
+#[cfg(feature = "blocking")]
 #[allow(dead_code)]
 pub fn setup() {
-    // FIXME: Until we impl database and collection on the blocking client we have to spawn in a
-    // runtime...
-    let rt = tokio::runtime::Runtime::new().unwrap();
-    rt.block_on(async_setup());
+    let client = mongod::blocking::Client::new();
+    client.database().drop().unwrap();
 }
 
+#[allow(dead_code)]
 pub async fn async_setup() {
     let client = mongod::Client::new();
     client.database().drop(None).await.unwrap();
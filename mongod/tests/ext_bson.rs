@@ -0,0 +1,73 @@
+//! Tests for the process wide settings of `ext::bson`.
+//!
+//! These run in their own process, as changing a setting would race with any other test
+//! converting the same types, and are serialized through `SETTINGS` for the same reason.
+
+use std::convert::TryFrom;
+use std::sync::{Mutex, MutexGuard};
+
+use mongod::bson;
+use mongod::ext::bson::{set_numeric_coercion, set_unsigned_wrapping, Bson};
+
+static SETTINGS: Mutex<()> = Mutex::new(());
+
+fn settings() -> MutexGuard<'static, ()> {
+    // A failed test must not fail the others
+    SETTINGS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn unsigned_wrapping() {
+    let _settings = settings();
+    set_unsigned_wrapping(true);
+    assert_eq!(Bson::from(u32::MAX).0, bson::Bson::Int32(-1));
+    assert_eq!(Bson::try_from(u64::MAX).unwrap().0, bson::Bson::Int64(-1));
+    assert_eq!(
+        u32::try_from(Bson(bson::Bson::Int32(-1))).unwrap(),
+        u32::MAX
+    );
+    assert_eq!(
+        u64::try_from(Bson(bson::Bson::Int64(-1))).unwrap(),
+        u64::MAX
+    );
+    set_unsigned_wrapping(false);
+    assert_eq!(Bson::from(u32::MAX).0, bson::Bson::Int64(u32::MAX as i64));
+}
+
+#[test]
+fn numeric_coercion() {
+    let _settings = settings();
+    set_numeric_coercion(true);
+    assert_eq!(i32::try_from(Bson(bson::Bson::Int64(1))).unwrap(), 1);
+    assert_eq!(u8::try_from(Bson(bson::Bson::Double(2.0))).unwrap(), 2);
+    assert_eq!(i64::try_from(Bson(bson::Bson::Int32(3))).unwrap(), 3);
+    assert_eq!(u64::try_from(Bson(bson::Bson::Double(4.0))).unwrap(), 4);
+    assert_eq!(f64::try_from(Bson(bson::Bson::Int64(5))).unwrap(), 5.0);
+    assert_eq!(f32::try_from(Bson(bson::Bson::Int32(6))).unwrap(), 6.0);
+    assert!(i32::try_from(Bson(bson::Bson::Int64(i64::MAX))).is_err());
+    assert!(i32::try_from(Bson(bson::Bson::Double(1.5))).is_err());
+    assert!(i64::try_from(Bson(bson::Bson::Double(9.3e18))).is_err());
+    assert!(f64::try_from(Bson(bson::Bson::Int64(i64::MAX))).is_err());
+    assert!(f32::try_from(Bson(bson::Bson::Int32(i32::MAX))).is_err());
+    assert!(i32::try_from(Bson(bson::Bson::String("1".to_owned()))).is_err());
+    set_numeric_coercion(false);
+    assert!(i32::try_from(Bson(bson::Bson::Int64(1))).is_err());
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_representation() {
+    use mongod::ext::bson::set_uuid_representation;
+    use mongod::options::UuidRepresentation;
+
+    let _settings = settings();
+    let v = uuid::Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+    set_uuid_representation(UuidRepresentation::JavaLegacy);
+    let b = Bson::from(v).0;
+    match &b {
+        bson::Bson::Binary(b) => assert_eq!(b.subtype, bson::spec::BinarySubtype::UuidOld),
+        _ => panic!("uuid should be binary"),
+    }
+    assert_eq!(uuid::Uuid::try_from(Bson(b)).unwrap(), v);
+    set_uuid_representation(UuidRepresentation::Standard);
+}